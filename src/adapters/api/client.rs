@@ -1,56 +1,110 @@
-use super::dto::{AsanaListResponse, AsanaResponse};
+use super::dto::{AsanaListResponse, AsanaResponse, SyncTokenDto};
+use super::retry::{Idempotency, RetryPolicy, TransientFailure};
+use crate::domain::Page;
 use crate::ports::{RepositoryError, RepositoryResult};
-use reqwest::{Client, Response};
+use reqwest::{Client, RequestBuilder, Response};
 use serde::de::DeserializeOwned;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-const ASANA_API_BASE: &str = "https://app.asana.com/api/1.0";
+/// Production Asana API; override with `--api-base`, `ASANA_API_BASE` or `api_base_url`
+pub const DEFAULT_API_BASE: &str = "https://app.asana.com/api/1.0";
+
+/// Largest page size Asana accepts for paginated list endpoints
+pub const MAX_PAGE_SIZE: usize = 100;
 
 pub struct AsanaClient {
     client: Client,
     api_token: String,
+    base_url: String,
+    retry_policy: RetryPolicy,
 }
 
 impl AsanaClient {
-    pub fn new(api_token: String) -> Self {
+    pub fn new(api_token: String, base_url: &str) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(30))
             .user_agent("asana-cli/0.1.0")
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client, api_token }
+        Self {
+            client,
+            api_token,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
     }
 
     pub async fn get<T: DeserializeOwned>(&self, path: &str) -> RepositoryResult<T> {
-        let url = format!("{ASANA_API_BASE}{path}");
+        let url = format!("{}{path}", self.base_url);
 
         let response = self
-            .client
-            .get(&url)
-            .bearer_auth(&self.api_token)
-            .send()
-            .await
-            .map_err(|e| RepositoryError::Network(e.to_string()))?;
+            .send(Idempotency::Idempotent, || self.client.get(&url))
+            .await?;
 
         self.handle_response(response).await
     }
 
+    /// Fetch every item of a list endpoint, following `next_page` cursors
     pub async fn get_list<T: DeserializeOwned>(&self, path: &str) -> RepositoryResult<Vec<T>> {
-        let url = format!("{ASANA_API_BASE}{path}");
+        Ok(self.get_list_up_to(path, None).await?.items)
+    }
+
+    /// Fetch a list endpoint page by page until `max_items` have been collected
+    /// or Asana reports no further pages.
+    ///
+    /// Any `limit`/`offset` already present in `path` are replaced: the page size
+    /// is chosen so the final request never over-fetches, which keeps the returned
+    /// cursor pointing exactly at the first item not included.
+    pub async fn get_list_up_to<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        max_items: Option<usize>,
+    ) -> RepositoryResult<Page<T>> {
+        let mut items = Vec::new();
+        let mut offset = query_param(path, "offset");
+
+        loop {
+            let remaining = max_items.map(|max| max.saturating_sub(items.len()));
+            let page_size = remaining.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+            if page_size == 0 {
+                break;
+            }
+
+            let mut page_path = set_query_param(path, "limit", &page_size.to_string());
+            if let Some(offset) = &offset {
+                page_path = set_query_param(&page_path, "offset", offset);
+            }
+
+            let page = self.get_page::<T>(&page_path).await?;
+            items.extend(page.items);
+            offset = page.next_offset;
+
+            if offset.is_none() {
+                break;
+            }
+        }
+
+        Ok(Page {
+            items,
+            next_offset: offset,
+        })
+    }
+
+    /// Fetch a single page of a list endpoint exactly as addressed by `path`
+    pub async fn get_page<T: DeserializeOwned>(&self, path: &str) -> RepositoryResult<Page<T>> {
+        let url = format!("{}{path}", self.base_url);
 
         let response = self
-            .client
-            .get(&url)
-            .bearer_auth(&self.api_token)
-            .send()
-            .await
-            .map_err(|e| RepositoryError::Network(e.to_string()))?;
-
-        let response_text = response
-            .text()
-            .await
-            .map_err(|e| RepositoryError::Network(e.to_string()))?;
+            .send(Idempotency::Idempotent, || self.client.get(&url))
+            .await?;
+
+        let response_text = self.read_success_body(response).await?;
 
         tracing::debug!("API List Response: {}", response_text);
 
@@ -60,7 +114,31 @@ impl AsanaClient {
                     "Failed to parse list response: {e}. Response was: {response_text}"
                 ))
             })?;
-        Ok(list_response.data)
+
+        Ok(Page {
+            items: list_response.data,
+            next_offset: list_response.next_page.map(|next| next.offset),
+        })
+    }
+
+    /// Fetch an endpoint whose response carries more than `data`, e.g. the
+    /// sync token beside `/events`
+    pub async fn get_body<T: DeserializeOwned>(&self, path: &str) -> RepositoryResult<T> {
+        let url = format!("{}{path}", self.base_url);
+
+        let response = self
+            .send(Idempotency::Idempotent, || self.client.get(&url))
+            .await?;
+
+        let response_text = self.read_success_body(response).await?;
+
+        tracing::debug!("API Response: {}", response_text);
+
+        serde_json::from_str(&response_text).map_err(|e| {
+            RepositoryError::Serialization(format!(
+                "Failed to parse response: {e}. Response was: {response_text}"
+            ))
+        })
     }
 
     pub async fn put<T: DeserializeOwned, R: serde::Serialize>(
@@ -68,16 +146,14 @@ impl AsanaClient {
         path: &str,
         body: &R,
     ) -> RepositoryResult<T> {
-        let url = format!("{ASANA_API_BASE}{path}");
+        let url = format!("{}{path}", self.base_url);
 
+        let body = serde_json::json!({ "data": body });
         let response = self
-            .client
-            .put(&url)
-            .bearer_auth(&self.api_token)
-            .json(&serde_json::json!({ "data": body }))
-            .send()
-            .await
-            .map_err(|e| RepositoryError::Network(e.to_string()))?;
+            .send(Idempotency::Idempotent, || {
+                self.client.put(&url).json(&body)
+            })
+            .await?;
 
         self.handle_response(response).await
     }
@@ -87,56 +163,104 @@ impl AsanaClient {
         path: &str,
         body: &R,
     ) -> RepositoryResult<T> {
-        let url = format!("{ASANA_API_BASE}{path}");
+        let url = format!("{}{path}", self.base_url);
 
+        let body = serde_json::json!({ "data": body });
         let response = self
-            .client
-            .post(&url)
-            .bearer_auth(&self.api_token)
-            .json(&serde_json::json!({ "data": body }))
-            .send()
-            .await
-            .map_err(|e| RepositoryError::Network(e.to_string()))?;
+            .send(Idempotency::NonIdempotent, || {
+                self.client.post(&url).json(&body)
+            })
+            .await?;
 
         self.handle_response(response).await
     }
 
+    /// Send a request, retrying transient failures according to the retry policy.
+    ///
+    /// `build` is called once per attempt since a `RequestBuilder` is consumed by sending.
+    /// The final response is returned as-is, so status mapping stays in one place.
+    async fn send(
+        &self,
+        idempotency: Idempotency,
+        build: impl Fn() -> RequestBuilder,
+    ) -> RepositoryResult<Response> {
+        let started = Instant::now();
+        let mut attempt = 1;
+
+        loop {
+            let (failure, outcome) = match build().bearer_auth(&self.api_token).send().await {
+                Ok(response) => match transient_status(&response) {
+                    Some(failure) => (failure, Ok(response)),
+                    None => return Ok(response),
+                },
+                Err(e) => {
+                    let failure = if e.is_connect() {
+                        TransientFailure::Connect
+                    } else {
+                        TransientFailure::Network
+                    };
+                    (failure, Err(RepositoryError::Network(e.to_string())))
+                }
+            };
+
+            let Some(delay) =
+                self.retry_policy
+                    .next_delay(failure, idempotency, attempt, started.elapsed())
+            else {
+                return outcome;
+            };
+
+            tracing::warn!(
+                "Request attempt {} failed ({:?}), retrying in {:?}",
+                attempt,
+                failure,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
     async fn handle_response<T: DeserializeOwned>(
         &self,
         response: Response,
     ) -> RepositoryResult<T> {
-        let status = response.status();
+        let response_text = self.read_success_body(response).await?;
 
-        match status.as_u16() {
-            200..=299 => {
-                let response_text = response
-                    .text()
-                    .await
-                    .map_err(|e| RepositoryError::Network(e.to_string()))?;
+        tracing::debug!("API Response: {}", response_text);
 
-                tracing::debug!("API Response: {}", response_text);
+        let asana_response: AsanaResponse<T> =
+            serde_json::from_str(&response_text).map_err(|e| {
+                RepositoryError::Serialization(format!(
+                    "Failed to parse response: {e}. Response was: {response_text}"
+                ))
+            })?;
+        Ok(asana_response.data)
+    }
 
-                let asana_response: AsanaResponse<T> = serde_json::from_str(&response_text)
-                    .map_err(|e| {
-                        RepositoryError::Serialization(format!(
-                            "Failed to parse response: {e}. Response was: {response_text}"
-                        ))
-                    })?;
-                Ok(asana_response.data)
-            }
+    /// Map non-2xx statuses to repository errors, returning the body otherwise
+    async fn read_success_body(&self, response: Response) -> RepositoryResult<String> {
+        let status = response.status();
+
+        match status.as_u16() {
+            200..=299 => response
+                .text()
+                .await
+                .map_err(|e| RepositoryError::Network(e.to_string())),
             401 => Err(RepositoryError::Authentication(
                 "Invalid API token".to_string(),
             )),
             404 => Err(RepositoryError::NotFound("Resource not found".to_string())),
-            429 => {
-                // Extract retry-after header if available
-                let retry_after = response
-                    .headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(60);
-                Err(RepositoryError::RateLimit(retry_after))
+            429 => Err(RepositoryError::RateLimit(
+                retry_after(&response)
+                    .unwrap_or_else(|| self.retry_policy.backoff(1).as_secs().max(1)),
+            )),
+            412 => {
+                let error_text = response.text().await.unwrap_or_default();
+                match serde_json::from_str::<SyncTokenDto>(&error_text) {
+                    Ok(token) => Err(RepositoryError::SyncExpired(token.sync)),
+                    Err(_) => Err(RepositoryError::Api(format!("HTTP {status}: {error_text}"))),
+                }
             }
             _ => {
                 let error_text = response
@@ -148,3 +272,80 @@ impl AsanaClient {
         }
     }
 }
+
+/// Classify a response status as retryable, if it is
+fn transient_status(response: &Response) -> Option<TransientFailure> {
+    let status = response.status();
+    if status.as_u16() == 429 {
+        Some(TransientFailure::RateLimited(
+            retry_after(response).map(Duration::from_secs),
+        ))
+    } else if status.is_server_error() {
+        Some(TransientFailure::ServerError)
+    } else {
+        None
+    }
+}
+
+/// Seconds to wait according to the `Retry-After` header, if it has a usable one
+fn retry_after(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse().ok())
+}
+
+/// Read a (decoded) query parameter from a request path
+fn query_param(path: &str, key: &str) -> Option<String> {
+    let (_, query) = path.split_once('?')?;
+    query.split('&').find_map(|pair| {
+        let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+        (k == key).then(|| {
+            urlencoding::decode(v)
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| v.to_string())
+        })
+    })
+}
+
+/// Set a query parameter on a request path, replacing any existing value
+fn set_query_param(path: &str, key: &str, value: &str) -> String {
+    let (base, query) = path.split_once('?').unwrap_or((path, ""));
+
+    let mut pairs: Vec<String> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(key))
+        .map(str::to_string)
+        .collect();
+    pairs.push(format!("{key}={}", urlencoding::encode(value)));
+
+    format!("{base}?{}", pairs.join("&"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_query_param() {
+        assert_eq!(set_query_param("/tasks", "limit", "50"), "/tasks?limit=50");
+        assert_eq!(
+            set_query_param("/tasks?workspace=1&limit=10", "limit", "50"),
+            "/tasks?workspace=1&limit=50"
+        );
+        assert_eq!(
+            set_query_param("/tasks?workspace=1", "offset", "eyJ0eXA=="),
+            "/tasks?workspace=1&offset=eyJ0eXA%3D%3D"
+        );
+    }
+
+    #[test]
+    fn test_query_param_round_trip() {
+        let path = set_query_param("/tasks?workspace=1", "offset", "eyJ0eXA==");
+        assert_eq!(query_param(&path, "offset").as_deref(), Some("eyJ0eXA=="));
+        assert_eq!(query_param(&path, "workspace").as_deref(), Some("1"));
+        assert_eq!(query_param(&path, "limit"), None);
+        assert_eq!(query_param("/tasks", "offset"), None);
+    }
+}
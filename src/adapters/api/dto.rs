@@ -1,5 +1,5 @@
 use crate::domain::*;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 // Asana API response wrapper
@@ -11,9 +11,29 @@ pub struct AsanaResponse<T> {
 #[derive(Debug, Deserialize)]
 pub struct AsanaListResponse<T> {
     pub data: Vec<T>,
-    // Pagination not implemented - field kept for API compatibility
-    #[allow(dead_code)]
-    pub next_page: Option<serde_json::Value>,
+    pub next_page: Option<NextPageDto>,
+}
+
+// `/events` answers with the token to continue from beside the events
+#[derive(Debug, Deserialize)]
+pub struct EventsResponse {
+    pub data: Vec<EventDto>,
+    pub sync: String,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+// The 412 a missing or expired sync token gets, carrying a fresh token
+#[derive(Debug, Deserialize)]
+pub struct SyncTokenDto {
+    pub sync: String,
+}
+
+// Cursor for the next page of a paginated list; `null` on the last page.
+// Asana also sends `path` and `uri`, but the request is rebuilt from the offset.
+#[derive(Debug, Deserialize)]
+pub struct NextPageDto {
+    pub offset: String,
 }
 
 // DTOs for API communication
@@ -24,8 +44,9 @@ pub struct TaskDto {
     pub notes: Option<String>,
     pub html_notes: Option<String>,
     pub completed: bool,
-    pub due_on: Option<String>, // YYYY-MM-DD format
-    pub due_at: Option<String>, // ISO 8601 format
+    pub due_on: Option<String>,   // YYYY-MM-DD format
+    pub due_at: Option<String>,   // ISO 8601 format
+    pub start_on: Option<String>, // YYYY-MM-DD format
     pub assignee: Option<UserDto>,
     pub projects: Vec<ProjectDto>,
     pub tags: Vec<TagDto>,
@@ -36,6 +57,28 @@ pub struct TaskDto {
     pub resource_subtype: Option<String>,
     pub custom_fields: Option<Vec<CustomFieldDto>>,
     pub dependencies: Option<Vec<TaskCompactDto>>,
+    pub memberships: Option<Vec<MembershipDto>>,
+    pub parent: Option<ResourceRefDto>,
+    pub num_subtasks: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MembershipDto {
+    pub project: Option<ResourceRefDto>,
+    pub section: Option<SectionDto>,
+}
+
+// A reference to another record by gid alone
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResourceRefDto {
+    pub gid: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SectionDto {
+    pub gid: String,
+    pub name: String,
+    pub project: Option<ResourceRefDto>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -111,6 +154,19 @@ pub struct CommentDto {
     pub resource_subtype: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct EventDto {
+    pub action: String,
+    pub resource: EventResourceDto,
+    pub parent: Option<EventResourceDto>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventResourceDto {
+    pub gid: String,
+    pub resource_type: String,
+}
+
 // Request DTOs
 #[derive(Debug, Serialize)]
 pub struct TaskUpdateDto {
@@ -119,33 +175,58 @@ pub struct TaskUpdateDto {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub completed: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub due_on: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_on: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub assignee: Option<Option<String>>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct TaskCreateDto {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_on: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct CommentCreateDto {
     pub text: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct SectionAddTaskDto {
+    pub task: String,
+}
+
 // Conversion implementations
 impl From<TaskDto> for Task {
     fn from(dto: TaskDto) -> Self {
-        // Parse due date - prefer due_at over due_on
-        let due_date = if let Some(due_at) = dto.due_at {
-            DateTime::parse_from_rfc3339(&due_at)
-                .map(|dt| dt.with_timezone(&Utc))
-                .ok()
-        } else if let Some(due_on) = dto.due_on {
-            // Parse YYYY-MM-DD and assume UTC midnight
-            chrono::NaiveDate::parse_from_str(&due_on, "%Y-%m-%d")
-                .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc())
-                .ok()
-        } else {
-            None
+        // A due_at means a specific moment; due_on alone is an all-day date
+        let due = match (dto.due_at, dto.due_on) {
+            (Some(due_at), _) => DateTime::parse_from_rfc3339(&due_at)
+                .map(|dt| Due::At(dt.with_timezone(&Utc)))
+                .ok(),
+            (None, Some(due_on)) => parse_date(&due_on).map(Due::On),
+            (None, None) => None,
         };
 
         Self {
@@ -153,7 +234,8 @@ impl From<TaskDto> for Task {
             name: dto.name,
             description: dto.html_notes.or(dto.notes),
             completed: dto.completed,
-            due_date,
+            due,
+            start_on: dto.start_on.as_deref().and_then(parse_date),
             assignee: dto.assignee.as_ref().map(|u| UserId(u.gid.clone())),
             assignee_name: dto.assignee.map(|u| u.name),
             projects: dto.projects.into_iter().map(|p| p.into()).collect(),
@@ -183,6 +265,22 @@ impl From<TaskDto> for Task {
                 .into_iter()
                 .map(|d| d.into())
                 .collect(),
+            memberships: dto
+                .memberships
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|m| {
+                    Some(TaskMembership {
+                        project: ProjectId(m.project?.gid),
+                        section: m.section.map(|s| TaskSection {
+                            id: SectionId(s.gid),
+                            name: s.name,
+                        }),
+                    })
+                })
+                .collect(),
+            parent: dto.parent.map(|p| TaskId(p.gid)),
+            num_subtasks: dto.num_subtasks.unwrap_or(0),
         }
     }
 }
@@ -267,6 +365,20 @@ impl From<ProjectDto> for Project {
     }
 }
 
+impl From<SectionDto> for Section {
+    fn from(dto: SectionDto) -> Self {
+        Self {
+            id: SectionId(dto.gid),
+            name: dto.name,
+            project: ProjectId(
+                dto.project
+                    .map(|p| p.gid)
+                    .unwrap_or_else(|| "unknown".to_string()),
+            ),
+        }
+    }
+}
+
 impl From<WorkspaceDto> for Workspace {
     fn from(dto: WorkspaceDto) -> Self {
         Self {
@@ -293,16 +405,211 @@ impl From<CommentDto> for Comment {
     }
 }
 
+impl EventDto {
+    /// The task this event is about, if any: the resource itself, or the task
+    /// a story belongs to. Events on sections, projects and the like are `None`.
+    pub fn into_task_event(self) -> Option<TaskEvent> {
+        let action = match self.action.as_str() {
+            "added" => EventAction::Added,
+            "changed" => EventAction::Changed,
+            "removed" => EventAction::Removed,
+            "deleted" => EventAction::Deleted,
+            "undeleted" => EventAction::Undeleted,
+            _ => return None,
+        };
+
+        let (task, story) = match self.resource.resource_type.as_str() {
+            "task" => (self.resource.gid, false),
+            "story" => (
+                self.parent
+                    .filter(|parent| parent.resource_type == "task")?
+                    .gid,
+                true,
+            ),
+            _ => return None,
+        };
+
+        Some(TaskEvent {
+            task: TaskId(task),
+            action,
+            story,
+        })
+    }
+}
+
+impl From<NewTask> for TaskCreateDto {
+    fn from(task: NewTask) -> Self {
+        Self {
+            name: task.name,
+            notes: task.description,
+            workspace: task.workspace.map(|w| w.0),
+            projects: task.projects.into_iter().map(|p| p.0).collect(),
+            assignee: task.assignee.map(|u| u.0),
+            due_on: task.due.and_then(|due| due_on(&due)),
+            due_at: task.due.and_then(|due| due_at(&due)),
+            start_on: task.start_on.map(|date| format_date(&date)),
+        }
+    }
+}
+
 impl From<TaskUpdate> for TaskUpdateDto {
     fn from(update: TaskUpdate) -> Self {
         Self {
             name: update.name,
             notes: update.description,
+            html_notes: update.html_description,
             completed: update.completed,
+            // Clearing goes through due_on: null, which clears due_at as well
             due_on: update
-                .due_date
-                .map(|opt_date| opt_date.map(|date| date.format("%Y-%m-%d").to_string())),
+                .due
+                .and_then(|opt_due| opt_due.map_or(Some(None), |due| due_on(&due).map(Some))),
+            due_at: update.due.flatten().and_then(|due| due_at(&due)),
+            start_on: update
+                .start_on
+                .map(|opt_date| opt_date.map(|date| format_date(&date))),
             assignee: update.assignee.map(|opt_user| opt_user.map(|user| user.0)),
         }
     }
 }
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+fn format_date(date: &NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn due_on(due: &Due) -> Option<String> {
+    match due {
+        Due::On(date) => Some(format_date(date)),
+        Due::At(_) => None,
+    }
+}
+
+fn due_at(due: &Due) -> Option<String> {
+    match due {
+        Due::On(_) => None,
+        Due::At(instant) => Some(instant.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support as fake;
+
+    fn task_dto(value: serde_json::Value) -> TaskDto {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_task_dto_conversion() {
+        let mut json = fake::task("1", "Write tests");
+        json["html_notes"] = serde_json::json!("<body><strong>Rich</strong></body>");
+        json["notes"] = serde_json::json!("Plain");
+        json["projects"] =
+            serde_json::json!([{ "gid": "p1", "name": "Roadmap", "color": "dark-blue" }]);
+        json["tags"] = serde_json::json!([{ "gid": "t1", "name": "bug" }]);
+        json["memberships"] = serde_json::json!([
+            { "project": { "gid": "p1" }, "section": { "gid": "s2", "name": "Doing" } },
+            { "project": { "gid": "p2" }, "section": null }
+        ]);
+        json["custom_fields"] = serde_json::json!([{
+            "gid": "cf1",
+            "name": "Priority",
+            "display_value": "High",
+            "enum_value": { "gid": "e1", "name": "High", "color": "red" }
+        }]);
+
+        let task: Task = task_dto(json).into();
+
+        assert_eq!(task.id, TaskId::from("1"));
+        assert_eq!(
+            task.description.as_deref(),
+            Some("<body><strong>Rich</strong></body>")
+        );
+        assert_eq!(task.assignee, Some(UserId::from("u1")));
+        assert_eq!(task.assignee_name.as_deref(), Some("Test User"));
+        assert_eq!(task.projects[0].name, "Roadmap");
+        assert_eq!(task.tags, vec!["bug".to_string()]);
+        assert_eq!(task.section_in(&"p1".into()).unwrap().name, "Doing");
+        assert!(task.section_in(&"p2".into()).is_none());
+        assert_eq!(task.parent, None);
+        assert!(!task.has_subtasks());
+        assert_eq!(task.workspace, WorkspaceId::from("w1"));
+        assert_eq!(
+            task.custom_fields[0].enum_value.as_ref().unwrap().name,
+            "High"
+        );
+    }
+
+    #[test]
+    fn test_task_dto_due_dates() {
+        let mut json = fake::task("1", "Due");
+        json["due_on"] = serde_json::json!("2025-03-04");
+        json["start_on"] = serde_json::json!("2025-03-01");
+        let task: Task = task_dto(json.clone()).into();
+        assert_eq!(task.due, Some(Due::On("2025-03-04".parse().unwrap())));
+        assert_eq!(task.start_on, Some("2025-03-01".parse().unwrap()));
+
+        json["due_at"] = serde_json::json!("2025-03-04T15:30:00.000Z");
+        let task: Task = task_dto(json).into();
+        assert_eq!(
+            task.due,
+            Some(Due::At("2025-03-04T15:30:00Z".parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_task_dto_tolerates_missing_optional_fields() {
+        let mut json = fake::task("1", "Sparse");
+        json["workspace"] = serde_json::Value::Null;
+        json["custom_fields"] = serde_json::Value::Null;
+        json["created_at"] = serde_json::json!("not a date");
+
+        let task: Task = task_dto(json).into();
+        assert_eq!(task.workspace, WorkspaceId::from("unknown"));
+        assert!(task.custom_fields.is_empty());
+    }
+
+    #[test]
+    fn test_task_update_dto_serialization() {
+        let clear_due = TaskUpdateDto::from(TaskUpdate {
+            due: Some(None),
+            ..Default::default()
+        });
+        assert_eq!(
+            serde_json::to_value(clear_due).unwrap(),
+            serde_json::json!({ "due_on": null })
+        );
+
+        let timed = TaskUpdateDto::from(TaskUpdate {
+            due: Some(Some(Due::At("2025-03-04T15:30:00Z".parse().unwrap()))),
+            start_on: Some(Some("2025-03-01".parse().unwrap())),
+            ..Default::default()
+        });
+        assert_eq!(
+            serde_json::to_value(timed).unwrap(),
+            serde_json::json!({ "due_at": "2025-03-04T15:30:00.000Z", "start_on": "2025-03-01" })
+        );
+
+        let rename = TaskUpdateDto::from(TaskUpdate {
+            name: Some("New".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            serde_json::to_value(rename).unwrap(),
+            serde_json::json!({ "name": "New" })
+        );
+
+        let describe = TaskUpdateDto::from(TaskUpdate {
+            html_description: Some("<body><em>Hi</em></body>".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            serde_json::to_value(describe).unwrap(),
+            serde_json::json!({ "html_notes": "<body><em>Hi</em></body>" })
+        );
+    }
+}
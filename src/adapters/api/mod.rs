@@ -1,7 +1,11 @@
 pub mod client;
 pub mod dto;
+pub mod retry;
 pub mod task_repo;
+#[cfg(test)]
+mod tests;
 
 pub use client::*;
 pub use dto::*;
+pub use retry::*;
 pub use task_repo::*;
@@ -0,0 +1,203 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Whether repeating a request can change server state more than once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idempotency {
+    /// GET/PUT: safe to resend after any transient failure
+    Idempotent,
+    /// POST: only resent when we know the server never acted on it
+    /// (rate limited, or the connection was never established)
+    NonIdempotent,
+}
+
+/// Why a request attempt failed, as far as retrying is concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransientFailure {
+    /// HTTP 429, with the server's `Retry-After` delay when it sent one
+    RateLimited(Option<Duration>),
+    /// HTTP 5xx
+    ServerError,
+    /// The connection could not be established; nothing reached the server
+    Connect,
+    /// Timeouts, resets and other failures after the request may have been sent
+    Network,
+}
+
+impl TransientFailure {
+    fn is_safe_for(self, idempotency: Idempotency) -> bool {
+        match idempotency {
+            Idempotency::Idempotent => true,
+            Idempotency::NonIdempotent => {
+                matches!(self, Self::RateLimited(_) | Self::Connect)
+            }
+        }
+    }
+}
+
+/// Retry policy for transient API failures.
+///
+/// Rate limits wait for the server's `Retry-After`; everything else, and rate
+/// limits without one, uses full-jitter exponential backoff starting at `base_delay`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one; `1` disables retrying
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Give up rather than sleep past this much total elapsed time
+    pub deadline: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            deadline: Some(Duration::from_secs(90)),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Decide how long to wait before the next attempt, or `None` to give up.
+    ///
+    /// `attempt` is the 1-based number of the attempt that just failed and
+    /// `elapsed` is the time spent since the first attempt started.
+    pub fn next_delay(
+        &self,
+        failure: TransientFailure,
+        idempotency: Idempotency,
+        attempt: u32,
+        elapsed: Duration,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !failure.is_safe_for(idempotency) {
+            return None;
+        }
+
+        let delay = match failure {
+            TransientFailure::RateLimited(Some(retry_after)) => retry_after,
+            _ => self.backoff(attempt),
+        };
+
+        match self.deadline {
+            Some(deadline) if elapsed + delay > deadline => None,
+            _ => Some(delay),
+        }
+    }
+
+    /// Full-jitter exponential backoff: uniform in `[0, min(max, base * 2^(n-1))]`
+    pub(super) fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let cap = exp.min(self.max_delay);
+        cap.mul_f64(jitter())
+    }
+}
+
+/// A pseudo-random fraction in `[0, 1)`, seeded from the std hasher's random keys
+fn jitter() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_honours_retry_after() {
+        let policy = RetryPolicy::default();
+        let delay = policy.next_delay(
+            TransientFailure::RateLimited(Some(Duration::from_secs(7))),
+            Idempotency::Idempotent,
+            1,
+            Duration::ZERO,
+        );
+        assert_eq!(delay, Some(Duration::from_secs(7)));
+
+        // Without a `Retry-After` the usual backoff applies
+        let delay = policy
+            .next_delay(
+                TransientFailure::RateLimited(None),
+                Idempotency::Idempotent,
+                2,
+                Duration::ZERO,
+            )
+            .unwrap();
+        assert!(delay <= policy.base_delay * 2, "{delay:?}");
+    }
+
+    #[test]
+    fn test_backoff_is_bounded() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            deadline: None,
+        };
+        for attempt in 1..10 {
+            let delay = policy
+                .next_delay(
+                    TransientFailure::ServerError,
+                    Idempotency::Idempotent,
+                    attempt,
+                    Duration::ZERO,
+                )
+                .unwrap();
+            let cap = Duration::from_millis(100 * 2u64.pow(attempt - 1)).min(policy.max_delay);
+            assert!(delay <= cap, "attempt {attempt}: {delay:?} > {cap:?}");
+        }
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts_and_deadline() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            deadline: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+        let failure = TransientFailure::ServerError;
+        assert!(policy
+            .next_delay(failure, Idempotency::Idempotent, 2, Duration::ZERO)
+            .is_some());
+        assert!(policy
+            .next_delay(failure, Idempotency::Idempotent, 3, Duration::ZERO)
+            .is_none());
+        assert!(policy
+            .next_delay(
+                TransientFailure::RateLimited(Some(Duration::from_secs(5))),
+                Idempotency::Idempotent,
+                1,
+                Duration::from_secs(6),
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_non_idempotent_requests_only_retry_when_unsent() {
+        let policy = RetryPolicy::default();
+        let post = Idempotency::NonIdempotent;
+        assert!(policy
+            .next_delay(TransientFailure::ServerError, post, 1, Duration::ZERO)
+            .is_none());
+        assert!(policy
+            .next_delay(TransientFailure::Network, post, 1, Duration::ZERO)
+            .is_none());
+        assert!(policy
+            .next_delay(TransientFailure::Connect, post, 1, Duration::ZERO)
+            .is_some());
+        assert!(policy
+            .next_delay(
+                TransientFailure::RateLimited(Some(Duration::from_secs(1))),
+                post,
+                1,
+                Duration::ZERO,
+            )
+            .is_some());
+    }
+}
@@ -1,10 +1,21 @@
 use super::{
-    AsanaClient, CommentCreateDto, CommentDto, TaskDto, TaskUpdateDto, UserDto, WorkspaceDto,
+    AsanaClient, CommentCreateDto, CommentDto, EventDto, EventsResponse, ProjectDto,
+    ResourceRefDto, SectionAddTaskDto, SectionDto, TaskCreateDto, TaskDto, TaskUpdateDto, UserDto,
+    WorkspaceDto,
 };
 use crate::domain::*;
-use crate::ports::{RepositoryResult, TaskRepository, WorkspaceRepository};
+use crate::ports::{
+    EventRepository, ProjectRepository, RepositoryResult, TaskRepository, WorkspaceRepository,
+};
 use async_trait::async_trait;
 
+/// Fields requested for every full task record
+const TASK_OPT_FIELDS: &str = "gid,name,notes,html_notes,completed,due_on,due_at,start_on,assignee.gid,assignee.name,assignee.email,projects.gid,projects.name,projects.color,tags.gid,tags.name,created_at,modified_at,workspace.gid,workspace.name,resource_type,resource_subtype,custom_fields.gid,custom_fields.name,custom_fields.display_value,custom_fields.text_value,custom_fields.number_value,custom_fields.enum_value.gid,custom_fields.enum_value.name,custom_fields.enum_value.color,dependencies.gid,dependencies.name,dependencies.resource_type,memberships.project.gid,memberships.section.gid,memberships.section.name,parent.gid,num_subtasks";
+
+/// Fields requested for every project record
+const PROJECT_OPT_FIELDS: &str =
+    "gid,name,notes,color,archived,workspace.gid,workspace.name,created_at,modified_at";
+
 pub struct AsanaTaskRepository {
     client: AsanaClient,
 }
@@ -17,39 +28,93 @@ impl AsanaTaskRepository {
     fn build_task_query_params(&self, filter: &TaskFilter) -> Vec<(String, String)> {
         let mut params = Vec::new();
 
-        if let Some(workspace) = &filter.workspace {
-            params.push(("workspace".to_string(), workspace.0.clone()));
-        }
-
+        // Asana takes a project *or* assignee + workspace; other criteria are checked locally
         if let Some(project) = &filter.project {
             params.push(("project".to_string(), project.0.clone()));
+        } else {
+            if let Some(workspace) = &filter.workspace {
+                params.push(("workspace".to_string(), workspace.0.clone()));
+            }
+
+            if let Some(assignee) = &filter.assignee {
+                params.push(("assignee".to_string(), assignee.0.clone()));
+            }
+        }
+
+        if let Some(since) = filter.completed_since {
+            // Incomplete tasks plus those completed since then
+            params.push(("completed_since".to_string(), since.to_rfc3339()));
+        } else if filter.completed == Some(false) {
+            // For incomplete tasks, use completed_since=now to exclude recently completed tasks
+            params.push(("completed_since".to_string(), "now".to_string()));
         }
 
-        if let Some(assignee) = &filter.assignee {
-            params.push(("assignee".to_string(), assignee.0.clone()));
+        if let Some(since) = filter.modified_since {
+            params.push(("modified_since".to_string(), since.to_rfc3339()));
         }
 
-        if let Some(completed) = filter.completed {
-            if !completed {
-                // For incomplete tasks, use completed_since=now to exclude recently completed tasks
-                params.push(("completed_since".to_string(), "now".to_string()));
+        // `limit` is managed by the client's pagination; only the starting cursor goes here
+        if let Some(offset) = &filter.offset {
+            params.push(("offset".to_string(), offset.clone()));
+        }
+
+        // Add fields we want to retrieve
+        params.push(("opt_fields".to_string(), TASK_OPT_FIELDS.to_string()));
+
+        params
+    }
+
+    fn build_search_query_params(&self, search: &WorkspaceSearch) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if let Some(text) = &search.text {
+            params.push(("text".to_string(), text.clone()));
+        }
+        // `.any` parameters take comma-separated gids
+        let any = [
+            (
+                "assignee.any",
+                search.assignees.iter().map(|a| a.0.clone()).collect(),
+            ),
+            (
+                "projects.any",
+                search.projects.iter().map(|p| p.0.clone()).collect(),
+            ),
+            ("tags.any", search.tags.clone()),
+        ];
+        for (key, ids) in any {
+            if !ids.is_empty() {
+                params.push((key.to_string(), ids.join(",")));
             }
-            // For completed tasks, we don't add completed_since parameter
+        }
+        if let Some(before) = search.due_before {
+            params.push(("due_on.before".to_string(), before.to_string()));
+        }
+        if let Some(after) = search.due_after {
+            params.push(("due_on.after".to_string(), after.to_string()));
+        }
+        if let Some(completed) = search.completed {
+            params.push(("completed".to_string(), completed.to_string()));
         }
 
-        if let Some(limit) = filter.limit {
-            params.push(("limit".to_string(), limit.to_string()));
+        for predicate in &search.custom_fields {
+            let key = |suffix: &str| format!("custom_fields.{}.{suffix}", predicate.field);
+            params.push(match &predicate.condition {
+                FieldCondition::Value(value) => (key("value"), value.clone()),
+                FieldCondition::Contains(text) => (key("contains"), text.clone()),
+                FieldCondition::LessThan(n) => (key("less_than"), n.to_string()),
+                FieldCondition::GreaterThan(n) => (key("greater_than"), n.to_string()),
+                FieldCondition::IsSet => (key("is_set"), "true".to_string()),
+            });
         }
 
-        if let Some(offset) = filter.offset {
-            params.push(("offset".to_string(), offset.to_string()));
+        if let Some(sort) = search.sort_by {
+            params.push(("sort_by".to_string(), sort.as_param().to_string()));
+        }
+        if search.sort_ascending {
+            params.push(("sort_ascending".to_string(), "true".to_string()));
         }
 
-        // Add fields we want to retrieve
-        params.push((
-            "opt_fields".to_string(),
-            "gid,name,notes,html_notes,completed,due_on,due_at,assignee.gid,assignee.name,assignee.email,projects.gid,projects.name,projects.color,tags.gid,tags.name,created_at,modified_at,workspace.gid,workspace.name,resource_type,resource_subtype,custom_fields.gid,custom_fields.name,custom_fields.display_value,custom_fields.text_value,custom_fields.number_value,custom_fields.enum_value.gid,custom_fields.enum_value.name,custom_fields.enum_value.color,dependencies.gid,dependencies.name,dependencies.resource_type".to_string(),
-        ));
+        params.push(("opt_fields".to_string(), TASK_OPT_FIELDS.to_string()));
 
         params
     }
@@ -73,32 +138,75 @@ impl AsanaTaskRepository {
 #[async_trait]
 impl TaskRepository for AsanaTaskRepository {
     async fn get_task(&self, id: &TaskId) -> RepositoryResult<Task> {
-        let path = format!(
-            "/tasks/{}?opt_fields=gid,name,notes,html_notes,completed,due_on,due_at,assignee.gid,assignee.name,assignee.email,projects.gid,projects.name,projects.color,tags.gid,tags.name,created_at,modified_at,workspace.gid,workspace.name,resource_type,resource_subtype,custom_fields.gid,custom_fields.name,custom_fields.display_value,custom_fields.text_value,custom_fields.number_value,custom_fields.enum_value.gid,custom_fields.enum_value.name,custom_fields.enum_value.color,dependencies.gid,dependencies.name,dependencies.resource_type",
-            id.0
-        );
+        let path = format!("/tasks/{}?opt_fields={TASK_OPT_FIELDS}", id.0);
 
         let task_dto: TaskDto = self.client.get(&path).await?;
         Ok(task_dto.into())
     }
 
-    async fn list_tasks(&self, filter: &TaskFilter) -> RepositoryResult<Vec<Task>> {
+    async fn list_tasks(&self, filter: &TaskFilter) -> RepositoryResult<Page<Task>> {
         let params = self.build_task_query_params(filter);
         let query_string = self.build_query_string(&params);
         let path = format!("/tasks{query_string}");
 
-        let task_dtos: Vec<TaskDto> = self.client.get_list(&path).await?;
-        Ok(task_dtos.into_iter().map(|dto| dto.into()).collect())
+        let page = self
+            .client
+            .get_list_up_to::<TaskDto>(&path, filter.limit)
+            .await?;
+        Ok(Page {
+            items: page.items.into_iter().map(|dto| dto.into()).collect(),
+            next_offset: page.next_offset,
+        })
+    }
+
+    async fn search_tasks(
+        &self,
+        workspace: &WorkspaceId,
+        search: &WorkspaceSearch,
+    ) -> RepositoryResult<Vec<Task>> {
+        let params = self.build_search_query_params(search);
+        let path = format!(
+            "/workspaces/{}/tasks/search{}",
+            workspace.0,
+            self.build_query_string(&params)
+        );
+
+        // Search results have no cursor, so this is always a single request
+        let page = self
+            .client
+            .get_list_up_to::<TaskDto>(&path, Some(search.result_limit()))
+            .await?;
+        Ok(page.items.into_iter().map(|dto| dto.into()).collect())
+    }
+
+    async fn create_task(&self, task: &NewTask) -> RepositoryResult<Task> {
+        // Subtasks are created under their parent and inherit its workspace
+        let path = match &task.parent {
+            Some(parent) => format!("/tasks/{}/subtasks?opt_fields={TASK_OPT_FIELDS}", parent.0),
+            None => format!("/tasks?opt_fields={TASK_OPT_FIELDS}"),
+        };
+        let create_dto: TaskCreateDto = task.clone().into();
+
+        let task_dto: TaskDto = self.client.post(&path, &create_dto).await?;
+        Ok(task_dto.into())
     }
 
     async fn update_task(&self, id: &TaskId, updates: &TaskUpdate) -> RepositoryResult<Task> {
-        let path = format!("/tasks/{}", id.0);
+        // Ask for the full record back so the cached copy keeps html_notes
+        let path = format!("/tasks/{}?opt_fields={TASK_OPT_FIELDS}", id.0);
         let update_dto: TaskUpdateDto = updates.clone().into();
 
         let task_dto: TaskDto = self.client.put(&path, &update_dto).await?;
         Ok(task_dto.into())
     }
 
+    async fn list_subtasks(&self, parent: &TaskId) -> RepositoryResult<Vec<Task>> {
+        let path = format!("/tasks/{}/subtasks?opt_fields={TASK_OPT_FIELDS}", parent.0);
+
+        let task_dtos: Vec<TaskDto> = self.client.get_list(&path).await?;
+        Ok(task_dtos.into_iter().map(|dto| dto.into()).collect())
+    }
+
     async fn get_task_comments(&self, task_id: &TaskId) -> RepositoryResult<Vec<Comment>> {
         let path = format!(
             "/tasks/{}/stories?opt_fields=gid,text,created_by.gid,created_by.name,created_by.email,created_at,type,resource_subtype",
@@ -134,7 +242,7 @@ impl WorkspaceRepository for AsanaTaskRepository {
     async fn list_workspaces(&self) -> RepositoryResult<Vec<Workspace>> {
         let path = "/workspaces?opt_fields=gid,name,is_organization";
 
-        let workspace_dtos: Vec<WorkspaceDto> = self.client.get(path).await?;
+        let workspace_dtos: Vec<WorkspaceDto> = self.client.get_list(path).await?;
         Ok(workspace_dtos.into_iter().map(|dto| dto.into()).collect())
     }
 
@@ -144,4 +252,98 @@ impl WorkspaceRepository for AsanaTaskRepository {
         let user_dto: UserDto = self.client.get(path).await?;
         Ok(user_dto.into())
     }
+
+    async fn list_users(&self, workspace: &WorkspaceId) -> RepositoryResult<Vec<User>> {
+        let path = format!(
+            "/workspaces/{}/users?opt_fields=gid,name,email,photo.image_60x60",
+            workspace.0
+        );
+
+        let user_dtos: Vec<UserDto> = self.client.get_list(&path).await?;
+        Ok(user_dtos.into_iter().map(|dto| dto.into()).collect())
+    }
+}
+
+#[async_trait]
+impl ProjectRepository for AsanaTaskRepository {
+    async fn list_projects(
+        &self,
+        workspace: &WorkspaceId,
+        include_archived: bool,
+    ) -> RepositoryResult<Vec<Project>> {
+        let mut params = vec![
+            ("workspace".to_string(), workspace.0.clone()),
+            ("opt_fields".to_string(), PROJECT_OPT_FIELDS.to_string()),
+        ];
+        if !include_archived {
+            params.push(("archived".to_string(), "false".to_string()));
+        }
+        let path = format!("/projects{}", self.build_query_string(&params));
+
+        let project_dtos: Vec<ProjectDto> = self.client.get_list(&path).await?;
+        Ok(project_dtos.into_iter().map(|dto| dto.into()).collect())
+    }
+
+    async fn get_project(&self, id: &ProjectId) -> RepositoryResult<Project> {
+        let path = format!("/projects/{}?opt_fields={PROJECT_OPT_FIELDS}", id.0);
+
+        let project_dto: ProjectDto = self.client.get(&path).await?;
+        Ok(project_dto.into())
+    }
+
+    async fn list_sections(&self, project: &ProjectId) -> RepositoryResult<Vec<Section>> {
+        let path = format!(
+            "/projects/{}/sections?opt_fields=gid,name,project.gid",
+            project.0
+        );
+
+        let section_dtos: Vec<SectionDto> = self.client.get_list(&path).await?;
+        Ok(section_dtos.into_iter().map(|dto| dto.into()).collect())
+    }
+
+    async fn add_task_to_section(
+        &self,
+        section: &SectionId,
+        task: &TaskId,
+    ) -> RepositoryResult<()> {
+        let path = format!("/sections/{}/addTask", section.0);
+        let add_dto = SectionAddTaskDto {
+            task: task.0.clone(),
+        };
+
+        // The response is an empty object
+        let _: serde_json::Value = self.client.post(&path, &add_dto).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventRepository for AsanaTaskRepository {
+    async fn get_events(&self, resource: &str, sync: Option<&str>) -> RepositoryResult<EventPage> {
+        let mut path = format!("/events?resource={resource}");
+        if let Some(sync) = sync {
+            path.push_str(&format!("&sync={}", urlencoding::encode(sync)));
+        }
+
+        let response: EventsResponse = self.client.get_body(&path).await?;
+        Ok(EventPage {
+            events: response
+                .data
+                .into_iter()
+                .filter_map(EventDto::into_task_event)
+                .collect(),
+            sync: response.sync,
+            has_more: response.has_more,
+        })
+    }
+
+    async fn get_user_task_list(&self, workspace: &WorkspaceId) -> RepositoryResult<String> {
+        let path = format!(
+            "/users/me/user_task_list?workspace={}&opt_fields=gid",
+            workspace.0
+        );
+
+        let list: ResourceRefDto = self.client.get(&path).await?;
+        Ok(list.gid)
+    }
 }
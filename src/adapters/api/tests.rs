@@ -0,0 +1,566 @@
+//! End-to-end tests of the Asana adapters against the in-process fake server
+
+use super::{AsanaClient, AsanaTaskRepository, RetryPolicy};
+use crate::domain::*;
+use crate::ports::{
+    EventRepository, ProjectRepository, RepositoryError, TaskRepository, WorkspaceRepository,
+};
+use crate::test_support::{self as fake, FakeAsana, FakeResponse};
+use serde_json::json;
+use std::time::Duration;
+
+fn repo_for(fake: &FakeAsana) -> AsanaTaskRepository {
+    let client = AsanaClient::new("test-token".to_string(), &fake.base_url()).with_retry_policy(
+        RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        },
+    );
+    AsanaTaskRepository::new(client)
+}
+
+fn repo_without_retries(fake: &FakeAsana) -> AsanaTaskRepository {
+    let client = AsanaClient::new("test-token".to_string(), &fake.base_url()).with_retry_policy(
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        },
+    );
+    AsanaTaskRepository::new(client)
+}
+
+fn unbounded_filter() -> TaskFilter {
+    TaskFilter {
+        limit: None,
+        ..Default::default()
+    }
+}
+
+fn numbered_tasks(count: usize) -> Vec<serde_json::Value> {
+    (1..=count)
+        .map(|i| fake::task(&i.to_string(), &format!("Task {i}")))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_list_tasks_follows_every_page() {
+    let fake = FakeAsana::start().await;
+    fake.on_paginated("/tasks", numbered_tasks(7), 3);
+    let repo = repo_for(&fake);
+
+    let page = repo.list_tasks(&unbounded_filter()).await.unwrap();
+
+    assert_eq!(page.items.len(), 7);
+    assert_eq!(page.items[6].name, "Task 7");
+    assert_eq!(page.next_offset, None);
+
+    let requests = fake.requests_to("GET", "/tasks");
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].query.get("offset"), None);
+    assert_eq!(requests[1].query.get("offset").unwrap(), "cursor-3");
+    assert_eq!(requests[2].query.get("offset").unwrap(), "cursor-6");
+    assert_eq!(
+        requests[0].authorization.as_deref(),
+        Some("Bearer test-token")
+    );
+}
+
+#[tokio::test]
+async fn test_list_tasks_stops_at_limit_and_resumes_from_cursor() {
+    let fake = FakeAsana::start().await;
+    fake.on_list("/tasks", numbered_tasks(5));
+    let repo = repo_for(&fake);
+
+    let filter = TaskFilter {
+        limit: Some(3),
+        ..unbounded_filter()
+    };
+    let first = repo.list_tasks(&filter).await.unwrap();
+    assert_eq!(first.items.len(), 3);
+    assert_eq!(fake.requests()[0].query.get("limit").unwrap(), "3");
+
+    let rest = repo
+        .list_tasks(&TaskFilter {
+            limit: None,
+            offset: first.next_offset.clone(),
+            ..filter
+        })
+        .await
+        .unwrap();
+    let names: Vec<_> = rest.items.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["Task 4", "Task 5"]);
+}
+
+#[tokio::test]
+async fn test_list_tasks_sends_filter_params() {
+    let fake = FakeAsana::start().await;
+    fake.on_list("/tasks", vec![]);
+    let repo = repo_for(&fake);
+
+    repo.list_tasks(&TaskFilter {
+        workspace: Some("w1".into()),
+        assignee: Some("u1".into()),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let request = &fake.requests()[0];
+    assert_eq!(request.query.get("workspace").unwrap(), "w1");
+    assert_eq!(request.query.get("assignee").unwrap(), "u1");
+    assert_eq!(request.query.get("completed_since").unwrap(), "now");
+    assert!(request
+        .query
+        .get("opt_fields")
+        .unwrap()
+        .contains("html_notes"));
+}
+
+#[tokio::test]
+async fn test_list_tasks_by_project_sends_project_scope_and_since_params() {
+    let fake = FakeAsana::start().await;
+    fake.on_list("/tasks", vec![]);
+    let repo = repo_for(&fake);
+    let since: chrono::DateTime<chrono::Utc> = "2026-10-01T00:00:00Z".parse().unwrap();
+
+    repo.list_tasks(&TaskFilter {
+        workspace: Some("w1".into()),
+        project: Some("p1".into()),
+        assignee: Some("u1".into()),
+        completed_since: Some(since),
+        modified_since: Some(since),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let request = &fake.requests()[0];
+    assert_eq!(request.query.get("project").unwrap(), "p1");
+    assert_eq!(request.query.get("workspace"), None);
+    assert_eq!(request.query.get("assignee"), None);
+    assert_eq!(
+        request.query.get("completed_since").unwrap(),
+        "2026-10-01T00:00:00+00:00"
+    );
+    assert_eq!(
+        request.query.get("modified_since").unwrap(),
+        "2026-10-01T00:00:00+00:00"
+    );
+}
+
+#[tokio::test]
+async fn test_search_tasks_sends_search_params() {
+    let fake = FakeAsana::start().await;
+    fake.on_list(
+        "/workspaces/w1/tasks/search",
+        vec![fake::task("1", "Launch plan")],
+    );
+    let repo = repo_for(&fake);
+
+    let tasks = repo
+        .search_tasks(
+            &"w1".into(),
+            &WorkspaceSearch {
+                text: Some("launch plan".to_string()),
+                assignees: vec!["u1".into(), "u2".into()],
+                projects: vec!["p1".into()],
+                tags: vec!["t1".to_string()],
+                due_before: Some("2026-11-01".parse().unwrap()),
+                completed: Some(false),
+                custom_fields: vec!["cf1=opt1".parse().unwrap(), "cf2>3".parse().unwrap()],
+                sort_by: Some(SearchSort::DueDate),
+                sort_ascending: true,
+                limit: Some(500),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(tasks.len(), 1);
+    let request = &fake.requests()[0];
+    for (key, value) in [
+        ("text", "launch plan"),
+        ("assignee.any", "u1,u2"),
+        ("projects.any", "p1"),
+        ("tags.any", "t1"),
+        ("due_on.before", "2026-11-01"),
+        ("completed", "false"),
+        ("custom_fields.cf1.value", "opt1"),
+        ("custom_fields.cf2.greater_than", "3"),
+        ("sort_by", "due_date"),
+        ("sort_ascending", "true"),
+        ("limit", "100"),
+    ] {
+        assert_eq!(
+            request.query.get(key).map(String::as_str),
+            Some(value),
+            "{key}"
+        );
+    }
+    assert_eq!(request.query.get("due_on.after"), None);
+}
+
+#[tokio::test]
+async fn test_get_task_maps_status_codes() {
+    let fake = FakeAsana::start().await;
+    fake.on(
+        "GET",
+        "/tasks/401",
+        FakeResponse::error(401, "Not Authorized"),
+    );
+    let repo = repo_for(&fake);
+
+    let not_found = repo.get_task(&"missing".into()).await;
+    assert!(matches!(not_found, Err(RepositoryError::NotFound(_))));
+
+    let unauthorized = repo.get_task(&"401".into()).await;
+    assert!(matches!(
+        unauthorized,
+        Err(RepositoryError::Authentication(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_rate_limit_surfaces_retry_after_when_not_retrying() {
+    let fake = FakeAsana::start().await;
+    fake.on("GET", "/tasks/1", FakeResponse::rate_limited(7));
+    let repo = repo_without_retries(&fake);
+
+    let result = repo.get_task(&"1".into()).await;
+    assert!(matches!(result, Err(RepositoryError::RateLimit(7))));
+}
+
+#[tokio::test]
+async fn test_rate_limits_without_retry_after_back_off_briefly() {
+    let fake = FakeAsana::start().await;
+    fake.enqueue("GET", "/tasks/1", FakeResponse::error(429, "Slow down"));
+    fake.on(
+        "GET",
+        "/tasks/1",
+        FakeResponse::data(fake::task("1", "Eventually")),
+    );
+    let repo = repo_for(&fake);
+
+    let started = std::time::Instant::now();
+    let task = repo.get_task(&"1".into()).await.unwrap();
+    assert_eq!(task.name, "Eventually");
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    fake.on("GET", "/tasks/2", FakeResponse::error(429, "Slow down"));
+    let result = repo_without_retries(&fake).get_task(&"2".into()).await;
+    assert!(matches!(result, Err(RepositoryError::RateLimit(1))));
+}
+
+#[tokio::test]
+async fn test_transient_failures_are_retried_for_reads() {
+    let fake = FakeAsana::start().await;
+    fake.enqueue("GET", "/tasks/1", FakeResponse::rate_limited(0));
+    fake.enqueue("GET", "/tasks/1", FakeResponse::error(503, "Unavailable"));
+    fake.on(
+        "GET",
+        "/tasks/1",
+        FakeResponse::data(fake::task("1", "Eventually")),
+    );
+    let repo = repo_for(&fake);
+
+    let task = repo.get_task(&"1".into()).await.unwrap();
+    assert_eq!(task.name, "Eventually");
+    assert_eq!(fake.requests_to("GET", "/tasks/1").len(), 3);
+}
+
+#[tokio::test]
+async fn test_comment_creation_is_not_retried_after_server_error() {
+    let fake = FakeAsana::start().await;
+    fake.enqueue("POST", "/tasks/1/stories", FakeResponse::error(500, "Oops"));
+    fake.on(
+        "POST",
+        "/tasks/1/stories",
+        FakeResponse::data(fake::story("s1", "Posted twice")),
+    );
+    let repo = repo_for(&fake);
+
+    let result = repo.create_comment(&"1".into(), "Hello").await;
+    assert!(matches!(result, Err(RepositoryError::Api(_))));
+    assert_eq!(fake.requests_to("POST", "/tasks/1/stories").len(), 1);
+}
+
+#[tokio::test]
+async fn test_malformed_payloads_are_serialization_errors() {
+    let fake = FakeAsana::start().await;
+    fake.on(
+        "GET",
+        "/tasks/1",
+        FakeResponse::raw(200, "{\"data\": {\"gid\": 1"),
+    );
+    fake.on(
+        "GET",
+        "/tasks",
+        FakeResponse::json(200, json!({ "data": [{ "name": "no gid" }] })),
+    );
+    let repo = repo_for(&fake);
+
+    let truncated = repo.get_task(&"1".into()).await;
+    assert!(matches!(truncated, Err(RepositoryError::Serialization(_))));
+
+    let wrong_shape = repo.list_tasks(&TaskFilter::default()).await;
+    assert!(matches!(
+        wrong_shape,
+        Err(RepositoryError::Serialization(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_comments_are_tagged_with_their_task() {
+    let fake = FakeAsana::start().await;
+    fake.on_list(
+        "/tasks/42/stories",
+        vec![fake::story("s1", "First"), fake::story("s2", "Second")],
+    );
+    let repo = repo_for(&fake);
+
+    let comments = repo.get_task_comments(&"42".into()).await.unwrap();
+    assert_eq!(comments.len(), 2);
+    assert!(comments.iter().all(|c| c.task_id == TaskId::from("42")));
+    assert_eq!(comments[0].author.as_ref().unwrap().name, "Test User");
+}
+
+#[tokio::test]
+async fn test_update_task_sends_data_envelope() {
+    let fake = FakeAsana::start().await;
+    let mut completed = fake::task("1", "Done");
+    completed["completed"] = json!(true);
+    fake.on("PUT", "/tasks/1", FakeResponse::data(completed));
+    let repo = repo_for(&fake);
+
+    let task = repo
+        .update_task(
+            &"1".into(),
+            &TaskUpdate {
+                completed: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert!(task.completed);
+    let request = &fake.requests_to("PUT", "/tasks/1")[0];
+    assert!(request
+        .query
+        .get("opt_fields")
+        .unwrap()
+        .contains("html_notes"));
+    assert_eq!(
+        request.body.clone().unwrap(),
+        json!({ "data": { "completed": true } })
+    );
+}
+
+#[tokio::test]
+async fn test_workspace_repository() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    let repo = repo_for(&fake);
+
+    let user = repo.get_current_user().await.unwrap();
+    assert_eq!(user.id, UserId::from("u1"));
+    assert_eq!(user.email, "u1@example.com");
+
+    let workspaces = repo.list_workspaces().await.unwrap();
+    assert_eq!(workspaces.len(), 1);
+    assert_eq!(workspaces[0].name, "Test Workspace");
+    assert!(workspaces[0].is_organization);
+    fake.on_list(
+        "/workspaces/w1/users",
+        vec![
+            fake::user("u1", "Test User"),
+            fake::user("u2", "Other User"),
+        ],
+    );
+    let users = repo.list_users(&"w1".into()).await.unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[1].email, "u2@example.com");
+}
+
+#[tokio::test]
+async fn test_project_repository() {
+    let fake = FakeAsana::start().await;
+    fake.on_list(
+        "/projects",
+        vec![
+            fake::project("p1", "Roadmap"),
+            fake::project("p2", "Launch"),
+        ],
+    );
+    fake.on(
+        "GET",
+        "/projects/p1",
+        FakeResponse::data(fake::project("p1", "Roadmap")),
+    );
+    let repo = repo_for(&fake);
+
+    let projects = repo.list_projects(&"w1".into(), false).await.unwrap();
+    assert_eq!(projects.len(), 2);
+    assert_eq!(projects[1].name, "Launch");
+    assert_eq!(projects[1].workspace, WorkspaceId::from("w1"));
+
+    let request = &fake.requests_to("GET", "/projects")[0];
+    assert_eq!(request.query.get("workspace").unwrap(), "w1");
+    assert_eq!(request.query.get("archived").unwrap(), "false");
+
+    repo.list_projects(&"w1".into(), true).await.unwrap();
+    let request = &fake.requests_to("GET", "/projects")[1];
+    assert!(!request.query.contains_key("archived"));
+
+    let project = repo.get_project(&"p1".into()).await.unwrap();
+    assert_eq!(project.id, ProjectId::from("p1"));
+    assert_eq!(project.color.as_deref(), Some("light-green"));
+    assert!(!project.archived);
+}
+
+#[tokio::test]
+async fn test_sections_list_and_add_task() {
+    let fake = FakeAsana::start().await;
+    fake.on_list(
+        "/projects/p1/sections",
+        vec![fake::section("s1", "To do"), fake::section("s2", "Doing")],
+    );
+    fake.on(
+        "POST",
+        "/sections/s2/addTask",
+        FakeResponse::data(json!({})),
+    );
+    let repo = repo_for(&fake);
+
+    let sections = repo.list_sections(&"p1".into()).await.unwrap();
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[1].id, SectionId::from("s2"));
+    assert_eq!(sections[1].project, ProjectId::from("p1"));
+
+    repo.add_task_to_section(&"s2".into(), &"42".into())
+        .await
+        .unwrap();
+    let request = &fake.requests_to("POST", "/sections/s2/addTask")[0];
+    assert_eq!(
+        request.body.clone().unwrap(),
+        json!({ "data": { "task": "42" } })
+    );
+}
+
+#[tokio::test]
+async fn test_list_subtasks() {
+    let fake = FakeAsana::start().await;
+    let mut parent = fake::task("1", "Release");
+    parent["num_subtasks"] = json!(2);
+    let mut subtask = fake::task("2", "Tag version");
+    subtask["parent"] = json!({ "gid": "1" });
+    fake.on("GET", "/tasks/1", FakeResponse::data(parent));
+    fake.on_list(
+        "/tasks/1/subtasks",
+        vec![subtask, fake::task("3", "Publish")],
+    );
+    let repo = repo_for(&fake);
+
+    let parent = repo.get_task(&"1".into()).await.unwrap();
+    assert_eq!(parent.num_subtasks, 2);
+    assert!(parent.has_subtasks());
+
+    let subtasks = repo.list_subtasks(&"1".into()).await.unwrap();
+    assert_eq!(subtasks.len(), 2);
+    assert_eq!(subtasks[0].parent, Some(TaskId::from("1")));
+    let request = &fake.requests_to("GET", "/tasks/1/subtasks")[0];
+    assert!(request.query.contains_key("opt_fields"));
+}
+
+#[tokio::test]
+async fn test_create_task_posts_new_task() {
+    let fake = FakeAsana::start().await;
+    fake.on(
+        "POST",
+        "/tasks",
+        FakeResponse::data(fake::task("99", "Brand new")),
+    );
+    let repo = repo_for(&fake);
+
+    let task = repo
+        .create_task(&NewTask {
+            name: "Brand new".to_string(),
+            workspace: Some("w1".into()),
+            assignee: Some("u1".into()),
+            due: Some(parse_due_date("2025-06-01").unwrap()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(task.id, TaskId::from("99"));
+    let request = &fake.requests_to("POST", "/tasks")[0];
+    assert!(request.query.contains_key("opt_fields"));
+    assert_eq!(
+        request.body.clone().unwrap(),
+        json!({ "data": {
+            "name": "Brand new",
+            "workspace": "w1",
+            "assignee": "u1",
+            "due_on": "2025-06-01"
+        } })
+    );
+}
+
+#[tokio::test]
+async fn test_events_hand_out_a_sync_token_then_task_events() {
+    let fake = FakeAsana::start().await;
+    fake.enqueue(
+        "GET",
+        "/events",
+        FakeResponse::json(
+            412,
+            json!({ "sync": "token-1", "errors": [{ "message": "Sync token invalid or too old" }] }),
+        ),
+    );
+    fake.on(
+        "GET",
+        "/events",
+        FakeResponse::json(
+            200,
+            json!({
+                "data": [
+                    { "action": "changed", "resource": { "gid": "1", "resource_type": "task" } },
+                    {
+                        "action": "added",
+                        "resource": { "gid": "s1", "resource_type": "story" },
+                        "parent": { "gid": "2", "resource_type": "task" }
+                    },
+                    { "action": "added", "resource": { "gid": "sec", "resource_type": "section" } }
+                ],
+                "sync": "token-2",
+                "has_more": false
+            }),
+        ),
+    );
+    let repo = repo_for(&fake);
+
+    let err = repo.get_events("p1", None).await.unwrap_err();
+    assert!(matches!(err, RepositoryError::SyncExpired(token) if token == "token-1"));
+
+    let page = repo.get_events("p1", Some("token-1")).await.unwrap();
+    assert_eq!(page.sync, "token-2");
+    assert_eq!(
+        page.events,
+        [
+            TaskEvent {
+                task: "1".into(),
+                action: EventAction::Changed,
+                story: false,
+            },
+            TaskEvent {
+                task: "2".into(),
+                action: EventAction::Added,
+                story: true,
+            },
+        ]
+    );
+    let request = &fake.requests_to("GET", "/events")[1];
+    assert_eq!(request.query["resource"], "p1");
+    assert_eq!(request.query["sync"], "token-1");
+}
@@ -0,0 +1,275 @@
+use crate::ports::Cache;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::Display;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::fs;
+
+/// File names longer than this are replaced by a hash of the key segment
+const MAX_FILE_NAME: usize = 200;
+
+/// A cache that outlives the process: one JSON file per key under `root`,
+/// mirrored in memory.
+///
+/// A `/` in a key starts a subdirectory, so keys like `user/workspace/list`
+/// keep each account's and workspace's entries apart on disk. Read and write
+/// failures are logged and treated as misses; a broken cache must never stop
+/// the app from talking to Asana.
+pub struct DiskCacheAdapter<K, V> {
+    root: PathBuf,
+    ttl: chrono::Duration,
+    memory: DashMap<K, Entry<V>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry<V> {
+    /// The key as written, since a hashed file name can't be turned back into one
+    #[serde(default)]
+    key: Option<String>,
+    stored_at: DateTime<Utc>,
+    value: V,
+}
+
+impl<K, V> DiskCacheAdapter<K, V>
+where
+    K: Hash + Eq + Display,
+{
+    /// Entries older than `ttl` are dropped when next read
+    pub fn new(root: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            root: root.into(),
+            ttl: chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX),
+            memory: DashMap::new(),
+        }
+    }
+
+    fn path(&self, key: &K) -> PathBuf {
+        let mut path = self.root.clone();
+        for segment in key.to_string().split('/') {
+            path.push(file_name(segment));
+        }
+        path.set_extension("json");
+        path
+    }
+
+    fn is_fresh<T>(&self, entry: &Entry<T>) -> bool {
+        Utc::now() - entry.stored_at < self.ttl
+    }
+}
+
+/// The app's cache directory `name` under the XDG cache dir
+/// (`~/.cache/asana-cli/<name>` on Linux), or under the temp dir without one
+pub fn default_cache_dir(name: &str) -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("asana-cli")
+        .join(name)
+}
+
+/// A key segment as a safe file name: percent-encoded, never `.` or `..`,
+/// and hashed when too long for the file system
+fn file_name(segment: &str) -> String {
+    let encoded = urlencoding::encode(segment).replace('.', "%2E");
+    if encoded.is_empty() {
+        return "_".to_string();
+    }
+    if encoded.len() <= MAX_FILE_NAME {
+        return encoded;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    segment.hash(&mut hasher);
+    format!("{}~{:016x}", &encoded[..64], hasher.finish())
+}
+
+#[async_trait]
+impl<K, V> Cache<K, V> for DiskCacheAdapter<K, V>
+where
+    K: Hash + Eq + Clone + Display + From<String> + Send + Sync + 'static,
+    V: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    async fn get(&self, key: &K) -> Option<V> {
+        if let Some(entry) = self.memory.get(key) {
+            if self.is_fresh(&entry) {
+                return Some(entry.value.clone());
+            }
+        }
+
+        let path = self.path(key);
+        let content = fs::read(&path).await.ok()?;
+        let entry: Entry<V> = match serde_json::from_slice(&content) {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable cache file {}: {}", path.display(), e);
+                return None;
+            }
+        };
+
+        if !self.is_fresh(&entry) {
+            self.remove(key).await;
+            return None;
+        }
+
+        let value = entry.value.clone();
+        self.memory.insert(key.clone(), entry);
+        Some(value)
+    }
+
+    async fn insert(&self, key: K, value: V) {
+        let path = self.path(&key);
+        let entry = Entry {
+            key: Some(key.to_string()),
+            stored_at: Utc::now(),
+            value,
+        };
+
+        match serde_json::to_vec(&entry) {
+            Ok(content) => {
+                // Write then rename, so a crash never leaves half a file behind
+                let temp = path.with_extension("json.tmp");
+                let written = async {
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent).await?;
+                    }
+                    fs::write(&temp, content).await?;
+                    fs::rename(&temp, &path).await
+                };
+                if let Err(e) = written.await {
+                    tracing::warn!("Failed to write cache file {}: {}", path.display(), e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to serialize cache entry: {}", e),
+        }
+
+        self.memory.insert(key, entry);
+    }
+
+    async fn remove(&self, key: &K) {
+        self.memory.remove(key);
+
+        let path = self.path(key);
+        if let Err(e) = fs::remove_file(&path).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("Failed to remove cache file {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    async fn clear(&self) {
+        self.memory.clear();
+
+        if let Err(e) = fs::remove_dir_all(&self.root).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("Failed to clear cache {}: {}", self.root.display(), e);
+            }
+        }
+    }
+
+    async fn keys(&self) -> Vec<K> {
+        let mut keys: Vec<K> = self
+            .memory
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        // Other processes may have written entries this one has never read
+        let mut dirs = vec![self.root.clone()];
+        while let Some(dir) = dirs.pop() {
+            let Ok(mut listing) = fs::read_dir(&dir).await else {
+                continue;
+            };
+            while let Ok(Some(item)) = listing.next_entry().await {
+                let path = item.path();
+                if item.file_type().await.is_ok_and(|kind| kind.is_dir()) {
+                    dirs.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "json") {
+                    let Ok(content) = fs::read(&path).await else {
+                        continue;
+                    };
+                    if let Ok(Entry { key: Some(key), .. }) =
+                        serde_json::from_slice::<Entry<serde::de::IgnoredAny>>(&content)
+                    {
+                        let key = K::from(key);
+                        if !keys.contains(&key) {
+                            keys.push(key);
+                        }
+                    }
+                }
+            }
+        }
+
+        keys
+    }
+
+    async fn contains_key(&self, key: &K) -> bool {
+        self.get(key).await.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root() -> PathBuf {
+        std::env::temp_dir().join(format!("asana-cli-cache-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_entries_survive_a_new_instance() {
+        let root = temp_root();
+        let cache = DiskCacheAdapter::<String, Vec<i32>>::new(&root, Duration::from_secs(60));
+
+        cache
+            .insert("u1/w1/tasks:all".to_string(), vec![1, 2])
+            .await;
+        assert!(root.join("u1").join("w1").join("tasks%3Aall.json").exists());
+
+        let reopened = DiskCacheAdapter::<String, Vec<i32>>::new(&root, Duration::from_secs(60));
+        assert_eq!(
+            reopened.get(&"u1/w1/tasks:all".to_string()).await,
+            Some(vec![1, 2])
+        );
+        assert_eq!(reopened.get(&"u1/w2/tasks:all".to_string()).await, None);
+
+        let long_key = format!("u1/w1/{}", "x".repeat(300));
+        cache.insert(long_key.clone(), vec![3]).await;
+        let mut keys = reopened.keys().await;
+        keys.sort();
+        assert_eq!(keys, ["u1/w1/tasks:all".to_string(), long_key]);
+
+        reopened.remove(&"u1/w1/tasks:all".to_string()).await;
+        assert_eq!(
+            DiskCacheAdapter::<String, Vec<i32>>::new(&root, Duration::from_secs(60))
+                .get(&"u1/w1/tasks:all".to_string())
+                .await,
+            None
+        );
+
+        cache.insert("key".to_string(), vec![3]).await;
+        cache.clear().await;
+        assert!(!root.exists());
+        assert!(!cache.contains_key(&"key".to_string()).await);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_are_dropped() {
+        let root = temp_root();
+        let cache = DiskCacheAdapter::<String, i32>::new(&root, Duration::ZERO);
+
+        cache.insert("key".to_string(), 42).await;
+
+        assert_eq!(cache.get(&"key".to_string()).await, None);
+        assert!(!root.join("key.json").exists());
+    }
+
+    #[test]
+    fn test_file_names_stay_inside_the_root() {
+        assert_eq!(file_name(".."), "%2E%2E");
+        assert_eq!(file_name(""), "_");
+        assert!(file_name(&"x".repeat(300)).len() < MAX_FILE_NAME);
+    }
+}
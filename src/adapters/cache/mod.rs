@@ -1,3 +1,5 @@
+pub mod disk_cache;
 pub mod moka_cache;
 
+pub use disk_cache::*;
 pub use moka_cache::*;
@@ -31,7 +31,7 @@ where
 #[async_trait]
 impl<K, V> Cache<K, V> for MokaCacheAdapter<K, V>
 where
-    K: Hash + Eq + Send + Sync + 'static,
+    K: Hash + Eq + Clone + Send + Sync + 'static,
     V: Clone + Send + Sync + 'static,
 {
     async fn get(&self, key: &K) -> Option<V> {
@@ -50,6 +50,10 @@ where
         self.inner.invalidate_all();
     }
 
+    async fn keys(&self) -> Vec<K> {
+        self.inner.iter().map(|(key, _)| K::clone(&key)).collect()
+    }
+
     async fn contains_key(&self, key: &K) -> bool {
         self.inner.contains_key(key)
     }
@@ -58,7 +62,6 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tokio_test;
 
     #[tokio::test]
     async fn test_cache_operations() {
@@ -72,6 +75,9 @@ mod tests {
         assert!(cache.contains_key(&"key1".to_string()).await);
         assert!(!cache.contains_key(&"nonexistent".to_string()).await);
 
+        // Test keys
+        assert_eq!(cache.keys().await, vec!["key1".to_string()]);
+
         // Test remove
         cache.remove(&"key1".to_string()).await;
         assert_eq!(cache.get(&"key1".to_string()).await, None);
@@ -7,9 +7,13 @@ use tokio::fs;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ConfigFile {
+    api_base_url: Option<String>,
     default_workspace: Option<String>,
     cache_ttl_seconds: Option<u64>,
     max_tasks_per_page: Option<usize>,
+    retry_max_attempts: Option<u32>,
+    retry_deadline_seconds: Option<u64>,
+    time_zone: Option<String>,
 }
 
 pub struct FileConfigStore {
@@ -23,13 +27,15 @@ impl FileConfigStore {
             ConfigError::ReadError("Cannot determine config directory".to_string())
         })?;
 
-        let app_config_dir = config_dir.join("asana-cli");
-        let config_path = app_config_dir.join("config.json");
+        Ok(Self::in_dir(config_dir.join("asana-cli")))
+    }
 
-        Ok(Self {
-            config_path,
+    /// A store keeping its files in `dir` instead of the user's config directory
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            config_path: dir.into().join("config.json"),
             keyring_service: "asana-cli".to_string(),
-        })
+        }
     }
 
     async fn ensure_config_dir(&self) -> ConfigResult<()> {
@@ -108,9 +114,13 @@ impl ConfigStore for FileConfigStore {
 
         Ok(AppConfig {
             api_token,
+            api_base_url: config_file.api_base_url,
             default_workspace: config_file.default_workspace.map(WorkspaceId),
             cache_ttl_seconds: config_file.cache_ttl_seconds.unwrap_or(300),
             max_tasks_per_page: config_file.max_tasks_per_page.unwrap_or(50),
+            retry_max_attempts: config_file.retry_max_attempts.unwrap_or(4),
+            retry_deadline_seconds: config_file.retry_deadline_seconds.unwrap_or(90),
+            time_zone: config_file.time_zone,
         })
     }
 
@@ -118,9 +128,13 @@ impl ConfigStore for FileConfigStore {
         self.ensure_config_dir().await?;
 
         let config_file = ConfigFile {
+            api_base_url: config.api_base_url.clone(),
             default_workspace: config.default_workspace.as_ref().map(|w| w.0.clone()),
             cache_ttl_seconds: Some(config.cache_ttl_seconds),
             max_tasks_per_page: Some(config.max_tasks_per_page),
+            retry_max_attempts: Some(config.retry_max_attempts),
+            retry_deadline_seconds: Some(config.retry_deadline_seconds),
+            time_zone: config.time_zone.clone(),
         };
 
         let content = serde_json::to_string_pretty(&config_file)
@@ -1,4 +1,6 @@
 pub mod api;
 pub mod cache;
 pub mod config;
+pub mod outbox;
+pub mod output;
 pub mod tui;
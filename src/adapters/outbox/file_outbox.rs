@@ -0,0 +1,130 @@
+use crate::domain::PendingChange;
+use crate::ports::{Outbox, OutboxError, OutboxResult};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+/// An outbox kept as one JSON file, rewritten whole on every change
+pub struct FileOutbox {
+    path: PathBuf,
+    // Loaded on first use, then kept in step with the file
+    changes: Mutex<Option<Vec<PendingChange>>>,
+}
+
+impl FileOutbox {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            changes: Mutex::new(None),
+        }
+    }
+
+    /// `outbox.json` in the app's XDG data dir (`~/.local/share/asana-cli` on
+    /// Linux); unlike the cache, nothing here may be thrown away
+    pub fn default_path() -> OutboxResult<PathBuf> {
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| OutboxError::ReadError("Cannot determine data directory".to_string()))?;
+        Ok(data_dir.join("asana-cli").join("outbox.json"))
+    }
+
+    async fn load(&self) -> OutboxResult<Vec<PendingChange>> {
+        match fs::read(&self.path).await {
+            Ok(content) => serde_json::from_slice(&content)
+                .map_err(|e| OutboxError::ReadError(format!("{}: {e}", self.path.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(OutboxError::ReadError(e.to_string())),
+        }
+    }
+
+    async fn save(&self, changes: &[PendingChange]) -> OutboxResult<()> {
+        let write_error = |e: std::io::Error| OutboxError::WriteError(e.to_string());
+        let content = serde_json::to_vec_pretty(changes)
+            .map_err(|e| OutboxError::WriteError(e.to_string()))?;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).await.map_err(write_error)?;
+        }
+        // Write then rename, so a crash never loses the queue
+        let temp = self.path.with_extension("json.tmp");
+        fs::write(&temp, content).await.map_err(write_error)?;
+        fs::rename(&temp, &self.path).await.map_err(write_error)
+    }
+}
+
+#[async_trait]
+impl Outbox for FileOutbox {
+    async fn push(&self, change: PendingChange) -> OutboxResult<()> {
+        let mut guard = self.changes.lock().await;
+        let mut changes = match guard.take() {
+            Some(changes) => changes,
+            None => self.load().await?,
+        };
+
+        changes.push(change);
+        let saved = self.save(&changes).await;
+        if saved.is_err() {
+            changes.pop();
+        }
+        *guard = Some(changes);
+        saved
+    }
+
+    async fn pending(&self) -> OutboxResult<Vec<PendingChange>> {
+        let mut guard = self.changes.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.load().await?);
+        }
+        Ok(guard.clone().unwrap_or_default())
+    }
+
+    async fn remove(&self, id: &str) -> OutboxResult<()> {
+        let mut guard = self.changes.lock().await;
+        let mut changes = match guard.take() {
+            Some(changes) => changes,
+            None => self.load().await?,
+        };
+
+        changes.retain(|change| change.id != id);
+        let saved = self.save(&changes).await;
+        *guard = Some(changes);
+        saved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::OfflineChange;
+    use crate::test_support::domain_task;
+
+    #[tokio::test]
+    async fn test_changes_persist_in_order_across_instances() {
+        let path = std::env::temp_dir()
+            .join(format!("asana-cli-outbox-{}", uuid::Uuid::new_v4()))
+            .join("outbox.json");
+        let outbox = FileOutbox::new(&path);
+        let task = domain_task("1", "Task");
+
+        let complete = PendingChange::new(&task, OfflineChange::SetCompleted { completed: true });
+        let comment = PendingChange::new(
+            &task,
+            OfflineChange::Comment {
+                text: "Done on the train".to_string(),
+            },
+        );
+        outbox.push(complete.clone()).await.unwrap();
+        outbox.push(comment.clone()).await.unwrap();
+
+        let reopened = FileOutbox::new(&path);
+        assert_eq!(
+            reopened.pending().await.unwrap(),
+            [complete.clone(), comment.clone()]
+        );
+
+        reopened.remove(&complete.id).await.unwrap();
+        assert_eq!(FileOutbox::new(&path).pending().await.unwrap(), [comment]);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
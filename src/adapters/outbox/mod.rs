@@ -0,0 +1,3 @@
+pub mod file_outbox;
+
+pub use file_outbox::*;
@@ -0,0 +1,385 @@
+use super::{Record, Template};
+use serde_json::{Map, Value};
+use std::io::{self, IsTerminal};
+use std::str::FromStr;
+use thiserror::Error;
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Pretty-printed JSON: an array for lists, an object for single records
+    Json,
+    /// One compact JSON object per line
+    Ndjson,
+    /// Aligned columns for reading in a terminal
+    Table,
+    Csv,
+    Tsv,
+}
+
+impl OutputFormat {
+    pub const NAMES: [&'static str; 5] = ["json", "ndjson", "table", "csv", "tsv"];
+
+    /// A table when a person is watching, JSON when piped into something else
+    pub fn default_for_stdout() -> Self {
+        if io::stdout().is_terminal() {
+            OutputFormat::Table
+        } else {
+            OutputFormat::Json
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            "table" => Ok(OutputFormat::Table),
+            "csv" => Ok(OutputFormat::Csv),
+            "tsv" => Ok(OutputFormat::Tsv),
+            _ => Err(format!(
+                "Unknown output format '{s}' (expected one of {})",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum OutputError {
+    #[error("Unknown field '{field}' (available: {available})")]
+    UnknownField { field: String, available: String },
+
+    #[error("Invalid template: {0}")]
+    Template(String),
+
+    #[error("Failed to serialize output: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+pub type OutputResult<T> = Result<T, OutputError>;
+
+/// One column of output, named as the user wrote it
+pub(super) enum Column {
+    Field(String),
+    /// A custom field, looked up by name; `cf:` forces this for names that
+    /// clash with a built-in field
+    Custom(String),
+}
+
+impl Column {
+    /// Look `name` up among `T`'s fields, falling back to its custom fields
+    pub(super) fn resolve<T: Record>(name: &str) -> OutputResult<Self> {
+        if T::FIELDS.contains(&name) {
+            return Ok(Column::Field(name.to_string()));
+        }
+        if T::HAS_CUSTOM_FIELDS {
+            let name = name.strip_prefix("cf:").unwrap_or(name);
+            return Ok(Column::Custom(name.to_string()));
+        }
+        Err(OutputError::UnknownField {
+            field: name.to_string(),
+            available: T::FIELDS.join(", "),
+        })
+    }
+
+    fn header(&self) -> &str {
+        match self {
+            Column::Field(name) | Column::Custom(name) => name,
+        }
+    }
+
+    pub(super) fn value<T: Record>(&self, record: &T) -> Value {
+        match self {
+            Column::Field(name) => record.field(name).unwrap_or(Value::Null),
+            Column::Custom(name) => record.custom_field(name),
+        }
+    }
+}
+
+/// How CLI results are written: the format plus an optional column selection,
+/// or a template that replaces both
+#[derive(Debug, Clone)]
+pub struct Output {
+    format: OutputFormat,
+    fields: Option<Vec<String>>,
+    template: Option<Template>,
+}
+
+impl Output {
+    pub fn new(format: OutputFormat, fields: Option<Vec<String>>) -> Self {
+        Self {
+            format,
+            fields,
+            template: None,
+        }
+    }
+
+    pub fn with_template(mut self, template: Template) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    pub fn print<T: Record>(&self, record: &T) -> OutputResult<()> {
+        let text = match &self.template {
+            Some(template) => template.render(record)?,
+            None => self.render(record)?,
+        };
+        println!("{text}");
+        Ok(())
+    }
+
+    pub fn print_list<T: Record>(&self, records: &[T]) -> OutputResult<()> {
+        let text = match &self.template {
+            Some(template) => template.render_list(records)?,
+            None => self.render_list(records)?,
+        };
+        if !text.is_empty() {
+            println!("{text}");
+        }
+        Ok(())
+    }
+
+    /// Render a single record; tables show it as one field per line
+    pub fn render<T: Record>(&self, record: &T) -> OutputResult<String> {
+        match self.format {
+            OutputFormat::Json => match &self.fields {
+                None => Ok(serde_json::to_string_pretty(record)?),
+                Some(_) => Ok(serde_json::to_string_pretty(
+                    &self.select(record, &self.columns::<T>()?),
+                )?),
+            },
+            OutputFormat::Table => {
+                let columns = self.columns::<T>()?;
+                let rows = columns
+                    .iter()
+                    .map(|c| vec![c.header().to_string(), table_cell(&c.value(record))])
+                    .collect::<Vec<_>>();
+                Ok(align(&rows))
+            }
+            _ => self.render_list(std::slice::from_ref(record)),
+        }
+    }
+
+    pub fn render_list<T: Record>(&self, records: &[T]) -> OutputResult<String> {
+        let columns = self.columns::<T>()?;
+
+        let text = match self.format {
+            OutputFormat::Json => match &self.fields {
+                None => serde_json::to_string_pretty(records)?,
+                Some(_) => serde_json::to_string_pretty(
+                    &records
+                        .iter()
+                        .map(|r| self.select(r, &columns))
+                        .collect::<Vec<_>>(),
+                )?,
+            },
+            OutputFormat::Ndjson => {
+                let mut lines = Vec::with_capacity(records.len());
+                for record in records {
+                    lines.push(match &self.fields {
+                        None => serde_json::to_string(record)?,
+                        Some(_) => serde_json::to_string(&self.select(record, &columns))?,
+                    });
+                }
+                lines.join("\n")
+            }
+            OutputFormat::Table => {
+                let mut rows = vec![columns.iter().map(|c| c.header().to_uppercase()).collect()];
+                rows.extend(records.iter().map(|record| {
+                    columns
+                        .iter()
+                        .map(|c| table_cell(&c.value(record)))
+                        .collect()
+                }));
+                align(&rows)
+            }
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                let (separator, escape): (&str, fn(&str) -> String) =
+                    if self.format == OutputFormat::Csv {
+                        (",", csv_escape)
+                    } else {
+                        ("\t", tsv_escape)
+                    };
+                let header = columns.iter().map(|c| escape(c.header()));
+                let mut lines = vec![header.collect::<Vec<_>>().join(separator)];
+                lines.extend(records.iter().map(|record| {
+                    columns
+                        .iter()
+                        .map(|c| escape(&plain_text(&c.value(record))))
+                        .collect::<Vec<_>>()
+                        .join(separator)
+                }));
+                lines.join("\n")
+            }
+        };
+
+        Ok(text)
+    }
+
+    /// Resolve `--fields` (or the record's defaults) against what `T` offers
+    fn columns<T: Record>(&self) -> OutputResult<Vec<Column>> {
+        let Some(fields) = &self.fields else {
+            return Ok(T::DEFAULT_FIELDS
+                .iter()
+                .map(|f| Column::Field(f.to_string()))
+                .collect());
+        };
+
+        fields
+            .iter()
+            .map(|field| Column::resolve::<T>(field))
+            .collect()
+    }
+
+    fn select<T: Record>(&self, record: &T, columns: &[Column]) -> Value {
+        let object: Map<String, Value> = columns
+            .iter()
+            .map(|c| (c.header().to_string(), c.value(record)))
+            .collect();
+        Value::Object(object)
+    }
+}
+
+/// A value as bare text: no quotes on strings, lists joined with commas
+pub(super) fn plain_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(plain_text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// Table cells stay on one line
+fn table_cell(value: &Value) -> String {
+    plain_text(value)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn csv_escape(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// TSV has no quoting, so separators inside a value become spaces
+fn tsv_escape(text: &str) -> String {
+    text.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Pad every column but the last to its widest cell
+fn align(rows: &[Vec<String>]) -> String {
+    let columns = rows.first().map_or(0, Vec::len);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| rows.iter().map(|row| row[i].width()).max().unwrap_or(0))
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            let mut line = String::new();
+            for (i, cell) in row.iter().enumerate() {
+                line.push_str(cell);
+                if i + 1 < columns {
+                    line.push_str(&" ".repeat(widths[i] - cell.width() + 2));
+                }
+            }
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CustomField, Task};
+    use crate::test_support::domain_task;
+
+    fn task(gid: &str, name: &str, priority: &str) -> Task {
+        let mut task = domain_task(gid, name);
+        task.assignee_name = Some("Ada".to_string());
+        task.tags = vec!["bug".to_string(), "ui".to_string()];
+        task.custom_fields = vec![CustomField {
+            gid: "cf1".to_string(),
+            name: "Priority".to_string(),
+            display_value: Some(priority.to_string()),
+            text_value: None,
+            number_value: None,
+            enum_value: None,
+        }];
+        task
+    }
+
+    fn fields(fields: &[&str]) -> Option<Vec<String>> {
+        Some(fields.iter().map(|f| f.to_string()).collect())
+    }
+
+    #[test]
+    fn test_table_aligns_columns() {
+        let output = Output::new(OutputFormat::Table, fields(&["id", "name", "tags"]));
+        let text = output
+            .render_list(&[task("1", "Short", "High"), task("22", "Longer name", "Low")])
+            .unwrap();
+
+        assert_eq!(
+            text,
+            "ID  NAME         TAGS\n\
+             1   Short        bug, ui\n\
+             22  Longer name  bug, ui"
+        );
+    }
+
+    #[test]
+    fn test_csv_and_tsv_escape_values_and_include_custom_fields() {
+        let tasks = [task("1", "Fix \"login\", again", "High")];
+
+        let csv = Output::new(OutputFormat::Csv, fields(&["name", "priority", "tags"]))
+            .render_list(&tasks)
+            .unwrap();
+        assert_eq!(
+            csv,
+            "name,priority,tags\n\"Fix \"\"login\"\", again\",High,\"bug, ui\""
+        );
+
+        let tsv = Output::new(OutputFormat::Tsv, fields(&["id", "cf:Priority"]))
+            .render_list(&tasks)
+            .unwrap();
+        assert_eq!(tsv, "id\tPriority\n1\tHigh");
+    }
+
+    #[test]
+    fn test_json_with_fields_keeps_only_those_keys() {
+        let output = Output::new(OutputFormat::Ndjson, fields(&["id", "completed"]));
+        let text = output
+            .render_list(&[task("1", "A", "High"), task("2", "B", "Low")])
+            .unwrap();
+
+        assert_eq!(
+            text,
+            "{\"completed\":false,\"id\":\"1\"}\n{\"completed\":false,\"id\":\"2\"}"
+        );
+    }
+
+    #[test]
+    fn test_unknown_field_is_an_error_without_custom_fields() {
+        let workspace = crate::domain::Workspace {
+            id: "w1".into(),
+            name: "Acme".to_string(),
+            is_organization: true,
+        };
+        let output = Output::new(OutputFormat::Table, fields(&["id", "priority"]));
+
+        let error = output.render(&workspace).unwrap_err();
+        assert!(
+            matches!(error, OutputError::UnknownField { ref field, .. } if field == "priority")
+        );
+    }
+}
@@ -0,0 +1,7 @@
+pub mod format;
+pub mod record;
+pub mod template;
+
+pub use format::*;
+pub use record::*;
+pub use template::*;
@@ -0,0 +1,161 @@
+use crate::domain::{Comment, Project, Section, Task, Workspace};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Something the CLI prints, addressable by column name for `--fields`
+pub trait Record: Serialize {
+    /// Every built-in field name, in the order `--help` lists them
+    const FIELDS: &'static [&'static str];
+
+    /// Columns used when `--fields` is not given
+    const DEFAULT_FIELDS: &'static [&'static str];
+
+    /// Whether names that are not built-in fields refer to custom fields
+    const HAS_CUSTOM_FIELDS: bool = false;
+
+    /// The value of a built-in field, or `None` if `name` is not one
+    fn field(&self, name: &str) -> Option<Value>;
+
+    /// The display value of the custom field called `name` (case-insensitive)
+    fn custom_field(&self, _name: &str) -> Value {
+        Value::Null
+    }
+}
+
+impl Record for Task {
+    const FIELDS: &'static [&'static str] = &[
+        "id",
+        "name",
+        "completed",
+        "due",
+        "start",
+        "overdue",
+        "assignee",
+        "assignee_id",
+        "projects",
+        "sections",
+        "tags",
+        "parent",
+        "subtasks",
+        "type",
+        "description",
+        "created",
+        "modified",
+    ];
+    const DEFAULT_FIELDS: &'static [&'static str] = &["id", "name", "due", "assignee", "projects"];
+    const HAS_CUSTOM_FIELDS: bool = true;
+
+    fn field(&self, name: &str) -> Option<Value> {
+        let value = match name {
+            "id" => json!(self.id),
+            "name" => json!(self.name),
+            "completed" => json!(self.completed),
+            "due" => json!(self.due),
+            "start" => json!(self.start_on),
+            "overdue" => json!(self.is_overdue()),
+            "assignee" => json!(self.assignee_name),
+            "assignee_id" => json!(self.assignee),
+            "projects" => json!(self.projects.iter().map(|p| &p.name).collect::<Vec<_>>()),
+            "sections" => json!(self
+                .memberships
+                .iter()
+                .filter_map(|m| m.section.as_ref().map(|s| &s.name))
+                .collect::<Vec<_>>()),
+            "tags" => json!(self.tags),
+            "parent" => json!(self.parent),
+            "subtasks" => json!(self.num_subtasks),
+            "type" => json!(self.resource_subtype),
+            "description" => json!(self.description),
+            "created" => json!(self.created_at),
+            "modified" => json!(self.modified_at),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn custom_field(&self, name: &str) -> Value {
+        let value = self
+            .custom_fields
+            .iter()
+            .find(|cf| cf.name.eq_ignore_ascii_case(name))
+            .and_then(|cf| cf.display_value.clone());
+        json!(value)
+    }
+}
+
+impl Record for Comment {
+    const FIELDS: &'static [&'static str] = &["id", "created", "author", "type", "text", "task"];
+    const DEFAULT_FIELDS: &'static [&'static str] = &["created", "author", "text"];
+
+    fn field(&self, name: &str) -> Option<Value> {
+        let value = match name {
+            "id" => json!(self.id),
+            "created" => json!(self.created_at),
+            "author" => json!(self.author.as_ref().map(|a| &a.name)),
+            "type" => json!(self.resource_subtype.as_ref().or(self.story_type.as_ref())),
+            "text" => json!(self.text),
+            "task" => json!(self.task_id),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl Record for Workspace {
+    const FIELDS: &'static [&'static str] = &["id", "name", "organization"];
+    const DEFAULT_FIELDS: &'static [&'static str] = &["id", "name", "organization"];
+
+    fn field(&self, name: &str) -> Option<Value> {
+        let value = match name {
+            "id" => json!(self.id),
+            "name" => json!(self.name),
+            "organization" => json!(self.is_organization),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl Record for Project {
+    const FIELDS: &'static [&'static str] = &[
+        "id",
+        "name",
+        "archived",
+        "color",
+        "description",
+        "workspace",
+        "created",
+        "modified",
+    ];
+    const DEFAULT_FIELDS: &'static [&'static str] = &["id", "name", "archived"];
+
+    fn field(&self, name: &str) -> Option<Value> {
+        let value = match name {
+            "id" => json!(self.id),
+            "name" => json!(self.name),
+            "archived" => json!(self.archived),
+            "color" => json!(self.color),
+            "description" => json!(self.description),
+            "workspace" => json!(self.workspace),
+            "created" => json!(self.created_at),
+            "modified" => json!(self.modified_at),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl Record for Section {
+    const FIELDS: &'static [&'static str] = &["id", "name", "project"];
+    const DEFAULT_FIELDS: &'static [&'static str] = &["id", "name"];
+
+    fn field(&self, name: &str) -> Option<Value> {
+        let value = match name {
+            "id" => json!(self.id),
+            "name" => json!(self.name),
+            "project" => json!(self.project),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
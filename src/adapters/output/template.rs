@@ -0,0 +1,364 @@
+use super::format::{plain_text, Column};
+use super::{OutputError, OutputResult, Record};
+use crate::domain::Zone;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, NaiveDate};
+use std::fmt::{self, Write};
+use unicode_width::UnicodeWidthStr;
+
+/// A `--format` template such as `{{id}}\t{{name | truncate 40}}\t{{due | date "%a"}}`.
+///
+/// `{{ }}` holds a field name, the same names `--fields` takes (quote names with
+/// spaces: `{{"Story Points"}}`), followed by any number of `| helper` steps:
+///
+/// - `date "FORMAT"`: reformat a date or timestamp with strftime codes, in the
+///   configured time zone
+/// - `truncate N`: cut to N characters, ending in `…` when shortened
+/// - `default "TEXT"`: use TEXT when the value is empty
+/// - `pad N`: right-pad to N columns so fields line up
+///
+/// `\t`, `\n` and `\\` in the surrounding text become tab, newline and backslash.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Field { name: String, helpers: Vec<Helper> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Helper {
+    Date(String),
+    Truncate(usize),
+    Default(String),
+    Pad(usize),
+}
+
+impl Template {
+    pub fn parse(source: &str) -> OutputResult<Self> {
+        let mut parts = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            push_text(&mut parts, &rest[..start]);
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| invalid(format!("unclosed '{{{{' at '{}'", &rest[start..])))?;
+            parts.push(parse_expression(&after[..end])?);
+            rest = &after[end + 2..];
+        }
+        push_text(&mut parts, rest);
+
+        Ok(Self { parts })
+    }
+
+    pub fn render<T: Record>(&self, record: &T) -> OutputResult<String> {
+        let columns = self.columns::<T>()?;
+        Ok(self.render_with(record, &columns))
+    }
+
+    /// One rendered line per record
+    pub fn render_list<T: Record>(&self, records: &[T]) -> OutputResult<String> {
+        let columns = self.columns::<T>()?;
+        Ok(records
+            .iter()
+            .map(|record| self.render_with(record, &columns))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Resolve every field up front so a typo fails before anything is printed
+    fn columns<T: Record>(&self) -> OutputResult<Vec<Option<Column>>> {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(_) => Ok(None),
+                Part::Field { name, .. } => Column::resolve::<T>(name).map(Some),
+            })
+            .collect()
+    }
+
+    fn render_with<T: Record>(&self, record: &T, columns: &[Option<Column>]) -> String {
+        let mut out = String::new();
+        for (part, column) in self.parts.iter().zip(columns) {
+            match (part, column) {
+                (Part::Text(text), _) => out.push_str(text),
+                (Part::Field { helpers, .. }, Some(column)) => {
+                    let value = plain_text(&column.value(record));
+                    out.push_str(&helpers.iter().fold(value, |value, h| h.apply(value)));
+                }
+                (Part::Field { .. }, None) => {}
+            }
+        }
+        out
+    }
+}
+
+impl Helper {
+    fn apply(&self, value: String) -> String {
+        match self {
+            Helper::Date(format) => format_date(&value, format).unwrap_or(value),
+            Helper::Truncate(max) => {
+                if value.chars().count() <= *max {
+                    value
+                } else {
+                    let mut cut: String = value.chars().take(max.saturating_sub(1)).collect();
+                    cut.push('…');
+                    cut
+                }
+            }
+            Helper::Default(fallback) if value.is_empty() => fallback.clone(),
+            Helper::Default(_) => value,
+            Helper::Pad(width) => {
+                let padding = width.saturating_sub(value.width());
+                value + &" ".repeat(padding)
+            }
+        }
+    }
+}
+
+/// Reformat a `YYYY-MM-DD` date or RFC 3339 timestamp; `None` leaves other text
+/// alone, as well as values lacking what the format asks for, like `%H` on a date
+fn format_date(value: &str, format: &str) -> Option<String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return write_formatted(date.format(format));
+    }
+    let instant = DateTime::parse_from_rfc3339(value).ok()?.to_utc();
+    // Keep the zone so `%z` and `%Z` have an offset to show
+    match Zone::current() {
+        Zone::Local => write_formatted(instant.with_timezone(&Local).format(format)),
+        Zone::Named(tz) => write_formatted(instant.with_timezone(&tz).format(format)),
+    }
+}
+
+/// Render a chrono format; `to_string` would panic where this returns `None`
+fn write_formatted(formatted: impl fmt::Display) -> Option<String> {
+    let mut out = String::new();
+    write!(out, "{formatted}").ok()?;
+    Some(out)
+}
+
+fn invalid(message: String) -> OutputError {
+    OutputError::Template(message)
+}
+
+fn push_text(parts: &mut Vec<Part>, raw: &str) {
+    if raw.is_empty() {
+        return;
+    }
+
+    let mut text = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => text.push('\t'),
+            Some('n') => text.push('\n'),
+            Some('\\') => text.push('\\'),
+            Some(other) => {
+                text.push('\\');
+                text.push(other);
+            }
+            None => text.push('\\'),
+        }
+    }
+    parts.push(Part::Text(text));
+}
+
+fn parse_expression(expression: &str) -> OutputResult<Part> {
+    let tokens = tokenize(expression)?;
+    let mut segments = tokens.split(|token| token.is_none());
+
+    let name = match segments.next() {
+        Some([Some(name)]) => name.clone(),
+        _ => {
+            return Err(invalid(format!(
+                "expected one field name in '{{{{{}}}}}'",
+                expression.trim()
+            )))
+        }
+    };
+
+    let helpers = segments
+        .map(|segment| parse_helper(&segment.iter().flatten().cloned().collect::<Vec<_>>()))
+        .collect::<OutputResult<Vec<_>>>()?;
+
+    Ok(Part::Field { name, helpers })
+}
+
+fn parse_helper(words: &[String]) -> OutputResult<Helper> {
+    let count = |helper: &str, arg: &str| {
+        arg.parse()
+            .map_err(|_| invalid(format!("{helper} needs a number, got '{arg}'")))
+    };
+
+    match words {
+        [name, arg] if name == "date" => {
+            // chrono panics on bad specifiers while formatting, so reject them now
+            if StrftimeItems::new(arg).any(|item| item == Item::Error) {
+                return Err(invalid(format!("invalid date format '{arg}'")));
+            }
+            Ok(Helper::Date(arg.clone()))
+        }
+        [name, arg] if name == "truncate" => Ok(Helper::Truncate(count(name, arg)?)),
+        [name, arg] if name == "default" => Ok(Helper::Default(arg.clone())),
+        [name, arg] if name == "pad" => Ok(Helper::Pad(count(name, arg)?)),
+        [name, ..] if ["date", "truncate", "default", "pad"].contains(&name.as_str()) => {
+            Err(invalid(format!("{name} takes exactly one argument")))
+        }
+        [name, ..] => Err(invalid(format!(
+            "unknown helper '{name}' (expected date, truncate, default or pad)"
+        ))),
+        [] => Err(invalid("empty helper after '|'".to_string())),
+    }
+}
+
+/// Split an expression into words, with `None` for each `|`. Double quotes
+/// keep spaces and `|` inside a word, as in `"Story Points"` or `cf:"A | B"`.
+fn tokenize(expression: &str) -> OutputResult<Vec<Option<String>>> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if c == '|' {
+            chars.next();
+            tokens.push(None);
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() || c == '|' {
+                break;
+            }
+            chars.next();
+            if c != '"' {
+                word.push(c);
+                continue;
+            }
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(c) => word.push(c),
+                    None => {
+                        return Err(invalid(format!(
+                            "unclosed quote in '{}'",
+                            expression.trim()
+                        )))
+                    }
+                }
+            }
+        }
+        tokens.push(Some(word));
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Comment, CommentId, CustomField, Due, Task};
+    use crate::test_support::domain_task;
+
+    fn task() -> Task {
+        let mut task = domain_task("42", "Write the quarterly report");
+        task.due = Some(Due::On("2026-10-16".parse().unwrap()));
+        task.custom_fields = vec![CustomField {
+            gid: "cf1".to_string(),
+            name: "Story Points".to_string(),
+            display_value: Some("5".to_string()),
+            text_value: None,
+            number_value: None,
+            enum_value: None,
+        }];
+        task
+    }
+
+    fn render(source: &str) -> String {
+        Template::parse(source).unwrap().render(&task()).unwrap()
+    }
+
+    #[test]
+    fn test_fields_escapes_and_custom_fields() {
+        assert_eq!(
+            render("{{id}}\\t{{ name }}"),
+            "42\tWrite the quarterly report"
+        );
+        assert_eq!(render("{{\"Story Points\"}}pt"), "5pt");
+        assert_eq!(render("{{cf:\"story points\"}}"), "5");
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(render("{{due | date \"%a %d %b\"}}"), "Fri 16 Oct");
+        assert_eq!(render("{{name | truncate 9}}"), "Write th…");
+        assert_eq!(render("{{assignee | default \"nobody\"}}!"), "nobody!");
+        assert_eq!(render("{{assignee|default \"a | b\"}}"), "a | b");
+        assert_eq!(render("[{{id | pad 4}}]"), "[42  ]");
+        assert_eq!(
+            render("{{start | date \"%d\" | default \"-\"}} {{name | truncate 5}}"),
+            "- Writ…"
+        );
+    }
+
+    #[test]
+    fn test_date_formats_the_value_cannot_supply_keep_it_as_is() {
+        assert_eq!(render("{{due | date \"%H:%M\"}}"), "2026-10-16");
+
+        let comment = Comment {
+            id: CommentId("c1".to_string()),
+            text: Some("Done".to_string()),
+            author: None,
+            created_at: "2026-10-16T09:30:00Z".parse().unwrap(),
+            task_id: "42".into(),
+            story_type: Some("comment".to_string()),
+            resource_subtype: Some("comment_added".to_string()),
+        };
+        let offset = Template::parse("{{created | date \"%z\"}}")
+            .unwrap()
+            .render(&comment)
+            .unwrap();
+        assert!(
+            offset.len() == 5 && offset.starts_with(['+', '-']),
+            "{offset:?} should be a UTC offset"
+        );
+    }
+
+    #[test]
+    fn test_render_list_is_one_line_per_record() {
+        let template = Template::parse("{{id}}").unwrap();
+        let tasks = [domain_task("1", "A"), domain_task("2", "B")];
+
+        assert_eq!(template.render_list(&tasks).unwrap(), "1\n2");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for source in [
+            "{{name",
+            "{{name | shout}}",
+            "{{name | truncate many}}",
+            "{{name | pad}}",
+            "{{due | date \"%Q\"}}",
+            "{{\"open}}",
+            "{{}}",
+        ] {
+            assert!(
+                matches!(Template::parse(source), Err(OutputError::Template(_))),
+                "{source:?} should be rejected"
+            );
+        }
+    }
+}
@@ -1,25 +1,59 @@
 use color_eyre::Result;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 // Removed tui_markdown due to version compatibility issues
 use super::{
+    editor,
     event::{AppEvent, EventHandler},
+    fuzzy::{self, TaskSearch},
     md,
-    widgets::SearchBar,
+    message::{AppMessage, LoadedDetails, LoadedTasks, Reply},
+    views::Board,
+    widgets::{CommentComposer, Picker, SearchBar, TaskForm},
+};
+use crate::application::{
+    AppResult, EventCursor, EventFeed, LiveUpdate, MutationOutcome, MutationPipeline, StateManager,
+    SyncReport,
+};
+use crate::domain::{
+    parse_due_date, Comment, NewTask, ProjectId, Task, TaskFilter, TaskId, TaskQuery, TaskUpdate,
+    User, UserId, WorkspaceId, Zone,
 };
-use crate::application::StateManager;
-use crate::domain::{Comment, Task, TaskId};
 use ratatui::{
     prelude::*,
-    widgets::{
-        Block, BorderType, Borders, Cell, Paragraph, Row, Scrollbar, ScrollbarOrientation,
-        ScrollbarState, Table, TableState, Wrap,
-    },
+    widgets::{Block, BorderType, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
 };
+use std::future::Future;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// How often to try sending offline changes, or to check whether Asana is back
+const OFFLINE_SYNC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+/// How long the cursor must rest on a row before its details are fetched
+const DETAIL_LOAD_DELAY: std::time::Duration = std::time::Duration::from_millis(150);
+/// Rows either side of the cursor whose details are fetched ahead of it
+const PREFETCH_NEIGHBOURS: usize = 3;
+/// Frames of the status bar spinner shown while anything loads
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+/// How often to ask Asana what changed in the shown list
+const EVENT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+/// How long a row someone else changed stays highlighted
+const FLASH_DURATION: std::time::Duration = std::time::Duration::from_secs(2);
+/// How long a notice about changes Asana didn't take stays in the status bar
+const NOTICE_DURATION: std::time::Duration = std::time::Duration::from_secs(30);
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppMode {
     Main, // Split layout: task list + details
     Help,
+    NewTask,
+    ComposeComment,
+    WorkspacePicker,
+    ProjectPicker,
+    Board,
+    AssigneePicker,
+    SetDueDate,
+    WorkspaceSearch,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -30,12 +64,6 @@ pub enum FocusedPane {
     Comments,    // Right bottom pane
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum TaskDetailPane {
-    Description,
-    Comments,
-}
-
 pub struct App {
     state_manager: Arc<StateManager>,
 
@@ -47,26 +75,107 @@ pub struct App {
     // Search
     search_bar: SearchBar,
     search_query: String,
+    task_search: TaskSearch,
+    // Last query that parsed; kept while the user is mid-way through a token
+    task_query: TaskQuery,
+    // Filter pushed down to the API for the current query; `None` is the base list
+    api_filter: Option<TaskFilter>,
+    // Project the task list is scoped to; `None` is My Tasks
+    project_scope: Option<(ProjectId, String)>,
+    // Text searched for across the workspace by Asana, replacing My Tasks with the results
+    workspace_search: Option<String>,
+    workspace_search_form: Option<TaskForm>,
+    // Matched name characters for the current search, worked out as rows are drawn
+    search_highlights: HashMap<TaskId, Vec<u32>>,
 
     // Task list
+    current_user_id: Option<UserId>,
     tasks: Vec<Task>,
     task_list_state: TableState,
     filtered_tasks: Vec<Task>,
 
+    // Subtask tree: expanded parents, their fetched subtasks, and the depth of each nested row
+    expanded_tasks: HashSet<TaskId>,
+    subtasks: HashMap<TaskId, Vec<Task>>,
+    task_depths: HashMap<TaskId, usize>,
+
+    // Network work runs on spawned tasks that report back through here
+    messages: mpsc::UnboundedSender<AppMessage>,
+    inbox: mpsc::UnboundedReceiver<AppMessage>,
+    // The task list load in flight, and a count of loads so a superseded one is ignored
+    tasks_load: Option<JoinHandle<()>>,
+    tasks_generation: u64,
+    // The details load in flight and its task, dropped when the cursor moves on
+    detail_load: Option<(TaskId, JoinHandle<()>)>,
+    // The row the cursor stopped on and since when, so details load once it settles
+    detail_wanted: Option<(TaskId, std::time::Instant)>,
+    // A request set off by a key press, e.g. opening a picker, and what it is
+    // doing for the status bar; others are ignored until its reply arrives
+    request: Option<(&'static str, JoinHandle<()>)>,
+    spinner_epoch: std::time::Instant,
+
     // Loading states
     is_loading: bool,
     error_message: Option<String>,
+    // When the shown list was fetched, while it is a cached copy from an earlier run
+    stale_since: Option<chrono::DateTime<chrono::Utc>>,
+    // Startup refresh of the cached list, running while it is shown
+    background_refresh: Option<tokio::task::JoinHandle<AppResult<()>>>,
+
+    // Task changes already shown and still on their way to Asana
+    mutations: MutationPipeline,
+    // Offline mode: how many changes wait for Asana, and the background
+    // attempt to send them
+    pending_changes: usize,
+    offline_sync: Option<tokio::task::JoinHandle<AppResult<SyncReport>>>,
+    last_offline_sync: std::time::Instant,
+    // Changes Asana rejected or a sync dropped, and when
+    status_notice: Option<(String, std::time::Instant)>,
+
+    // Live refresh: the list's place in its Asana event stream (taken while a
+    // poll is out), a count of restarts so a superseded poll is ignored, and
+    // the rows that changed with when, to flash them
+    event_cursor: Option<EventCursor>,
+    events_poll: Option<JoinHandle<()>>,
+    events_generation: u64,
+    last_events_poll: std::time::Instant,
+    flashed_rows: HashMap<TaskId, std::time::Instant>,
 
     // Comment input
+    comment_composer: Option<CommentComposer>,
+
+    // Set when the description should be opened in $EDITOR by the main loop
+    description_edit_requested: bool,
+
+    // Modal form for creating tasks
+    task_form: Option<TaskForm>,
+    // Parent of the subtask being created in the form
+    subtask_parent: Option<TaskId>,
+
+    // Modal list for switching workspaces
+    workspace_picker: Option<Picker<WorkspaceId>>,
+
+    // Modal list for scoping the task list to a project; `None` entries mean My Tasks
+    project_picker: Option<Picker<Option<ProjectId>>>,
+
+    // Section columns of the scoped project, shown in place of the panes
+    board: Option<Board>,
+
+    // Assignee picker and due date prompt, and the task they apply to
+    assignee_picker: Option<Picker<Option<UserId>>>,
+    due_date_form: Option<TaskForm>,
+    edited_task: Option<TaskId>,
+    // People in the current workspace, fetched when first needed
+    workspace_users: Option<Vec<User>>,
 
     // Task detail
     current_task: Option<Task>,
     task_comments: Vec<Comment>,
+    detail_subtasks: Vec<Task>,
     detail_scroll_offset: u16, // Legacy - will be replaced
     detail_loading: bool,
 
     // Task detail panes
-    focused_detail_pane: TaskDetailPane,
     description_scroll_offset: u16,
     comments_scroll_offset: u16,
     needs_task_reload: bool,
@@ -132,6 +241,8 @@ impl App {
     }
 
     pub fn new(state_manager: Arc<StateManager>) -> Self {
+        let mutations = MutationPipeline::new(state_manager.clone());
+        let (messages, inbox) = mpsc::unbounded_channel();
         let mut app = Self {
             state_manager,
             mode: AppMode::Main,
@@ -139,18 +250,60 @@ impl App {
             fullscreen_pane: None,
             search_bar: SearchBar::new(),
             search_query: String::new(),
+            task_search: TaskSearch::new(),
+            task_query: TaskQuery::default(),
+            api_filter: None,
+            project_scope: None,
+            workspace_search: None,
+            workspace_search_form: None,
+            search_highlights: HashMap::new(),
+            current_user_id: None,
             tasks: Vec::new(),
             task_list_state: TableState::default(),
             filtered_tasks: Vec::new(),
+            expanded_tasks: HashSet::new(),
+            subtasks: HashMap::new(),
+            task_depths: HashMap::new(),
+            messages,
+            inbox,
+            tasks_load: None,
+            tasks_generation: 0,
+            detail_load: None,
+            detail_wanted: None,
+            request: None,
+            spinner_epoch: std::time::Instant::now(),
             is_loading: false,
             error_message: None,
+            stale_since: None,
+            background_refresh: None,
+            mutations,
+            pending_changes: 0,
+            offline_sync: None,
+            last_offline_sync: std::time::Instant::now(),
+            status_notice: None,
+            event_cursor: None,
+            events_poll: None,
+            events_generation: 0,
+            last_events_poll: std::time::Instant::now(),
+            flashed_rows: HashMap::new(),
+            comment_composer: None,
+            description_edit_requested: false,
+            task_form: None,
+            subtask_parent: None,
+            workspace_picker: None,
+            project_picker: None,
+            board: None,
+            assignee_picker: None,
+            due_date_form: None,
+            edited_task: None,
+            workspace_users: None,
             current_task: None,
             task_comments: Vec::new(),
+            detail_subtasks: Vec::new(),
             detail_scroll_offset: 0,
             detail_loading: false,
 
             // Task detail panes
-            focused_detail_pane: TaskDetailPane::Description,
             description_scroll_offset: 0,
             comments_scroll_offset: 0,
             needs_task_reload: false,
@@ -168,75 +321,635 @@ impl App {
     }
 
     pub async fn initialize(&mut self) -> Result<()> {
-        self.state_manager.initialize().await?;
-        self.load_tasks().await?;
+        // Show what the last run saw straight away and check with Asana behind it
+        if self.show_cached_tasks().await {
+            let state_manager = self.state_manager.clone();
+            let filter = self.base_task_filter().await?;
+            self.background_refresh = Some(tokio::spawn(async move {
+                state_manager.initialize().await?;
+                state_manager.get_tasks_with_filter(&filter, false).await?;
+                Ok(())
+            }));
+            return Ok(());
+        }
+
+        let state_manager = self.state_manager.clone();
+        self.spawn_request("Signing in", async move {
+            let result = match state_manager.initialize().await {
+                Ok(()) => Ok(state_manager.get_current_workspace().await.is_some()),
+                Err(e) => Err(e),
+            };
+            Reply::Initialized(result)
+        });
         Ok(())
     }
 
-    async fn load_tasks(&mut self) -> Result<()> {
-        self.is_loading = true;
-        self.error_message = None;
+    /// Run `request` in the background and hand its reply to [`Self::show_reply`],
+    /// unless another request is still out
+    fn spawn_request(
+        &mut self,
+        activity: &'static str,
+        request: impl Future<Output = Reply> + Send + 'static,
+    ) {
+        if self.request.is_some() {
+            return;
+        }
+        let messages = self.messages.clone();
+        let handle = tokio::spawn(async move {
+            let _ = messages.send(AppMessage::Reply(Box::new(request.await)));
+        });
+        self.request = Some((activity, handle));
+    }
+
+    /// Fill the list from the previous session's cache; false when there is nothing to show
+    async fn show_cached_tasks(&mut self) -> bool {
+        if !matches!(self.state_manager.restore_session().await, Ok(true)) {
+            return false;
+        }
+        let Ok(filter) = self.base_task_filter().await else {
+            return false;
+        };
+        let Some(cached) = self.state_manager.cached_tasks(&filter).await else {
+            return false;
+        };
+
+        self.current_user_id = self.state_manager.get_current_user().await.map(|u| u.id);
+        self.tasks = cached.items;
+        self.stale_since = Some(cached.fetched_at);
+        self.task_search.set_tasks(&self.tasks);
+        self.update_filtered_tasks();
+        true
+    }
+
+    /// Swap the cached startup list for the fresh one once the background refresh is done
+    pub async fn poll_background_refresh(&mut self) -> Result<()> {
+        if !self
+            .background_refresh
+            .as_ref()
+            .is_some_and(|handle| handle.is_finished())
+        {
+            return Ok(());
+        }
+        let Some(handle) = self.background_refresh.take() else {
+            return Ok(());
+        };
+
+        match handle
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result.map_err(|e| e.to_string()))
+        {
+            Ok(()) => {
+                self.current_user_id = self.state_manager.get_current_user().await.map(|u| u.id);
+
+                // The fresh list is cached now; keep the cursor on the same task
+                self.reload_tasks_keeping_selection().await?;
+            }
+            // The offline sync will notice when Asana is back
+            Err(_) if self.state_manager.is_offline() => {}
+            Err(e) => {
+                self.error_message = Some(format!("Showing cached tasks; refresh failed: {e}"));
+            }
+        }
+
+        Ok(())
+    }
 
-        match self
+    /// Send changes made offline in the background, every so often while
+    /// offline or while any are queued, and reload once they have gone through
+    pub async fn tick_offline_sync(&mut self) -> Result<()> {
+        self.pending_changes = self
             .state_manager
-            .get_tasks_for_current_workspace(true)
+            .pending_changes()
+            .await
+            .map_or(0, |pending| pending.len());
+
+        let Some(handle) = self.offline_sync.take() else {
+            let due = self.last_offline_sync.elapsed() >= OFFLINE_SYNC_INTERVAL;
+            if due && (self.state_manager.is_offline() || self.pending_changes > 0) {
+                let state_manager = self.state_manager.clone();
+                self.offline_sync = Some(tokio::spawn(async move {
+                    state_manager.sync_offline_changes().await
+                }));
+                self.last_offline_sync = std::time::Instant::now();
+            }
+            return Ok(());
+        };
+        if !handle.is_finished() {
+            self.offline_sync = Some(handle);
+            return Ok(());
+        }
+
+        let report = match handle
             .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result.map_err(|e| e.to_string()))
         {
-            Ok(tasks) => {
-                self.tasks = tasks;
-                self.update_filtered_tasks();
-
-                // Reset selection to first item if we have tasks
-                if !self.filtered_tasks.is_empty() {
-                    self.task_list_state.select(Some(0));
-
-                    // Preload comments for visible tasks (first 10)
-                    let visible_tasks: Vec<TaskId> = self
-                        .filtered_tasks
-                        .iter()
-                        .take(10)
-                        .map(|t| t.id.clone())
-                        .collect();
+            Ok(report) => report,
+            Err(e) => {
+                let notice = format!("Offline sync failed: {e}");
+                self.status_notice = Some((notice, std::time::Instant::now()));
+                return Ok(());
+            }
+        };
+
+        if !report.conflicts.is_empty() {
+            let dropped: Vec<String> = report
+                .conflicts
+                .iter()
+                .map(|conflict| {
+                    format!(
+                        "'{}' {} ({})",
+                        conflict.task_name,
+                        conflict.change.change.describe(),
+                        conflict.reason
+                    )
+                })
+                .collect();
+            self.status_notice = Some((
+                format!("Dropped offline changes: {}", dropped.join("; ")),
+                std::time::Instant::now(),
+            ));
+        } else if let Some(reason) = &report.held_back {
+            self.status_notice = Some((
+                format!("Offline changes still queued: {reason}"),
+                std::time::Instant::now(),
+            ));
+        }
+
+        // Back online: show what Asana has now, keeping the cursor where it was
+        let changed = report.sent > 0 || !report.conflicts.is_empty();
+        if !report.still_offline && (changed || self.stale_since.is_some()) {
+            self.reload_tasks_keeping_selection().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn load_tasks(&mut self) -> Result<()> {
+        self.load_tasks_selecting(None).await
+    }
+
+    /// Reload the list with the cursor staying on the task it is on now
+    async fn reload_tasks_keeping_selection(&mut self) -> Result<()> {
+        let selected = self.selected_task().map(|task| task.id.clone());
+        self.load_tasks_selecting(selected).await
+    }
+
+    /// Fetch the list in the background, replacing any load still running, and
+    /// put the cursor on `select` once it arrives, or on the first row
+    async fn load_tasks_selecting(&mut self, select: Option<TaskId>) -> Result<()> {
+        self.is_loading = true;
+        self.error_message = None;
 
-                    // Spawn background task to preload comments
-                    let state_manager = self.state_manager.clone();
-                    tokio::spawn(async move {
-                        for task_id in visible_tasks {
-                            let _ = state_manager.get_task_comments(&task_id).await;
+        let filter = match &self.api_filter {
+            Some(filter) => filter.clone(),
+            None => match self.base_task_filter().await {
+                Ok(filter) => filter,
+                Err(e) => {
+                    self.error_message = Some(format!("Failed to load tasks: {e}"));
+                    self.is_loading = false;
+                    return Ok(());
+                }
+            },
+        };
+
+        if let Some(handle) = self.tasks_load.take() {
+            handle.abort();
+        }
+        self.tasks_generation += 1;
+        let generation = self.tasks_generation;
+        let expanded: Vec<TaskId> = self.expanded_tasks.iter().cloned().collect();
+        let state_manager = self.state_manager.clone();
+        let messages = self.messages.clone();
+
+        self.tasks_load = Some(tokio::spawn(async move {
+            let result = match state_manager.get_tasks_with_filter(&filter, true).await {
+                Ok(tasks) => {
+                    let mut subtasks = HashMap::new();
+                    for parent in expanded {
+                        match state_manager.get_subtasks(&parent).await {
+                            Ok(children) => {
+                                subtasks.insert(parent, children);
+                            }
+                            Err(e) => {
+                                tracing::warn!("Failed to load subtasks of {}: {}", parent, e)
+                            }
                         }
-                    });
+                    }
+                    Ok(LoadedTasks { tasks, subtasks })
+                }
+                Err(e) => Err(e),
+            };
+
+            let _ = messages.send(AppMessage::TasksLoaded {
+                generation,
+                result,
+                select,
+            });
+        }));
+
+        Ok(())
+    }
+
+    /// Take in what background loads have reported since the last call
+    pub async fn process_messages(&mut self) -> Result<()> {
+        while let Ok(message) = self.inbox.try_recv() {
+            match message {
+                AppMessage::TasksLoaded {
+                    generation,
+                    result,
+                    select,
+                } => {
+                    // A newer load replaced this one
+                    if generation == self.tasks_generation {
+                        self.show_loaded_tasks(result, select);
+                    }
+                }
+                AppMessage::TaskDetailsLoaded(details) => self.show_task_details(*details),
+                AppMessage::EventsPolled {
+                    generation,
+                    cursor,
+                    result,
+                } => {
+                    if generation == self.events_generation {
+                        self.events_poll = None;
+                        self.event_cursor = Some(cursor);
+                        self.show_live_update(result).await?;
+                    }
+                }
+                AppMessage::Reply(reply) => {
+                    self.request = None;
+                    self.show_reply(*reply).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finish what a key press set off once Asana has answered
+    async fn show_reply(&mut self, reply: Reply) -> Result<()> {
+        match reply {
+            Reply::Initialized(result) => {
+                let has_workspace = result?;
+                self.current_user_id = self.state_manager.get_current_user().await.map(|u| u.id);
+
+                // With several workspaces and none remembered, ask which one to use
+                if has_workspace {
+                    self.load_tasks().await?;
                 } else {
-                    self.task_list_state.select(None);
+                    self.open_workspace_picker();
+                }
+            }
+            Reply::SubtasksLoaded { parent, result } => match result {
+                Ok(subtasks) => {
+                    self.subtasks.insert(parent.clone(), subtasks);
+                    self.expanded_tasks.insert(parent);
+                    self.apply_search_results();
+                }
+                Err(e) => self.error_message = Some(format!("Failed to load subtasks: {e}")),
+            },
+            Reply::UsersLoaded { task, result } => match result {
+                Ok(users) => {
+                    self.workspace_users = Some(users);
+                    if self.mode == AppMode::Main {
+                        self.show_assignee_picker(task);
+                    }
+                }
+                Err(e) => self.error_message = Some(format!("Failed to list users: {e}")),
+            },
+            Reply::WorkspacesLoaded(result) => match result {
+                Ok((workspaces, current)) if self.mode == AppMode::Main => {
+                    let items = workspaces.into_iter().map(|w| (w.id, w.name)).collect();
+                    self.workspace_picker = Some(
+                        Picker::new("Switch Workspace", items)
+                            .with_active(|id| Some(id) == current.as_ref()),
+                    );
+                    self.mode = AppMode::WorkspacePicker;
+                }
+                Ok(_) => {}
+                Err(e) => self.error_message = Some(format!("Failed to list workspaces: {e}")),
+            },
+            Reply::ProjectsLoaded(result) => match result {
+                Ok(projects) if self.mode == AppMode::Main => {
+                    let current = self.project_scope.as_ref().map(|(id, _)| id.clone());
+                    let items = std::iter::once((None, "My Tasks".to_string()))
+                        .chain(projects.into_iter().map(|p| (Some(p.id), p.name)))
+                        .collect();
+                    self.project_picker =
+                        Some(Picker::new("Browse Project", items).with_active(|id| *id == current));
+                    self.mode = AppMode::ProjectPicker;
+                }
+                Ok(_) => {}
+                Err(e) => self.error_message = Some(format!("Failed to list projects: {e}")),
+            },
+            Reply::BoardLoaded {
+                project,
+                name,
+                refresh,
+                result,
+            } => match result {
+                Ok((sections, tasks)) => {
+                    let board = Board::new(&project, name, sections, tasks);
+                    if refresh && self.board.is_some() {
+                        self.board = Some(board);
+                    } else if !refresh && self.mode == AppMode::Main {
+                        self.board = Some(board);
+                        self.mode = AppMode::Board;
+                    }
+                }
+                Err(e) if refresh => self.set_board_error(format!("Refresh failed: {e}")),
+                Err(e) => self.error_message = Some(format!("Failed to load board: {e}")),
+            },
+            Reply::BoardTaskMoved { task, step, result } => match result {
+                Ok(()) => {
+                    let Some(board) = self.board.as_mut() else {
+                        return Ok(());
+                    };
+                    board.set_error(None);
+                    if board
+                        .selected_task()
+                        .is_some_and(|selected| selected.id == task)
+                    {
+                        board.move_selected(step);
+                    } else {
+                        // The cursor moved on meanwhile; show the board as Asana has it
+                        self.refresh_board();
+                    }
+                }
+                Err(e) => self.set_board_error(format!("Failed to move task: {e}")),
+            },
+            Reply::TaskCreated(result) => match result {
+                Ok(task) => {
+                    self.task_form = None;
+                    if self.mode == AppMode::NewTask {
+                        self.mode = AppMode::Main;
+                    }
+                    // Show the new subtask under its parent
+                    if let Some(parent) = self.subtask_parent.take() {
+                        self.expanded_tasks.insert(parent);
+                    }
+                    self.load_tasks_selecting(Some(task.id)).await?;
+                }
+                Err(e) => match self.task_form.as_mut() {
+                    Some(form) => form.set_error(format!("Failed to create task: {e}")),
+                    None => self.error_message = Some(format!("Failed to create task: {e}")),
+                },
+            },
+            Reply::CommentPosted { task, result } => match result {
+                Ok(comment) => {
+                    self.comment_composer = None;
+                    if self.mode == AppMode::ComposeComment {
+                        self.mode = AppMode::Main;
+                    }
+                    if self.current_task.as_ref().is_some_and(|t| t.id == task) {
+                        self.task_comments.push(comment);
+                        self.cached_comments_lines = None;
+
+                        // Scroll to the new comment at the bottom
+                        self.comments_scroll_offset = u16::MAX;
+                        self.clamp_scroll_offset();
+                    }
+                }
+                Err(e) => match self.comment_composer.as_mut() {
+                    Some(composer) => composer.set_error(format!("Failed to post comment: {e}")),
+                    None => self.error_message = Some(format!("Failed to post comment: {e}")),
+                },
+            },
+        }
+
+        Ok(())
+    }
+
+    /// What the shown list follows for live refresh; workspace search results have nothing to follow
+    fn event_feed(&self) -> Option<EventFeed> {
+        if self.workspace_search.is_some() {
+            return None;
+        }
+        Some(match &self.project_scope {
+            Some((project, _)) => EventFeed::Project(project.clone()),
+            None => EventFeed::MyTasks,
+        })
+    }
+
+    /// Poll the shown list's event stream in the background: straight away
+    /// for a list not followed yet, then every [`EVENT_POLL_INTERVAL`]
+    pub fn tick_live_refresh(&mut self) {
+        self.flashed_rows
+            .retain(|_, at| at.elapsed() < FLASH_DURATION);
+        if self.events_poll.is_some() || self.state_manager.is_offline() {
+            return;
+        }
+        let Some(feed) = self.event_feed() else {
+            self.event_cursor = None;
+            return;
+        };
+
+        let mut cursor = match self.event_cursor.take() {
+            Some(cursor) if cursor.feed == feed => {
+                if self.last_events_poll.elapsed() < EVENT_POLL_INTERVAL {
+                    self.event_cursor = Some(cursor);
+                    return;
                 }
+                cursor
             }
+            _ => EventCursor::new(feed),
+        };
+
+        let generation = self.events_generation;
+        let state_manager = self.state_manager.clone();
+        let messages = self.messages.clone();
+        self.events_poll = Some(tokio::spawn(async move {
+            let result = state_manager.poll_events(&mut cursor).await;
+            let _ = messages.send(AppMessage::EventsPolled {
+                generation,
+                cursor,
+                result,
+            });
+        }));
+        self.last_events_poll = std::time::Instant::now();
+    }
+
+    /// Follow a different event stream from scratch, e.g. after switching workspaces
+    fn restart_live_refresh(&mut self) {
+        if let Some(handle) = self.events_poll.take() {
+            handle.abort();
+        }
+        self.event_cursor = None;
+        self.events_generation += 1;
+    }
+
+    /// Show what changed in Asana: reload the list from the patched caches,
+    /// flash the rows that changed and refresh the open task if it was one
+    async fn show_live_update(&mut self, result: AppResult<LiveUpdate>) -> Result<()> {
+        let update = match result {
+            Ok(update) => update,
+            // The offline sync notices when Asana is back
+            Err(_) if self.state_manager.is_offline() => return Ok(()),
             Err(e) => {
-                self.error_message = Some(format!("Failed to load tasks: {e}"));
+                tracing::warn!("Live refresh failed: {}", e);
+                return Ok(());
             }
+        };
+        if update.is_empty() {
+            return Ok(());
         }
 
+        let current = self.current_task.as_ref().map(|task| task.id.clone());
+        let current_changed = match &update {
+            LiveUpdate::Changed {
+                tasks, commented, ..
+            } => {
+                let now = std::time::Instant::now();
+                for task in tasks {
+                    self.flashed_rows.insert(task.id.clone(), now);
+                }
+                current.as_ref().is_some_and(|current| {
+                    tasks.iter().any(|task| &task.id == current) || commented.contains(current)
+                })
+            }
+            LiveUpdate::Resync => true,
+        };
+        if let (Some(current), true) = (current, current_changed) {
+            self.load_task_details(&current);
+        }
+
+        self.reload_tasks_keeping_selection().await
+    }
+
+    fn show_loaded_tasks(&mut self, result: AppResult<LoadedTasks>, select: Option<TaskId>) {
         self.is_loading = false;
-        Ok(())
+        self.tasks_load = None;
+
+        let LoadedTasks { tasks, subtasks } = match result {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load tasks: {e}"));
+                return;
+            }
+        };
+
+        self.tasks = tasks;
+        self.stale_since = None;
+        self.expanded_tasks
+            .retain(|parent| subtasks.contains_key(parent));
+        self.subtasks = subtasks;
+        self.task_search.set_tasks(&self.tasks);
+        self.update_filtered_tasks();
+
+        let selected = select
+            .and_then(|id| self.filtered_tasks.iter().position(|task| task.id == id))
+            .or((!self.filtered_tasks.is_empty()).then_some(0));
+        self.task_list_state.select(selected);
+        self.needs_task_reload = true;
     }
 
     fn update_filtered_tasks(&mut self) {
-        if self.search_query.is_empty() {
+        match TaskQuery::parse(&self.search_query) {
+            Ok(query) => {
+                self.task_query = query;
+                self.search_bar.set_error(None);
+            }
+            Err(e) => {
+                self.search_bar
+                    .set_error(Some(format!("{e} (column {})", e.position + 1)));
+            }
+        }
+
+        self.task_search.set_query(&self.task_query.text);
+        // Short wait so most keystrokes see their results on the next frame
+        self.task_search.tick(10);
+        self.apply_search_results();
+    }
+
+    /// The filter behind the list before any search bar query: the scoped project
+    /// or My Tasks, widened to everyone's tasks during a workspace search
+    async fn base_task_filter(&self) -> AppResult<TaskFilter> {
+        let mut filter = match &self.project_scope {
+            Some((project, _)) => StateManager::project_task_filter(project),
+            None => self.state_manager.default_task_filter().await?,
+        };
+
+        if let Some(text) = &self.workspace_search {
+            filter.assignee = None;
+            filter.search_query = Some(text.clone());
+        }
+
+        Ok(filter)
+    }
+
+    /// Refetch the task list when the query can narrow or widen the API request,
+    /// e.g. `project:<gid>` or `is:completed`
+    async fn apply_query_push_down(&mut self) -> Result<()> {
+        let api_filter = self.pushed_down_filter().await?;
+
+        let current_key = self.api_filter.as_ref().map(TaskFilter::to_cache_key);
+        if api_filter.as_ref().map(TaskFilter::to_cache_key) != current_key {
+            self.api_filter = api_filter;
+            self.load_tasks().await?;
+        }
+
+        Ok(())
+    }
+
+    /// The API filter for the current query, or `None` when it adds nothing to the base filter
+    async fn pushed_down_filter(&self) -> AppResult<Option<TaskFilter>> {
+        let base = self.base_task_filter().await?;
+        let pushed = self.task_query.push_down(&base);
+        Ok((pushed.to_cache_key() != base.to_cache_key()).then_some(pushed))
+    }
+
+    /// Pick up fuzzy matches that finished after the last keystroke
+    pub fn tick_search(&mut self) {
+        if self.task_search.tick(0) && !self.task_query.text.is_empty() {
+            self.apply_search_results();
+        }
+    }
+
+    fn apply_search_results(&mut self) {
+        self.search_highlights.clear();
+
+        let text = self.task_query.text.as_str();
+        if text.is_empty() {
             self.filtered_tasks = self.tasks.clone();
         } else {
-            let query_lower = self.search_query.to_lowercase();
-            self.filtered_tasks = self
-                .tasks
-                .iter()
-                .filter(|task| {
-                    task.name.to_lowercase().contains(&query_lower)
-                        || task
-                            .description
-                            .as_ref()
-                            .is_some_and(|desc| desc.to_lowercase().contains(&query_lower))
-                })
-                .cloned()
-                .collect();
+            let mut matched = vec![false; self.tasks.len()];
+            let mut filtered = Vec::new();
+
+            // Fuzzy name matches first, best first
+            for index in self.task_search.matches() {
+                let Some(task) = self.tasks.get(index) else {
+                    continue;
+                };
+                matched[index] = true;
+                filtered.push(task.clone());
+            }
+
+            // Then tasks that only mention the query in their description
+            let query_lower = text.to_lowercase();
+            for (task, _) in self.tasks.iter().zip(&matched).filter(|(_, m)| !**m) {
+                if task
+                    .description
+                    .as_ref()
+                    .is_some_and(|desc| desc.to_lowercase().contains(&query_lower))
+                {
+                    filtered.push(task.clone());
+                }
+            }
+
+            self.filtered_tasks = filtered;
+        }
+
+        if !self.task_query.filters.is_empty() {
+            let now = chrono::Utc::now();
+            let me = self.current_user_id.as_ref();
+            self.filtered_tasks
+                .retain(|task| self.task_query.matches(task, me, now));
         }
 
+        self.insert_expanded_subtasks();
+
         // Adjust selection if needed
         if let Some(selected) = self.task_list_state.selected() {
             if selected >= self.filtered_tasks.len() {
@@ -250,7 +963,99 @@ impl App {
         }
     }
 
-    async fn load_task_details(&mut self, task_id: &TaskId) -> Result<()> {
+    /// Interleave fetched subtasks under their expanded parents
+    fn insert_expanded_subtasks(&mut self) {
+        self.task_depths.clear();
+        if self.expanded_tasks.is_empty() {
+            return;
+        }
+
+        let roots = std::mem::take(&mut self.filtered_tasks);
+        let mut tree = Vec::with_capacity(roots.len());
+        for task in roots {
+            self.push_subtree(task, 0, &mut tree);
+        }
+        self.filtered_tasks = tree;
+    }
+
+    fn push_subtree(&mut self, task: Task, depth: usize, tree: &mut Vec<Task>) {
+        let children = if self.expanded_tasks.contains(&task.id) {
+            self.subtasks.get(&task.id).cloned().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        if depth > 0 {
+            self.task_depths.insert(task.id.clone(), depth);
+        }
+        tree.push(task);
+        for child in children {
+            self.push_subtree(child, depth + 1, tree);
+        }
+    }
+
+    /// Expand or collapse the selected task's subtasks, fetching them on first expand.
+    /// On a subtask without children this collapses its parent instead.
+    fn toggle_selected_subtasks(&mut self) {
+        let Some(task) = self
+            .task_list_state
+            .selected()
+            .and_then(|i| self.filtered_tasks.get(i))
+        else {
+            return;
+        };
+        let id = task.id.clone();
+        let parent = task.parent.clone();
+
+        let focus = if self.expanded_tasks.remove(&id) {
+            None
+        } else if task.has_subtasks() {
+            // Expanded once they arrive
+            if !self.subtasks.contains_key(&id) {
+                let state_manager = self.state_manager.clone();
+                self.spawn_request("Loading subtasks", async move {
+                    let result = state_manager.get_subtasks(&id).await;
+                    Reply::SubtasksLoaded { parent: id, result }
+                });
+                return;
+            }
+            self.expanded_tasks.insert(id);
+            None
+        } else if let Some(parent) = parent.filter(|p| self.expanded_tasks.contains(p)) {
+            self.expanded_tasks.remove(&parent);
+            Some(parent)
+        } else {
+            return;
+        };
+
+        self.apply_search_results();
+
+        if let Some(index) =
+            focus.and_then(|id| self.filtered_tasks.iter().position(|t| t.id == id))
+        {
+            self.task_list_state.select(Some(index));
+            self.needs_task_reload = true;
+        }
+    }
+
+    /// Swap in a changed task wherever it is shown
+    fn replace_task(&mut self, updated: &Task) {
+        for task in self
+            .tasks
+            .iter_mut()
+            .chain(self.subtasks.values_mut().flatten())
+            .chain(self.detail_subtasks.iter_mut())
+            .chain(self.current_task.iter_mut())
+            .filter(|t| t.id == updated.id)
+        {
+            *task = updated.clone();
+        }
+        self.cached_description_lines = None;
+    }
+
+    /// Fetch a task's details, comments and subtasks in the background,
+    /// dropping any load for a task the cursor has left
+    fn load_task_details(&mut self, task_id: &TaskId) {
         self.detail_loading = true;
         self.detail_scroll_offset = 0;
 
@@ -262,30 +1067,82 @@ impl App {
         self.cached_description_lines = None;
         self.cached_comments_lines = None;
 
-        // Load task details and comments in parallel
-        let task_future = self.state_manager.get_task(task_id);
-        let comments_future = self.state_manager.get_task_comments(task_id);
+        if let Some((_, handle)) = self.detail_load.take() {
+            handle.abort();
+        }
+        let loading = task_id.clone();
+        let task_id = task_id.clone();
+        let state_manager = self.state_manager.clone();
+        let messages = self.messages.clone();
+
+        let handle = tokio::spawn(async move {
+            // Load task details and comments in parallel
+            let (task, comments) = tokio::join!(
+                state_manager.get_task(&task_id),
+                state_manager.get_task_comments(&task_id)
+            );
+
+            let subtasks = match &task {
+                Ok(task) if task.has_subtasks() => {
+                    match state_manager.get_subtasks(&task_id).await {
+                        Ok(subtasks) => subtasks,
+                        Err(e) => {
+                            tracing::warn!("Failed to load subtasks of {}: {}", task_id, e);
+                            Vec::new()
+                        }
+                    }
+                }
+                _ => Vec::new(),
+            };
+
+            let _ = messages.send(AppMessage::TaskDetailsLoaded(Box::new(LoadedDetails {
+                task_id,
+                task,
+                comments,
+                subtasks,
+            })));
+        });
+        self.detail_load = Some((loading, handle));
+    }
 
-        let (task_result, comments_result) = tokio::join!(task_future, comments_future);
+    fn show_task_details(&mut self, details: LoadedDetails) {
+        let LoadedDetails {
+            task_id,
+            task,
+            comments,
+            subtasks,
+        } = details;
+
+        // Earlier loads were aborted, but one may have finished first
+        if self
+            .detail_load
+            .as_ref()
+            .is_some_and(|(loading, _)| loading == &task_id)
+        {
+            self.detail_loading = false;
+            self.detail_load = None;
+        }
+        // The cursor moved on while this was loading
+        if self.selected_task().map(|task| &task.id) != Some(&task_id) {
+            return;
+        }
 
-        match task_result {
+        match task {
             Ok(task) => self.current_task = Some(task),
             Err(e) => {
                 self.error_message = Some(format!("Failed to load task: {e}"));
                 self.current_task = None;
             }
         }
+        self.detail_subtasks = subtasks;
 
-        match comments_result {
+        match comments {
             Ok(comments) => self.task_comments = comments,
             Err(e) => {
                 self.error_message = Some(format!("Failed to load comments: {e}"));
                 self.task_comments = Vec::new();
             }
         }
-
-        self.detail_loading = false;
-        Ok(())
     }
 
     fn clamp_scroll_offset(&mut self) {
@@ -391,7 +1248,53 @@ impl App {
         }
     }
 
+    /// Whether keys type into a text field rather than run shortcuts
+    fn text_input_focused(&self) -> bool {
+        match self.mode {
+            AppMode::Main => self.focused_pane == FocusedPane::Search,
+            AppMode::Help | AppMode::Board => false,
+            AppMode::NewTask
+            | AppMode::ComposeComment
+            | AppMode::WorkspacePicker
+            | AppMode::ProjectPicker
+            | AppMode::AssigneePicker
+            | AppMode::SetDueDate
+            | AppMode::WorkspaceSearch => true,
+        }
+    }
+
     pub async fn handle_event(&mut self, event: AppEvent) -> Result<bool> {
+        // '/' arrives as the search shortcut; in a text field it's just a character
+        let event = match event {
+            AppEvent::FocusSearch if self.text_input_focused() => AppEvent::Character('/'),
+            event => event,
+        };
+
+        if self.mode == AppMode::NewTask {
+            return self.handle_task_form_event(event).await;
+        }
+        if self.mode == AppMode::ComposeComment {
+            return self.handle_comment_composer_event(event).await;
+        }
+        if self.mode == AppMode::WorkspacePicker {
+            return self.handle_workspace_picker_event(event).await;
+        }
+        if self.mode == AppMode::ProjectPicker {
+            return self.handle_project_picker_event(event).await;
+        }
+        if self.mode == AppMode::Board {
+            return self.handle_board_event(event).await;
+        }
+        if self.mode == AppMode::AssigneePicker {
+            return self.handle_assignee_picker_event(event).await;
+        }
+        if self.mode == AppMode::SetDueDate {
+            return self.handle_due_date_event(event).await;
+        }
+        if self.mode == AppMode::WorkspaceSearch {
+            return self.handle_workspace_search_event(event).await;
+        }
+
         match event {
             AppEvent::Quit => return Ok(true),
 
@@ -546,8 +1449,89 @@ impl App {
                     }
                     'c' => {
                         if self.handle_char_with_search_priority(c) {
-                            // Start comment when not in search
-                            // TODO: Implement comment functionality when needed
+                            // Start comment on the loaded task when not in search
+                            if self.current_task.is_some() {
+                                self.comment_composer = Some(CommentComposer::new());
+                                self.mode = AppMode::ComposeComment;
+                                self.focused_pane = FocusedPane::Comments;
+                            }
+                        }
+                    }
+                    'e' => {
+                        if self.handle_char_with_search_priority(c) {
+                            // Edit the loaded task's description when not in search
+                            self.description_edit_requested = self.current_task.is_some();
+                        }
+                    }
+                    'w' => {
+                        if self.handle_char_with_search_priority(c) {
+                            // Switch workspace when not in search
+                            self.open_workspace_picker();
+                        }
+                    }
+                    'b' => {
+                        if self.handle_char_with_search_priority(c) {
+                            // Show the scoped project as a board when not in search
+                            self.open_board();
+                        }
+                    }
+                    'p' => {
+                        if self.handle_char_with_search_priority(c) {
+                            // Scope the list to a project when not in search
+                            self.open_project_picker();
+                        }
+                    }
+                    'n' => {
+                        if self.handle_char_with_search_priority(c) {
+                            // Open the new task form when not in search
+                            self.task_form = Some(TaskForm::new_task());
+                            self.subtask_parent = None;
+                            self.mode = AppMode::NewTask;
+                        }
+                    }
+                    'N' => {
+                        if self.handle_char_with_search_priority(c) {
+                            // Add a subtask to the selected task when not in search
+                            if let Some(task) = self
+                                .task_list_state
+                                .selected()
+                                .and_then(|i| self.filtered_tasks.get(i))
+                            {
+                                self.subtask_parent = Some(task.id.clone());
+                                self.task_form = Some(TaskForm::new_subtask());
+                                self.mode = AppMode::NewTask;
+                            }
+                        }
+                    }
+                    'a' => {
+                        if self.handle_char_with_search_priority(c) {
+                            // Reassign the selected task when not in search
+                            self.open_assignee_picker();
+                        }
+                    }
+                    'd' => {
+                        if self.handle_char_with_search_priority(c) {
+                            // Set the selected task's due date when not in search
+                            if let Some(task) = self.selected_task() {
+                                self.edited_task = Some(task.id.clone());
+                                self.due_date_form = Some(TaskForm::due_date());
+                                self.mode = AppMode::SetDueDate;
+                            }
+                        }
+                    }
+                    'S' => {
+                        if self.handle_char_with_search_priority(c) {
+                            // Search the whole workspace when not in search
+                            self.workspace_search_form = Some(TaskForm::workspace_search());
+                            self.mode = AppMode::WorkspaceSearch;
+                        }
+                    }
+                    'o' => {
+                        if self.handle_char_with_search_priority(c)
+                            && self.focused_pane == FocusedPane::TaskList
+                        {
+                            // Expand or collapse subtasks when not in search
+                            self.toggle_selected_subtasks();
                         }
                     }
                     '?' => {
@@ -560,22 +1544,13 @@ impl App {
                         if self.handle_char_with_search_priority(c) {
                             // Toggle task completion when not in search
                             if self.focused_pane == FocusedPane::TaskList {
-                                if let Some(selected) = self.task_list_state.selected() {
-                                    if let Some(task) = self.filtered_tasks.get(selected) {
-                                        match self
-                                            .state_manager
-                                            .toggle_task_completion(&task.id)
-                                            .await
-                                        {
-                                            Ok(_) => {
-                                                self.load_tasks().await?;
-                                            }
-                                            Err(e) => {
-                                                self.error_message =
-                                                    Some(format!("Failed to toggle task: {e}"));
-                                            }
-                                        }
-                                    }
+                                // Ticked off in place, like a checklist, until the next reload
+                                if let Some(task) = self.selected_task().cloned() {
+                                    let update = TaskUpdate {
+                                        completed: Some(!task.completed),
+                                        ..Default::default()
+                                    };
+                                    self.submit_update(&task, update);
                                 }
                             }
                         }
@@ -607,106 +1582,743 @@ impl App {
                 }
             }
 
-            AppEvent::Backspace => {
-                if self.focused_pane == FocusedPane::Search {
-                    self.search_bar.delete_char();
-                    self.search_query = self.search_bar.query().to_string();
-                    self.update_filtered_tasks();
+            AppEvent::Backspace => {
+                if self.focused_pane == FocusedPane::Search {
+                    self.search_bar.delete_char();
+                    self.search_query = self.search_bar.query().to_string();
+                    self.update_filtered_tasks();
+                }
+            }
+
+            AppEvent::CloseModal => {
+                if self.focused_pane == FocusedPane::Search {
+                    // Esc from search: clear search and focus task list
+                    self.search_bar.clear();
+                    self.search_query.clear();
+                    self.update_filtered_tasks();
+                    self.apply_query_push_down().await?;
+                    self.focused_pane = FocusedPane::TaskList;
+                    self.search_bar.set_focused(false);
+                } else if self.workspace_search.is_some()
+                    && self.mode == AppMode::Main
+                    && self.focused_pane == FocusedPane::TaskList
+                {
+                    // Esc from workspace search results: back to the list they replaced
+                    self.search_workspace(None).await?;
+                } else {
+                    // Esc from other contexts: close modals/details
+                    self.mode = AppMode::Main;
+                }
+            }
+
+            AppEvent::NextTask => {
+                // Arrow keys work in both search and task list for navigation
+                if self.focused_pane == FocusedPane::Description {
+                    self.description_scroll_offset =
+                        self.description_scroll_offset.saturating_add(1);
+                    self.clamp_scroll_offset();
+                } else if self.focused_pane == FocusedPane::Comments {
+                    self.comments_scroll_offset = self.comments_scroll_offset.saturating_add(1);
+                    self.clamp_scroll_offset();
+                } else if self.focused_pane == FocusedPane::TaskList
+                    || self.focused_pane == FocusedPane::Search
+                {
+                    self.next_task();
+                }
+            }
+
+            AppEvent::PreviousTask => {
+                // Arrow keys work in both search and task list for navigation
+                if self.focused_pane == FocusedPane::Description {
+                    self.description_scroll_offset =
+                        self.description_scroll_offset.saturating_sub(1);
+                    self.clamp_scroll_offset();
+                } else if self.focused_pane == FocusedPane::Comments {
+                    self.comments_scroll_offset = self.comments_scroll_offset.saturating_sub(1);
+                    self.clamp_scroll_offset();
+                } else if self.focused_pane == FocusedPane::TaskList
+                    || self.focused_pane == FocusedPane::Search
+                {
+                    self.previous_task();
+                }
+            }
+
+            AppEvent::Enter => {
+                if self.focused_pane == FocusedPane::Search {
+                    // Enter from search: switch to task list and select highlighted task
+                    self.focused_pane = FocusedPane::TaskList;
+                    self.search_bar.set_focused(false);
+
+                    // Let the API apply what it can of the query
+                    self.apply_query_push_down().await?;
+
+                    // Switch focus to task list
+                    self.focused_pane = FocusedPane::TaskList;
+                } else if self.focused_pane == FocusedPane::TaskList {
+                    // Focus the task details pane and load details for selected task
+                    self.focused_pane = FocusedPane::Description;
+                    if let Some(task_id) = self.selected_task().map(|task| task.id.clone()) {
+                        self.load_task_details(&task_id);
+                    }
+                }
+            }
+
+            AppEvent::ScrollDetailPageUp => {
+                if self.focused_pane == FocusedPane::Description {
+                    self.description_scroll_offset =
+                        self.description_scroll_offset.saturating_sub(10);
+                    self.clamp_scroll_offset();
+                } else if self.focused_pane == FocusedPane::Comments {
+                    self.comments_scroll_offset = self.comments_scroll_offset.saturating_sub(10);
+                    self.clamp_scroll_offset();
+                }
+            }
+
+            AppEvent::ScrollDetailPageDown => {
+                if self.focused_pane == FocusedPane::Description {
+                    self.description_scroll_offset =
+                        self.description_scroll_offset.saturating_add(10);
+                    self.clamp_scroll_offset();
+                } else if self.focused_pane == FocusedPane::Comments {
+                    self.comments_scroll_offset = self.comments_scroll_offset.saturating_add(10);
+                    self.clamp_scroll_offset();
+                }
+            }
+
+            AppEvent::Submit => {}
+        }
+
+        Ok(false)
+    }
+
+    /// The task under the cursor in the task list
+    fn selected_task(&self) -> Option<&Task> {
+        self.task_list_state
+            .selected()
+            .and_then(|i| self.filtered_tasks.get(i))
+    }
+
+    /// Open the assignee picker for the selected task, once the workspace's people are known
+    fn open_assignee_picker(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        let task_id = task.id.clone();
+
+        if self.workspace_users.is_none() {
+            let state_manager = self.state_manager.clone();
+            self.spawn_request("Loading people", async move {
+                let result = state_manager.list_users().await;
+                Reply::UsersLoaded {
+                    task: task_id,
+                    result,
+                }
+            });
+            return;
+        }
+        self.show_assignee_picker(task_id);
+    }
+
+    fn show_assignee_picker(&mut self, task_id: TaskId) {
+        let Some(task) = self.find_task(&task_id) else {
+            return;
+        };
+        let current = task.assignee;
+        let users = self.workspace_users.as_deref().unwrap_or_default();
+
+        let items = std::iter::once((None, "Unassigned".to_string()))
+            .chain(users.iter().map(|user| {
+                let label = if user.email.is_empty() {
+                    user.name.clone()
+                } else {
+                    format!("{} <{}>", user.name, user.email)
+                };
+                (Some(user.id.clone()), label)
+            }))
+            .collect();
+        self.assignee_picker =
+            Some(Picker::new("Assign To", items).with_active(|id| *id == current));
+        self.edited_task = Some(task_id);
+        self.mode = AppMode::AssigneePicker;
+    }
+
+    /// Route input to the assignee picker while it is open
+    async fn handle_assignee_picker_event(&mut self, event: AppEvent) -> Result<bool> {
+        let Some(picker) = self.assignee_picker.as_mut() else {
+            self.mode = AppMode::Main;
+            return Ok(false);
+        };
+
+        match event {
+            AppEvent::Quit => return Ok(true),
+            AppEvent::CloseModal => {
+                self.assignee_picker = None;
+                self.mode = AppMode::Main;
+            }
+            AppEvent::NextTask | AppEvent::Tab => picker.next(),
+            AppEvent::PreviousTask | AppEvent::BackTab => picker.previous(),
+            AppEvent::Character(c) => picker.insert_char(c),
+            AppEvent::Backspace => picker.delete_char(),
+            AppEvent::Enter => {
+                if let Some(assignee) = picker.selected().cloned() {
+                    self.assignee_picker = None;
+                    self.mode = AppMode::Main;
+                    self.update_edited_task(TaskUpdate {
+                        assignee: Some(assignee),
+                        ..Default::default()
+                    })
+                    .await;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    /// Route input to the due date prompt while it is open
+    async fn handle_due_date_event(&mut self, event: AppEvent) -> Result<bool> {
+        let Some(form) = self.due_date_form.as_mut() else {
+            self.mode = AppMode::Main;
+            return Ok(false);
+        };
+
+        match event {
+            AppEvent::Quit => return Ok(true),
+            AppEvent::CloseModal => {
+                self.due_date_form = None;
+                self.mode = AppMode::Main;
+            }
+            AppEvent::Character(c) => form.insert_char(c),
+            AppEvent::Backspace => form.delete_char(),
+            AppEvent::Enter => {
+                // An empty answer clears the due date
+                let due = match form.value("Due") {
+                    "" => None,
+                    due => match parse_due_date(due) {
+                        Ok(due) => Some(due),
+                        Err(e) => {
+                            form.set_error(e);
+                            return Ok(false);
+                        }
+                    },
+                };
+
+                self.due_date_form = None;
+                self.mode = AppMode::Main;
+                self.update_edited_task(TaskUpdate {
+                    due: Some(due),
+                    ..Default::default()
+                })
+                .await;
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    /// Route input to the workspace search prompt while it is open
+    async fn handle_workspace_search_event(&mut self, event: AppEvent) -> Result<bool> {
+        let Some(form) = self.workspace_search_form.as_mut() else {
+            self.mode = AppMode::Main;
+            return Ok(false);
+        };
+
+        match event {
+            AppEvent::Quit => return Ok(true),
+            AppEvent::CloseModal => {
+                self.workspace_search_form = None;
+                self.mode = AppMode::Main;
+            }
+            AppEvent::Character(c) => form.insert_char(c),
+            AppEvent::Backspace => form.delete_char(),
+            AppEvent::Enter => {
+                // An empty answer ends the search
+                let text = form.value("Text").trim();
+                let text = (!text.is_empty()).then(|| text.to_string());
+
+                self.workspace_search_form = None;
+                self.mode = AppMode::Main;
+                self.search_workspace(text).await?;
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    /// Replace the list with Asana's results for `text`, or go back to the
+    /// normal list when `None`
+    async fn search_workspace(&mut self, text: Option<String>) -> Result<()> {
+        self.workspace_search = text;
+
+        // The search bar query still applies, now on top of the results
+        self.api_filter = self.pushed_down_filter().await?;
+        self.expanded_tasks.clear();
+        self.focused_pane = FocusedPane::TaskList;
+
+        self.load_tasks().await
+    }
+
+    /// Apply `update` to the task a picker or prompt was opened for
+    async fn update_edited_task(&mut self, update: TaskUpdate) {
+        let Some(task) = self.edited_task.take().and_then(|id| self.find_task(&id)) else {
+            return;
+        };
+        self.submit_update(&task, update);
+    }
+
+    /// The shown copy of a task, wherever it appears
+    fn find_task(&self, id: &TaskId) -> Option<Task> {
+        self.tasks
+            .iter()
+            .chain(self.subtasks.values().flatten())
+            .chain(self.detail_subtasks.iter())
+            .chain(self.current_task.iter())
+            .find(|task| &task.id == id)
+            .cloned()
+    }
+
+    /// Show `update` on `task` straight away and send it in the background;
+    /// [`Self::poll_mutations`] undoes it if Asana says no
+    fn submit_update(&mut self, task: &Task, update: TaskUpdate) {
+        let mut shown = self.mutations.submit(task, update);
+
+        // Name a new assignee from the picker's list until Asana does
+        if let (Some(assignee), None) = (&shown.assignee, &shown.assignee_name) {
+            shown.assignee_name = self
+                .workspace_users
+                .iter()
+                .flatten()
+                .find(|user| &user.id == assignee)
+                .map(|user| user.name.clone());
+        }
+
+        self.replace_task(&shown);
+        self.apply_search_results();
+    }
+
+    /// Take in Asana's answers to changes sent by [`Self::submit_update`]:
+    /// its copy of the task when accepted, the change undone when rejected
+    pub fn poll_mutations(&mut self) {
+        let outcomes = self.mutations.poll();
+        if outcomes.is_empty() {
+            return;
+        }
+
+        for outcome in outcomes {
+            match outcome {
+                MutationOutcome::Confirmed(task) => self.replace_task(&task),
+                MutationOutcome::Rejected {
+                    before,
+                    update,
+                    error,
+                } => {
+                    let Some(mut task) = self.find_task(&before.id) else {
+                        continue;
+                    };
+                    update.revert(&mut task, &before);
+                    self.replace_task(&task);
+
+                    let notice = format!("Couldn't save '{}': {error}", before.name);
+                    self.status_notice = Some((notice, std::time::Instant::now()));
                 }
             }
+        }
+        self.apply_search_results();
+    }
+
+    /// Route input to the new task form while it is open
+    async fn handle_task_form_event(&mut self, event: AppEvent) -> Result<bool> {
+        let Some(form) = self.task_form.as_mut() else {
+            self.mode = AppMode::Main;
+            return Ok(false);
+        };
 
+        match event {
+            AppEvent::Quit => return Ok(true),
             AppEvent::CloseModal => {
-                if self.focused_pane == FocusedPane::Search {
-                    // Esc from search: clear search and focus task list
-                    self.search_bar.clear();
-                    self.search_query.clear();
-                    self.update_filtered_tasks();
-                    self.focused_pane = FocusedPane::TaskList;
-                    self.search_bar.set_focused(false);
-                } else {
-                    // Esc from other contexts: close modals/details
-                    self.mode = AppMode::Main;
-                }
+                self.task_form = None;
+                self.subtask_parent = None;
+                self.mode = AppMode::Main;
             }
+            AppEvent::Tab | AppEvent::NextTask => form.next_field(),
+            AppEvent::BackTab | AppEvent::PreviousTask => form.previous_field(),
+            AppEvent::Character(c) => form.insert_char(c),
+            AppEvent::Backspace => form.delete_char(),
+            AppEvent::Enter => self.submit_task_form().await?,
+            _ => {}
+        }
 
-            AppEvent::NextTask => {
-                // Arrow keys work in both search and task list for navigation
-                if self.focused_pane == FocusedPane::Description {
-                    self.description_scroll_offset =
-                        self.description_scroll_offset.saturating_add(1);
-                    self.clamp_scroll_offset();
-                } else if self.focused_pane == FocusedPane::Comments {
-                    self.comments_scroll_offset = self.comments_scroll_offset.saturating_add(1);
-                    self.clamp_scroll_offset();
-                } else if self.focused_pane == FocusedPane::TaskList
-                    || self.focused_pane == FocusedPane::Search
-                {
-                    self.next_task();
+        Ok(false)
+    }
+
+    async fn submit_task_form(&mut self) -> Result<()> {
+        let Some(form) = self.task_form.as_mut() else {
+            return Ok(());
+        };
+
+        let name = form.value("Name").to_string();
+        if name.is_empty() {
+            form.set_error("Name is required".to_string());
+            return Ok(());
+        }
+
+        let due = match form.value("Due") {
+            "" => None,
+            due => match parse_due_date(due) {
+                Ok(due) => Some(due),
+                Err(e) => {
+                    form.set_error(e);
+                    return Ok(());
                 }
+            },
+        };
+
+        let new_task = match self.subtask_parent.clone() {
+            // Subtasks are checklist items under their parent, left unassigned
+            Some(parent) => NewTask {
+                name,
+                parent: Some(parent),
+                due,
+                ..Default::default()
+            },
+            // Assign to the current user so the task shows up in "my tasks"
+            None => NewTask {
+                name,
+                assignee: self.state_manager.get_current_user().await.map(|u| u.id),
+                due,
+                ..Default::default()
+            },
+        };
+
+        let state_manager = self.state_manager.clone();
+        self.spawn_request("Creating task", async move {
+            Reply::TaskCreated(state_manager.create_task(new_task).await)
+        });
+
+        Ok(())
+    }
+
+    /// Route input to the comment composer while it is open
+    async fn handle_comment_composer_event(&mut self, event: AppEvent) -> Result<bool> {
+        let Some(composer) = self.comment_composer.as_mut() else {
+            self.mode = AppMode::Main;
+            return Ok(false);
+        };
+
+        match event {
+            AppEvent::Quit => return Ok(true),
+            AppEvent::CloseModal => {
+                self.comment_composer = None;
+                self.mode = AppMode::Main;
             }
+            AppEvent::Character(c) => composer.insert_char(c),
+            AppEvent::Enter => composer.insert_newline(),
+            AppEvent::Backspace => composer.delete_char(),
+            AppEvent::Submit => self.submit_comment().await?,
+            _ => {}
+        }
 
-            AppEvent::PreviousTask => {
-                // Arrow keys work in both search and task list for navigation
-                if self.focused_pane == FocusedPane::Description {
-                    self.description_scroll_offset =
-                        self.description_scroll_offset.saturating_sub(1);
-                    self.clamp_scroll_offset();
-                } else if self.focused_pane == FocusedPane::Comments {
-                    self.comments_scroll_offset = self.comments_scroll_offset.saturating_sub(1);
-                    self.clamp_scroll_offset();
-                } else if self.focused_pane == FocusedPane::TaskList
-                    || self.focused_pane == FocusedPane::Search
-                {
-                    self.previous_task();
+        Ok(false)
+    }
+
+    async fn submit_comment(&mut self) -> Result<()> {
+        let (Some(composer), Some(task)) = (self.comment_composer.as_mut(), &self.current_task)
+        else {
+            return Ok(());
+        };
+
+        let text = composer.text().to_string();
+        if text.is_empty() {
+            composer.set_error("Comment is empty".to_string());
+            return Ok(());
+        }
+
+        let task = task.id.clone();
+        let state_manager = self.state_manager.clone();
+        self.spawn_request("Posting comment", async move {
+            let result = state_manager.create_comment(&task, &text).await;
+            Reply::CommentPosted { task, result }
+        });
+
+        Ok(())
+    }
+
+    fn open_workspace_picker(&mut self) {
+        let state_manager = self.state_manager.clone();
+        self.spawn_request("Loading workspaces", async move {
+            let result = match state_manager.list_workspaces().await {
+                Ok(workspaces) => Ok((workspaces, state_manager.get_current_workspace().await)),
+                Err(e) => Err(e),
+            };
+            Reply::WorkspacesLoaded(result)
+        });
+    }
+
+    /// Route input to the workspace picker while it is open
+    async fn handle_workspace_picker_event(&mut self, event: AppEvent) -> Result<bool> {
+        // There is nothing to go back to until a workspace is chosen
+        let can_cancel = self.state_manager.get_current_workspace().await.is_some();
+        let Some(picker) = self.workspace_picker.as_mut() else {
+            self.mode = AppMode::Main;
+            return Ok(false);
+        };
+
+        match event {
+            AppEvent::Quit => return Ok(true),
+            AppEvent::CloseModal if can_cancel => {
+                self.workspace_picker = None;
+                self.mode = AppMode::Main;
+            }
+            AppEvent::NextTask | AppEvent::Tab => picker.next(),
+            AppEvent::PreviousTask | AppEvent::BackTab => picker.previous(),
+            AppEvent::Character(c) => picker.insert_char(c),
+            AppEvent::Backspace => picker.delete_char(),
+            AppEvent::Enter => {
+                if let Some(workspace) = picker.selected().cloned() {
+                    self.switch_workspace(workspace).await?;
                 }
             }
+            _ => {}
+        }
 
-            AppEvent::Enter => {
-                if self.focused_pane == FocusedPane::Search {
-                    // Enter from search: switch to task list and select highlighted task
-                    self.focused_pane = FocusedPane::TaskList;
-                    self.search_bar.set_focused(false);
+        Ok(false)
+    }
 
-                    // Switch focus to task list
-                    self.focused_pane = FocusedPane::TaskList;
-                } else if self.focused_pane == FocusedPane::TaskList {
-                    // Focus the task details pane and load details for selected task
-                    self.focused_pane = FocusedPane::Description;
-                    if let Some(selected) = self.task_list_state.selected() {
-                        if let Some(task) = self.filtered_tasks.get(selected) {
-                            let task_id = task.id.clone();
-                            self.load_task_details(&task_id).await?;
-                        }
-                    }
+    async fn switch_workspace(&mut self, workspace: WorkspaceId) -> Result<()> {
+        if let Err(e) = self.state_manager.switch_workspace(workspace).await {
+            self.error_message = Some(format!("Failed to switch workspace: {e}"));
+            return Ok(());
+        }
+
+        self.workspace_picker = None;
+        self.mode = AppMode::Main;
+
+        // Nothing from the previous workspace carries over
+        self.api_filter = None;
+        self.workspace_users = None;
+        self.project_scope = None;
+        self.workspace_search = None;
+        self.search_bar.clear();
+        self.search_query.clear();
+        self.task_query = TaskQuery::default();
+        self.tasks.clear();
+        self.filtered_tasks.clear();
+        self.current_task = None;
+        self.task_comments.clear();
+        self.detail_subtasks.clear();
+        self.expanded_tasks.clear();
+        self.cached_description_lines = None;
+        self.cached_comments_lines = None;
+        self.needs_task_reload = true;
+        self.restart_live_refresh();
+
+        self.load_tasks().await
+    }
+
+    fn open_project_picker(&mut self) {
+        let state_manager = self.state_manager.clone();
+        self.spawn_request("Loading projects", async move {
+            Reply::ProjectsLoaded(state_manager.list_projects(false).await)
+        });
+    }
+
+    /// Route input to the project picker while it is open
+    async fn handle_project_picker_event(&mut self, event: AppEvent) -> Result<bool> {
+        let Some(picker) = self.project_picker.as_mut() else {
+            self.mode = AppMode::Main;
+            return Ok(false);
+        };
+
+        match event {
+            AppEvent::Quit => return Ok(true),
+            AppEvent::CloseModal => {
+                self.project_picker = None;
+                self.mode = AppMode::Main;
+            }
+            AppEvent::NextTask | AppEvent::Tab => picker.next(),
+            AppEvent::PreviousTask | AppEvent::BackTab => picker.previous(),
+            AppEvent::Character(c) => picker.insert_char(c),
+            AppEvent::Backspace => picker.delete_char(),
+            AppEvent::Enter => {
+                if let Some(project) = picker.selected().cloned() {
+                    let scope = project.map(|id| {
+                        let name = picker.selected_label().unwrap_or_default().to_string();
+                        (id, name)
+                    });
+                    self.project_picker = None;
+                    self.mode = AppMode::Main;
+                    self.scope_to_project(scope).await?;
                 }
             }
+            _ => {}
+        }
 
-            AppEvent::ScrollDetailPageUp => {
-                if self.focused_pane == FocusedPane::Description {
-                    self.description_scroll_offset =
-                        self.description_scroll_offset.saturating_sub(10);
-                    self.clamp_scroll_offset();
-                } else if self.focused_pane == FocusedPane::Comments {
-                    self.comments_scroll_offset = self.comments_scroll_offset.saturating_sub(10);
-                    self.clamp_scroll_offset();
-                }
+        Ok(false)
+    }
+
+    /// Re-scope the task list to `scope`, or back to My Tasks
+    async fn scope_to_project(&mut self, scope: Option<(ProjectId, String)>) -> Result<()> {
+        self.project_scope = scope;
+
+        // The search query still applies, now on top of the new base filter
+        self.api_filter = self.pushed_down_filter().await?;
+        self.tasks.clear();
+        self.filtered_tasks.clear();
+        self.current_task = None;
+        self.task_comments.clear();
+        self.detail_subtasks.clear();
+        self.expanded_tasks.clear();
+        self.cached_description_lines = None;
+        self.cached_comments_lines = None;
+        self.needs_task_reload = true;
+
+        self.load_tasks().await
+    }
+
+    /// Lay the scoped project's tasks out by section
+    fn open_board(&mut self) {
+        if self.project_scope.is_none() {
+            self.error_message = Some("Pick a project with p to see its board".to_string());
+            return;
+        }
+        self.load_board(false);
+    }
+
+    /// Fetch the open board again, e.g. after a move it couldn't show
+    fn refresh_board(&mut self) {
+        self.load_board(true);
+    }
+
+    /// Fetch the scoped project's sections and tasks for its board
+    fn load_board(&mut self, refresh: bool) {
+        let Some((project, name)) = self.project_scope.clone() else {
+            return;
+        };
+        let state_manager = self.state_manager.clone();
+        self.spawn_request("Loading board", async move {
+            let result = match state_manager.list_sections(&project).await {
+                Ok(sections) => state_manager
+                    .get_tasks_with_filter(&StateManager::project_task_filter(&project), true)
+                    .await
+                    .map(|tasks| (sections, tasks)),
+                Err(e) => Err(e),
+            };
+            Reply::BoardLoaded {
+                project,
+                name,
+                refresh,
+                result,
             }
+        });
+    }
 
-            AppEvent::ScrollDetailPageDown => {
-                if self.focused_pane == FocusedPane::Description {
-                    self.description_scroll_offset =
-                        self.description_scroll_offset.saturating_add(10);
-                    self.clamp_scroll_offset();
-                } else if self.focused_pane == FocusedPane::Comments {
-                    self.comments_scroll_offset = self.comments_scroll_offset.saturating_add(10);
-                    self.clamp_scroll_offset();
-                }
+    /// Route input to the board while it is shown
+    async fn handle_board_event(&mut self, event: AppEvent) -> Result<bool> {
+        let Some(board) = self.board.as_mut() else {
+            self.mode = AppMode::Main;
+            return Ok(false);
+        };
+
+        match event {
+            AppEvent::Quit => return Ok(true),
+            AppEvent::CloseModal | AppEvent::Character('q') | AppEvent::Character('b') => {
+                self.board = None;
+                self.mode = AppMode::Main;
+                // Moves made on the board change what the list shows
+                self.load_tasks().await?;
             }
+            AppEvent::NextTask | AppEvent::Character('j') => board.next_task(),
+            AppEvent::PreviousTask | AppEvent::Character('k') => board.previous_task(),
+            AppEvent::Tab | AppEvent::Character('l') => board.next_column(),
+            AppEvent::BackTab | AppEvent::Character('h') => board.previous_column(),
+            AppEvent::Character('L') => self.move_board_task(1),
+            AppEvent::Character('H') => self.move_board_task(-1),
+            AppEvent::Character('r') => self.refresh_board(),
+            _ => {}
         }
 
         Ok(false)
     }
 
+    /// Move the selected board task to the neighbouring section in direction `step`
+    fn move_board_task(&mut self, step: isize) {
+        let Some((task, section)) = self.board.as_ref().and_then(|b| b.move_target(step)) else {
+            return;
+        };
+
+        let state_manager = self.state_manager.clone();
+        self.spawn_request("Moving task", async move {
+            let result = state_manager.move_task_to_section(&task, &section).await;
+            Reply::BoardTaskMoved { task, step, result }
+        });
+    }
+
+    fn set_board_error(&mut self, message: String) {
+        if let Some(board) = self.board.as_mut() {
+            board.set_error(Some(message));
+        }
+    }
+
+    /// Take a pending request to edit the current description, returning the
+    /// task and its description as markdown
+    pub fn take_description_edit(&mut self) -> Option<(TaskId, String)> {
+        if !std::mem::take(&mut self.description_edit_requested) {
+            return None;
+        }
+
+        let task = self.current_task.as_ref()?;
+        let markdown = md::html_to_markdown(task.description.as_deref().unwrap_or_default());
+        Some((task.id.clone(), markdown))
+    }
+
+    /// Save a description edited in $EDITOR back to Asana as rich text
+    pub async fn finish_description_edit(
+        &mut self,
+        task_id: &TaskId,
+        original: &str,
+        edited: Result<String>,
+    ) -> Result<()> {
+        let edited = match edited {
+            Ok(edited) => edited,
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+                return Ok(());
+            }
+        };
+        if edited.trim() == original.trim() {
+            return Ok(());
+        }
+
+        let update = TaskUpdate {
+            html_description: Some(md::markdown_to_html(&edited)),
+            ..Default::default()
+        };
+        if let Some(task) = self.find_task(task_id) {
+            self.submit_update(&task, update);
+            self.description_scroll_offset = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Draw the comment composer along the bottom of the comments pane, returning
+    /// the area left for the comments themselves
+    fn render_comment_composer(&self, frame: &mut Frame, area: Rect) -> Rect {
+        let Some(composer) = &self.comment_composer else {
+            return area;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(7)])
+            .split(area);
+        composer.render(frame, chunks[1]);
+        chunks[0]
+    }
+
     fn next_task(&mut self) {
         if self.filtered_tasks.is_empty() {
             return;
@@ -738,6 +2350,12 @@ impl App {
     }
 
     pub fn render(&mut self, frame: &mut Frame) {
+        // The board replaces the panes entirely
+        if let Some(board) = self.board.as_mut() {
+            board.render(frame, frame.area());
+            return;
+        }
+
         // Check if we're in fullscreen mode
         if let Some(fullscreen_pane) = self.fullscreen_pane {
             // Render fullscreen pane without borders, using entire frame area
@@ -813,12 +2431,58 @@ impl App {
             if matches!(self.mode, AppMode::Help) {
                 self.render_help(frame);
             }
+
+            // Render new task form if active
+            if let Some(form) = &self.task_form {
+                Self::render_form(frame, form);
+            }
+
+            // Render due date prompt if active
+            if let Some(form) = &self.due_date_form {
+                Self::render_form(frame, form);
+            }
+
+            // Render workspace search prompt if active
+            if let Some(form) = &self.workspace_search_form {
+                Self::render_form(frame, form);
+            }
+        }
+
+        // Render workspace picker if active
+        if let Some(picker) = self.workspace_picker.as_mut() {
+            let area = Self::centered_rect(50, 60, frame.area());
+            picker.render(frame, area);
+        }
+
+        // Render project picker if active
+        if let Some(picker) = self.project_picker.as_mut() {
+            let area = Self::centered_rect(50, 60, frame.area());
+            picker.render(frame, area);
+        }
+
+        // Render assignee picker if active
+        if let Some(picker) = self.assignee_picker.as_mut() {
+            let area = Self::centered_rect(50, 60, frame.area());
+            picker.render(frame, area);
         }
     }
 
+    /// Center a form horizontally and vertically at its natural height
+    fn render_form(frame: &mut Frame, form: &TaskForm) {
+        let area = Self::centered_rect(60, 100, frame.area());
+        let height = form.height().min(area.height);
+        let area = Rect {
+            y: area.y + (area.height - height) / 2,
+            height,
+            ..area
+        };
+        form.render(frame, area);
+    }
+
     // Fullscreen render methods (without borders)
     fn render_task_list_fullscreen(&mut self, frame: &mut Frame, area: Rect) {
-        if self.is_loading {
+        // A reload keeps the current rows up until the new ones arrive
+        if self.is_loading && self.filtered_tasks.is_empty() {
             let paragraph =
                 Paragraph::new("Loading tasks...").style(Style::default().fg(Color::Gray));
             frame.render_widget(paragraph, area);
@@ -850,10 +2514,7 @@ impl App {
                 let (status_text, _) = task.status_display();
                 let due_text = task.due_date_display();
                 // Get icon and color based on task type and due date
-                let icon = task.type_icon();
-                let icon_color = task.icon_color();
-                let icon_span = Span::styled(icon, Style::default().fg(icon_color));
-                let title_with_icon = vec![icon_span, Span::raw(" "), Span::raw(&task.name)];
+                let title_with_icon = self.task_title_spans(task);
 
                 // Make due dates dark gray
                 let due_style = Style::default().fg(Color::DarkGray);
@@ -900,35 +2561,66 @@ impl App {
     }
 
     fn render_comments_fullscreen(&mut self, frame: &mut Frame, area: Rect) {
+        let area = self.render_comment_composer(frame, area);
         self.render_comments_content_only(frame, area);
     }
 
-    /// Auto-load task details when selection changes
-    pub async fn auto_load_selected_task(&mut self) -> Result<()> {
+    /// Load details for the selected row once the cursor has rested on it
+    /// for [`DETAIL_LOAD_DELAY`], so scrolling through the list stays quick
+    pub fn auto_load_selected_task(&mut self) {
         if !self.needs_task_reload {
-            return Ok(());
+            return;
         }
 
-        self.needs_task_reload = false;
-
-        if let Some(selected) = self.task_list_state.selected() {
-            if let Some(task) = self.filtered_tasks.get(selected) {
-                let task_id = task.id.clone();
-
-                // Only reload if it's a different task
-                let needs_loading = self
-                    .current_task
-                    .as_ref()
-                    .map(|current| current.id != task_id)
-                    .unwrap_or(true);
+        let Some(task_id) = self.selected_task().map(|task| task.id.clone()) else {
+            self.needs_task_reload = false;
+            return;
+        };
+        // Only reload if it's a different task
+        if self.current_task.as_ref().map(|task| &task.id) == Some(&task_id) {
+            self.needs_task_reload = false;
+            return;
+        }
 
-                if needs_loading {
-                    self.load_task_details(&task_id).await?;
+        match &self.detail_wanted {
+            Some((wanted, since)) if wanted == &task_id => {
+                if since.elapsed() < DETAIL_LOAD_DELAY {
+                    return;
+                }
+            }
+            _ => {
+                self.prefetch_neighbours();
+                // Prefetched rows load from the caches, so there's nothing to wait for
+                if !self.state_manager.is_prefetched(&task_id) {
+                    self.detail_wanted = Some((task_id, std::time::Instant::now()));
+                    return;
                 }
             }
         }
 
-        Ok(())
+        self.needs_task_reload = false;
+        self.detail_wanted = None;
+        self.load_task_details(&task_id);
+    }
+
+    /// Prefetch the rows around the cursor, nearest first, so moving onto
+    /// them shows their details straight away
+    fn prefetch_neighbours(&self) {
+        let Some(selected) = self.task_list_state.selected() else {
+            return;
+        };
+        let ids = (1..=PREFETCH_NEIGHBOURS)
+            .flat_map(|distance| {
+                [
+                    selected.checked_add(distance),
+                    selected.checked_sub(distance),
+                ]
+            })
+            .flatten()
+            .filter_map(|i| self.filtered_tasks.get(i))
+            .map(|task| task.id.clone())
+            .collect();
+        self.state_manager.prefetch_tasks(ids);
     }
 
     fn render_description_pane_standalone(&mut self, frame: &mut Frame, area: Rect) {
@@ -985,6 +2677,7 @@ impl App {
         };
 
         let title = "Comments & Activity";
+        let area = self.render_comment_composer(frame, area);
 
         // Get currently selected task
         let selected_task = self
@@ -1100,6 +2793,16 @@ impl App {
                 is_code_block: false,
             });
 
+            if let Some(start_on) = task.start_on {
+                lines.push(md::MarkdownLine {
+                    line: Line::from(vec![
+                        Span::styled("Start: ", Style::default().fg(Color::Cyan)),
+                        Span::raw(start_on.format("%Y-%m-%d").to_string()),
+                    ]),
+                    is_code_block: false,
+                });
+            }
+
             if task.assignee.is_some() {
                 let assignee_display = task.assignee_name.as_deref().unwrap_or("Unknown User");
                 lines.push(md::MarkdownLine {
@@ -1186,6 +2889,37 @@ impl App {
                 });
             }
 
+            // Add subtasks as a checklist
+            if !self.detail_subtasks.is_empty() {
+                let done = self.detail_subtasks.iter().filter(|t| t.completed).count();
+                lines.push(md::MarkdownLine {
+                    line: Line::from(Span::styled(
+                        format!("Subtasks ({done}/{}):", self.detail_subtasks.len()),
+                        Style::default().fg(Color::Cyan),
+                    )),
+                    is_code_block: false,
+                });
+                for subtask in &self.detail_subtasks {
+                    let (check, style) = if subtask.completed {
+                        (
+                            "☑",
+                            Style::default()
+                                .fg(Color::DarkGray)
+                                .add_modifier(Modifier::CROSSED_OUT),
+                        )
+                    } else {
+                        ("☐", Style::default())
+                    };
+                    lines.push(md::MarkdownLine {
+                        line: Line::from(vec![
+                            Span::raw(format!("  {check} ")),
+                            Span::styled(subtask.name.clone(), style),
+                        ]),
+                        is_code_block: false,
+                    });
+                }
+            }
+
             // Add blank line separator
             lines.push(md::MarkdownLine {
                 line: Line::from(""),
@@ -1226,12 +2960,7 @@ impl App {
                 .collect();
 
             // Render lines with special handling for code blocks
-            let mut y = 0;
-            for marked_line in visible_lines {
-                if y >= area.height {
-                    break;
-                }
-
+            for (y, marked_line) in (0..area.height).zip(visible_lines) {
                 // Create a sub-area for this line
                 let line_area = Rect {
                     x: area.x,
@@ -1252,8 +2981,6 @@ impl App {
                         .alignment(ratatui::layout::Alignment::Left);
                     frame.render_widget(paragraph, line_area);
                 }
-
-                y += 1;
             }
         }
     }
@@ -1391,7 +3118,26 @@ impl App {
 
     fn render_task_list(&mut self, frame: &mut Frame, area: Rect) {
         let len = self.filtered_tasks.len();
-        let title = format!("Tasks ({len})");
+        let title = match (&self.workspace_search, &self.project_scope) {
+            (Some(text), Some((_, name))) => format!("Search \"{text}\" in {name} ({len})"),
+            (Some(text), None) => format!("Search \"{text}\" ({len})"),
+            (None, Some((_, name))) => format!("{name} ({len})"),
+            (None, None) => format!("Tasks ({len})"),
+        };
+        let title = match self.stale_since {
+            Some(fetched_at) => format!(
+                "{title} · cached {}{}",
+                Zone::current()
+                    .local_datetime(fetched_at)
+                    .format("%b %d %H:%M"),
+                if self.background_refresh.is_some() {
+                    " · refreshing…"
+                } else {
+                    ""
+                }
+            ),
+            None => title,
+        };
         let border_style = if self.focused_pane == FocusedPane::TaskList {
             Style::default().fg(Color::Green)
         } else {
@@ -1404,7 +3150,7 @@ impl App {
             .border_type(BorderType::Rounded)
             .border_style(border_style);
 
-        if self.is_loading {
+        if self.is_loading && self.filtered_tasks.is_empty() {
             let paragraph = Paragraph::new("Loading tasks...")
                 .block(block)
                 .style(Style::default().fg(Color::Gray));
@@ -1420,6 +3166,8 @@ impl App {
             return;
         }
 
+        self.highlight_visible_rows(area.height as usize);
+
         if self.filtered_tasks.is_empty() {
             let message = if self.search_query.is_empty() {
                 "No tasks found"
@@ -1440,18 +3188,21 @@ impl App {
                 let due_text = task.due_date_display();
 
                 // Get icon and color based on task type and due date
-                let icon = task.type_icon();
-                let icon_color = task.icon_color();
-                let icon_span = Span::styled(icon, Style::default().fg(icon_color));
-                let title_with_icon = vec![icon_span, Span::raw(" "), Span::raw(&task.name)];
+                let title_with_icon = self.task_title_spans(task);
 
                 // Make due dates dark gray
                 let due_style = Style::default().fg(Color::DarkGray);
 
-                Row::new(vec![
+                let row = Row::new(vec![
                     Cell::from(Line::from(title_with_icon)),
                     Cell::from(due_text).style(due_style),
-                ])
+                ]);
+                // Someone else just changed this task
+                if self.flashed_rows.contains_key(&task.id) {
+                    row.style(Style::default().bg(Color::Rgb(80, 64, 16)))
+                } else {
+                    row
+                }
             })
             .collect();
 
@@ -1469,99 +3220,137 @@ impl App {
         frame.render_stateful_widget(table, area, &mut self.task_list_state);
     }
 
-    fn render_status_bar(&self, frame: &mut Frame, area: Rect) {
-        let help_text = match self.focused_pane {
-            FocusedPane::Search => "Tab: switch to tasks | Enter: go to tasks | /: focus search | f: fullscreen | q: quit | ?: help",
-            FocusedPane::TaskList => "j/k: navigate | Tab: switch panes | Space: toggle complete | /: search | f: fullscreen | q: quit | ?: help",
-            FocusedPane::Description => "j/k: scroll | Tab: next pane | f: fullscreen | q: quit | ?: help",
-            FocusedPane::Comments => "j/k: scroll | Tab: next pane | f: fullscreen | q: quit | ?: help",
-        };
+    /// Work out search highlights for the rows about to be drawn, whichever way
+    /// the list scrolls to keep the selection in view
+    fn highlight_visible_rows(&mut self, height: usize) {
+        if self.task_query.text.is_empty() {
+            return;
+        }
 
-        let paragraph = Paragraph::new(help_text).style(Style::default().fg(Color::DarkGray));
-        frame.render_widget(paragraph, area);
+        let offset = self.task_list_state.offset();
+        let selected = self.task_list_state.selected().unwrap_or(offset);
+        let start = offset.min((selected + 1).saturating_sub(height));
+        let end = (offset + height).max(selected + 1);
+        for task in self.filtered_tasks.iter().take(end).skip(start) {
+            if !self.search_highlights.contains_key(&task.id) {
+                let indices = self.task_search.highlights(&task.name);
+                self.search_highlights.insert(task.id.clone(), indices);
+            }
+        }
     }
 
-    fn render_task_details_pane(&mut self, frame: &mut Frame, area: Rect) {
-        // Determine border style based on focus
-        let border_style = if self.focused_pane == FocusedPane::Description
-            || self.focused_pane == FocusedPane::Comments
-        {
-            Style::default().fg(Color::Yellow)
+    /// Tree indent and fold marker, type icon, then the task name with search matches
+    /// highlighted
+    fn task_title_spans(&self, task: &Task) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+
+        let depth = self.task_depths.get(&task.id).copied().unwrap_or(0);
+        if depth > 0 {
+            spans.push(Span::raw("  ".repeat(depth)));
+        }
+        if task.has_subtasks() {
+            let marker = if self.expanded_tasks.contains(&task.id) {
+                "▾ "
+            } else {
+                "▸ "
+            };
+            spans.push(Span::styled(marker, Style::default().fg(Color::DarkGray)));
+        }
+
+        let (icon, base) = if task.completed {
+            (
+                "✓",
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT),
+            )
         } else {
-            Style::default().fg(Color::Gray)
+            (task.type_icon(), Style::default())
         };
+        spans.push(Span::styled(icon, Style::default().fg(task.icon_color())));
+        spans.push(Span::raw(" "));
+
+        let indices = self
+            .search_highlights
+            .get(&task.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        spans.extend(fuzzy::highlight_spans(
+            &task.name,
+            indices,
+            base,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+
+        if task.has_subtasks() {
+            spans.push(Span::styled(
+                format!(" [{}]", task.num_subtasks),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
 
-        // Get currently selected task
-        let selected_task = self
-            .task_list_state
-            .selected()
-            .and_then(|i| self.filtered_tasks.get(i));
+        spans
+    }
 
-        if let Some(task) = selected_task {
-            // Auto-load task details if not already loaded or if different task
-            let needs_loading = self
-                .current_task
-                .as_ref()
-                .map(|current| current.id != task.id)
-                .unwrap_or(true);
-
-            if needs_loading && !self.detail_loading {
-                // Trigger async loading - we'll handle this in event processing
-                // For now, show loading state
-            }
-
-            if self.detail_loading {
-                // Show loading state
-                let block = Block::default()
-                    .title("Task Details")
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(border_style);
-
-                let paragraph = Paragraph::new("Loading task details...")
-                    .block(block)
-                    .style(Style::default().fg(Color::Gray));
-                frame.render_widget(paragraph, area);
-            } else if let Some(current_task) = &self.current_task {
-                // Show task details using existing render logic
-                let block = Block::default()
-                    .title(format!("Task Details - {}", current_task.name))
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(border_style);
-
-                let inner_area = block.inner(area);
-                frame.render_widget(block, area);
-
-                // Use existing scrollable content renderer
-                self.render_scrollable_content(frame, inner_area, current_task);
-            } else {
-                // Task selected but details not loaded yet
-                let block = Block::default()
-                    .title("Task Details")
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(border_style);
-
-                let paragraph = Paragraph::new("Select a task to view details")
-                    .block(block)
-                    .style(Style::default().fg(Color::Gray));
-                frame.render_widget(paragraph, area);
-            }
+    /// What is running in the background, for the status bar spinner
+    fn activity(&self) -> Option<&'static str> {
+        if let Some((activity, _)) = &self.request {
+            Some(activity)
+        } else if self.is_loading {
+            Some("Loading tasks")
+        } else if self.detail_loading {
+            Some("Loading details")
+        } else if self.mutations.is_busy() {
+            Some("Saving")
+        } else if self.background_refresh.is_some() || self.offline_sync.is_some() {
+            Some("Syncing")
         } else {
-            // No task selected - show placeholder
-            let block = Block::default()
-                .title("Task Details")
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(border_style);
-
-            let paragraph = Paragraph::new("No task selected\n\nUse ↑↓ to select a task")
-                .block(block)
-                .style(Style::default().fg(Color::Gray))
-                .alignment(Alignment::Center);
-            frame.render_widget(paragraph, area);
+            None
+        }
+    }
+
+    fn render_status_bar(&self, frame: &mut Frame, area: Rect) {
+        let help_text = match self.focused_pane {
+            FocusedPane::Search => "Tab: switch to tasks | Enter: go to tasks | /: focus search | f: fullscreen | q: quit | ?: help",
+            FocusedPane::TaskList => "j/k: navigate | Tab: switch panes | Space: toggle complete | a: assign | d: due | n: new task | N: subtask | o: subtasks | e: edit description | c: comment | p: project | b: board | w: workspace | /: search | S: search workspace | f: fullscreen | q: quit | ?: help",
+            FocusedPane::Description => "j/k: scroll | Tab: next pane | e: edit | c: comment | f: fullscreen | q: quit | ?: help",
+            FocusedPane::Comments => "j/k: scroll | Tab: next pane | c: comment | f: fullscreen | q: quit | ?: help",
+        };
+
+        let alert = self
+            .status_notice
+            .as_ref()
+            .filter(|(_, at)| at.elapsed() < NOTICE_DURATION);
+        let notice = match (alert, self.state_manager.is_offline(), self.pending_changes) {
+            (Some((message, _)), _, _) => Some((message.clone(), Color::Red)),
+            (None, true, 0) => Some(("Offline".to_string(), Color::Yellow)),
+            (None, true, n) => Some((format!("Offline · {n} queued"), Color::Yellow)),
+            (None, false, 0) => None,
+            (None, false, n) => Some((format!("Syncing {n} offline changes"), Color::Yellow)),
+        };
+
+        let mut spans = Vec::new();
+        if let Some(activity) = self.activity() {
+            let frame =
+                SPINNER[(self.spinner_epoch.elapsed().as_millis() / 100) as usize % SPINNER.len()];
+            spans.push(Span::styled(
+                format!("{frame} {activity}"),
+                Style::default().fg(Color::Cyan),
+            ));
+            spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
         }
+        if let Some((notice, color)) = notice {
+            spans.push(Span::styled(notice, Style::default().fg(color)));
+            spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
+        }
+        spans.push(Span::styled(
+            help_text,
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
     }
 
     fn render_help(&self, frame: &mut Frame) {
@@ -1586,11 +3375,25 @@ impl App {
             "",
             "Task Actions:",
             "  Space          - Toggle task completion",
+            "  a              - Assign the selected task (type to filter people)",
+            "  d              - Set the due date: tomorrow, fri, +3d, 2026-11-01 (empty clears)",
+            "  n              - Create a new task",
+            "  N              - Add a subtask to the selected task",
+            "  o              - Expand/collapse the selected task's subtasks",
+            "  c              - Comment on the selected task (Ctrl+S to post)",
+            "  e              - Edit the description in $EDITOR",
             "  r              - Refresh task list",
+            "  p              - Browse a project's tasks (My Tasks to go back)",
+            "  b              - Show the project as a board (H/L move tasks between sections)",
+            "  w              - Switch workspace",
             "",
             "Search:",
             "  /              - Focus search bar",
             "  Esc            - Clear search",
+            "  S              - Search the whole workspace through Asana (Esc returns)",
+            "  Filters        - project:Name tag:bug due:<7d due:today overdue",
+            "                   assignee:me is:milestone is:done cf:Priority=High",
+            "                   -tag:bug negates, \"quotes\" allow spaces",
             "  Note: Type normally in search (j/k/g work as regular letters)",
             "",
             "General:",
@@ -1610,504 +3413,6 @@ impl App {
         frame.render_widget(paragraph, popup_area);
     }
 
-    fn render_task_detail(&self, frame: &mut Frame, _task_id: &TaskId) {
-        let popup_area = Self::centered_rect(70, 85, frame.area());
-
-        frame.render_widget(ratatui::widgets::Clear, popup_area);
-
-        if self.detail_loading {
-            let paragraph = Paragraph::new("Loading task details...")
-                .block(Block::default().title("Task Detail").borders(Borders::ALL))
-                .style(Style::default().fg(Color::Gray));
-            frame.render_widget(paragraph, popup_area);
-            return;
-        }
-
-        let Some(task) = &self.current_task else {
-            let paragraph = Paragraph::new("Task not found\n\nPress q or Esc to close")
-                .block(Block::default().title("Task Detail").borders(Borders::ALL))
-                .style(Style::default().fg(Color::Red));
-            frame.render_widget(paragraph, popup_area);
-            return;
-        };
-
-        // Calculate dynamic title height based on text wrapping
-        let available_width = popup_area.width.saturating_sub(4) as usize; // Account for margins and borders
-        let title_height = if available_width > 0 {
-            // Simple word-aware wrapping calculation
-            let words: Vec<&str> = task.name.split_whitespace().collect();
-            let mut lines = 1u16;
-            let mut current_line_len = 0;
-
-            for word in words {
-                let word_len = word.chars().count();
-                if current_line_len + word_len + 1 > available_width && current_line_len > 0 {
-                    lines += 1;
-                    current_line_len = word_len;
-                } else {
-                    current_line_len += if current_line_len > 0 {
-                        word_len + 1
-                    } else {
-                        word_len
-                    };
-                }
-            }
-            lines.min(3) // Cap at 3 lines max
-        } else {
-            1
-        };
-
-        // Split the modal into sections
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(title_height), // Task title (dynamic height)
-                Constraint::Min(10),              // All scrollable content (gets most space)
-                Constraint::Length(1),            // Instructions
-            ])
-            .margin(1)
-            .split(popup_area);
-
-        // Render task title at top in bold with wrapping
-        let title_paragraph = Paragraph::new(task.name.clone())
-            .style(
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .alignment(Alignment::Center)
-            .wrap(Wrap { trim: false });
-        frame.render_widget(title_paragraph, chunks[0]);
-
-        // Render all content as one scrollable section
-        self.render_scrollable_content(frame, chunks[1], task);
-
-        // Render instructions
-        let instructions = Paragraph::new(
-            "↑↓: scroll | q/Esc: close | g/G: top/bottom | Page Up/Down: fast scroll",
-        )
-        .style(Style::default().fg(Color::Gray));
-        frame.render_widget(instructions, chunks[2]);
-
-        // Render border with title including task ID
-        let border = Block::default()
-            .title(format!("Task Detail - {}", task.id))
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Yellow));
-        frame.render_widget(border, popup_area);
-    }
-
-    fn render_scrollable_content(&self, frame: &mut Frame, area: Rect, task: &Task) {
-        // Split into two panes: Description pane (top) and Comments pane (bottom)
-        let panes = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(60), // Description pane (task info + description)
-                Constraint::Percentage(40), // Comments pane
-            ])
-            .split(area);
-
-        // Render description pane (top)
-        self.render_description_pane(frame, panes[0], task);
-
-        // Render comments pane (bottom)
-        self.render_comments_pane(frame, panes[1]);
-    }
-
-    fn render_description_pane(&self, frame: &mut Frame, area: Rect, task: &Task) {
-        // Create border with focus indicator
-        let border_style = if self.focused_detail_pane == TaskDetailPane::Description {
-            Style::default().fg(Color::Cyan)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-
-        let title = if self.focused_detail_pane == TaskDetailPane::Description {
-            "Description [FOCUSED] (Tab to switch)"
-        } else {
-            "Description (Tab to switch)"
-        };
-
-        let block = Block::default()
-            .title(title)
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(border_style);
-
-        let inner_area = block.inner(area);
-        frame.render_widget(block, area);
-
-        // Collect all content for this pane
-        let mut lines = Vec::new();
-
-        // Add task info section
-        let (status_text, status_color) = task.status_display();
-        let status_style = match status_color {
-            "red" => Style::default().fg(Color::Red),
-            "yellow" => Style::default().fg(Color::Yellow),
-            "green" => Style::default().fg(Color::Green),
-            "gray" => Style::default().fg(Color::Gray),
-            _ => Style::default(),
-        };
-
-        lines.push(Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Color::Cyan)),
-            Span::styled(status_text, status_style),
-        ]));
-
-        let due_text = task.due_date_display();
-        let due_style = if task.is_overdue() {
-            Style::default().fg(Color::Red)
-        } else {
-            Style::default()
-        };
-        lines.push(Line::from(vec![
-            Span::styled("Due: ", Style::default().fg(Color::Cyan)),
-            Span::styled(due_text, due_style),
-        ]));
-
-        if task.assignee.is_some() {
-            let assignee_display = task.assignee_name.as_deref().unwrap_or("Unknown User");
-            lines.push(Line::from(vec![
-                Span::styled("Assignee: ", Style::default().fg(Color::Cyan)),
-                Span::raw(assignee_display.to_string()),
-            ]));
-        }
-
-        // Add blank line separator
-        lines.push(Line::from(""));
-
-        // Add description if present
-        if let Some(description) = &task.description {
-            if !description.trim().is_empty() {
-                let markdown_desc = md::html_to_markdown(description);
-                let styled_lines = md::parse_markdown_to_lines(&markdown_desc);
-
-                lines.extend(styled_lines);
-            }
-        } else {
-            lines.push(Line::from(vec![Span::styled(
-                "No description available",
-                Style::default().fg(Color::Gray),
-            )]));
-        }
-
-        // Apply scrolling - skip lines based on scroll offset
-        let visible_lines: Vec<Line> = lines
-            .into_iter()
-            .skip(self.description_scroll_offset as usize)
-            .collect();
-
-        let paragraph = Paragraph::new(visible_lines)
-            .wrap(Wrap { trim: false })
-            .alignment(ratatui::layout::Alignment::Left);
-        frame.render_widget(paragraph, inner_area);
-    }
-
-    fn render_comments_pane(&self, frame: &mut Frame, area: Rect) {
-        // Create border with focus indicator
-        let border_style = if self.focused_detail_pane == TaskDetailPane::Comments {
-            Style::default().fg(Color::Cyan)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-
-        let title = if self.focused_detail_pane == TaskDetailPane::Comments {
-            "Comments & Activity [FOCUSED] (Tab to switch)"
-        } else {
-            "Comments & Activity (Tab to switch)"
-        };
-
-        let block = Block::default()
-            .title(title)
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(border_style);
-
-        let inner_area = block.inner(area);
-        frame.render_widget(block, area);
-
-        // Collect all comments content
-        let mut lines = Vec::new();
-
-        // Comments and activity
-        if self.task_comments.is_empty() {
-            lines.push(Line::from(vec![Span::styled(
-                "No comments or activity",
-                Style::default().fg(Color::Gray),
-            )]));
-        } else {
-            // Separate comments from system activity
-            let mut user_comments = Vec::new();
-            let mut system_activity = Vec::new();
-
-            for comment in &self.task_comments {
-                match comment.story_type.as_deref() {
-                    Some("comment") => user_comments.push(comment),
-                    Some("system") => system_activity.push(comment),
-                    _ => system_activity.push(comment), // Default to system if unclear
-                }
-            }
-
-            // Render user comments first
-            if !user_comments.is_empty() {
-                lines.push(Line::from(vec![Span::styled(
-                    "Comments",
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                )]));
-                lines.push(Line::from(""));
-
-                for comment in &user_comments {
-                    let author_name = comment
-                        .author
-                        .as_ref()
-                        .map(|u| u.name.as_str())
-                        .unwrap_or("Unknown");
-                    let time_display = comment.created_at.format("%Y-%m-%d %H:%M").to_string();
-
-                    // Header: Name • timestamp
-                    lines.push(Line::from(vec![
-                        Span::styled(author_name, Style::default().fg(Color::Blue)),
-                        Span::styled(
-                            format!(" • {time_display}"),
-                            Style::default().fg(Color::Gray),
-                        ),
-                    ]));
-
-                    if let Some(ref text) = comment.text {
-                        let cleaned_text = md::html_to_markdown(text);
-                        let comment_lines = Self::format_comment_lines(&cleaned_text, None);
-                        lines.extend(comment_lines.into_iter().map(|ml| ml.line));
-                    }
-                    lines.push(Line::from(""));
-                }
-            }
-
-            // Render system activity
-            if !system_activity.is_empty() {
-                lines.push(Line::from(vec![Span::styled(
-                    "Activity",
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                )]));
-                lines.push(Line::from(""));
-
-                for activity in &system_activity {
-                    let time_display = activity.created_at.format("%Y-%m-%d %H:%M").to_string();
-                    let cleaned_text = activity
-                        .text
-                        .as_ref()
-                        .map(|text| md::html_to_markdown(text))
-                        .unwrap_or_else(|| "[No text content]".to_string());
-
-                    let mut activity_spans =
-                        vec![Span::styled("• ", Style::default().fg(Color::Blue))];
-                    activity_spans.extend(Self::format_activity_line(&cleaned_text, &time_display));
-
-                    lines.push(Line::from(activity_spans));
-                }
-            }
-        }
-
-        // Apply scrolling - skip lines based on scroll offset
-        let visible_lines: Vec<Line> = lines
-            .into_iter()
-            .skip(self.comments_scroll_offset as usize)
-            .collect();
-
-        let paragraph = Paragraph::new(visible_lines).wrap(Wrap { trim: false });
-        frame.render_widget(paragraph, inner_area);
-    }
-
-    fn render_task_info_section(&self, frame: &mut Frame, area: Rect, task: &Task) {
-        let mut lines = Vec::new();
-
-        // Task info section
-        let (status_text, status_color) = task.status_display();
-        let status_style = match status_color {
-            "red" => Style::default().fg(Color::Red),
-            "yellow" => Style::default().fg(Color::Yellow),
-            "green" => Style::default().fg(Color::Green),
-            "gray" => Style::default().fg(Color::Gray),
-            _ => Style::default(),
-        };
-
-        lines.push(Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Color::Cyan)),
-            Span::styled(status_text, status_style),
-        ]));
-
-        // Due date with red color if overdue
-        let due_text = task.due_date_display();
-        let due_style = if task.is_overdue() {
-            Style::default().fg(Color::Red)
-        } else {
-            Style::default()
-        };
-        lines.push(Line::from(vec![
-            Span::styled("Due: ", Style::default().fg(Color::Cyan)),
-            Span::styled(due_text, due_style),
-        ]));
-
-        // Assignee
-        if task.assignee.is_some() {
-            let assignee_display = task.assignee_name.as_deref().unwrap_or("Unknown User");
-            lines.push(Line::from(vec![
-                Span::styled("Assignee: ", Style::default().fg(Color::Cyan)),
-                Span::raw(assignee_display.to_string()),
-            ]));
-        }
-
-        let paragraph = Paragraph::new(lines);
-        frame.render_widget(paragraph, area);
-    }
-
-    fn render_description_section(&self, frame: &mut Frame, area: Rect, task: &Task) {
-        if let Some(description) = &task.description {
-            if !description.trim().is_empty() {
-                let markdown_desc = md::html_to_markdown(description);
-
-                // Parse and render markdown with custom styling
-                let styled_lines = md::parse_markdown_to_lines(&markdown_desc);
-
-                let paragraph = Paragraph::new(styled_lines).wrap(Wrap { trim: false });
-
-                frame.render_widget(paragraph, area);
-            }
-        }
-    }
-
-    fn render_comments_section(&self, frame: &mut Frame, area: Rect) {
-        let mut lines = Vec::new();
-
-        // Comments and activity
-        if self.task_comments.is_empty() {
-            lines.push(Line::from(vec![Span::styled(
-                "No comments or activity",
-                Style::default().fg(Color::Gray),
-            )]));
-        } else {
-            // Separate comments from system activity
-            let mut user_comments = Vec::new();
-            let mut system_activity = Vec::new();
-
-            for comment in &self.task_comments {
-                match comment.story_type.as_deref() {
-                    Some("comment") => user_comments.push(comment),
-                    Some("system") => system_activity.push(comment),
-                    _ => system_activity.push(comment), // Default to system if unclear
-                }
-            }
-
-            // Render user comments first
-            if !user_comments.is_empty() {
-                lines.push(Line::from(vec![Span::styled(
-                    "Comments",
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                )]));
-                lines.push(Line::from(""));
-
-                for comment in &user_comments {
-                    // Author and timestamp
-                    let author_name = comment
-                        .author
-                        .as_ref()
-                        .map(|u| u.name.as_str())
-                        .unwrap_or("Unknown");
-                    let time_display = comment.created_at.format("%Y-%m-%d %H:%M").to_string();
-
-                    // Header: Name • timestamp
-                    lines.push(Line::from(vec![
-                        Span::styled(author_name, Style::default().fg(Color::Blue)),
-                        Span::styled(
-                            format!(" • {}", time_display),
-                            Style::default().fg(Color::Gray),
-                        ),
-                    ]));
-
-                    // Comment text with proper markdown parsing and ┃ prefix
-                    if let Some(ref text) = comment.text {
-                        let cleaned_text = md::html_to_markdown(text);
-                        let comment_lines = Self::format_comment_lines(&cleaned_text, None);
-                        lines.extend(comment_lines.into_iter().map(|ml| ml.line));
-                    }
-                    lines.push(Line::from(""));
-                }
-            }
-
-            // Render system activity
-            if !system_activity.is_empty() {
-                lines.push(Line::from(vec![Span::styled(
-                    "Activity",
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                )]));
-                lines.push(Line::from(""));
-
-                for activity in &system_activity {
-                    let time_display = activity.created_at.format("%Y-%m-%d %H:%M").to_string();
-                    let icon = match activity.resource_subtype.as_deref() {
-                        Some("due_date_changed") => "•",
-                        Some("duplicated") => "•",
-                        Some("due_today") => "•",
-                        _ => "•",
-                    };
-
-                    let activity_text = activity
-                        .text
-                        .as_ref()
-                        .map(|s| s.as_str())
-                        .unwrap_or("[No text content]");
-                    let mut activity_spans = vec![Span::styled(
-                        format!("{icon} "),
-                        Style::default().fg(Color::Yellow),
-                    )];
-                    activity_spans.extend(Self::format_activity_line(activity_text, &time_display));
-
-                    lines.push(Line::from(activity_spans));
-                }
-            }
-        }
-
-        // Calculate scrolling with proper clamping
-        let content_height = lines.len() as u16;
-        let max_scroll = content_height.saturating_sub(area.height);
-        let scroll_offset = self.detail_scroll_offset.min(max_scroll);
-
-        let paragraph = Paragraph::new(lines)
-            .wrap(Wrap { trim: false })
-            .scroll((scroll_offset, 0));
-
-        frame.render_widget(paragraph, area);
-
-        // Render scrollbar if content is longer than area
-        if content_height > area.height {
-            let scrollbar = Scrollbar::default()
-                .orientation(ScrollbarOrientation::VerticalRight)
-                .begin_symbol(Some("↑"))
-                .end_symbol(Some("↓"));
-
-            let mut scrollbar_state =
-                ScrollbarState::new(content_height as usize).position(scroll_offset as usize);
-
-            frame.render_stateful_widget(
-                scrollbar,
-                area.inner(Margin {
-                    vertical: 0,
-                    horizontal: 0,
-                }),
-                &mut scrollbar_state,
-            );
-        }
-    }
-
     fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         let popup_layout = Layout::default()
             .direction(Direction::Vertical)
@@ -2168,18 +3473,6 @@ fn hex_to_rgb(hex: &str) -> Option<(u8, u8, u8)> {
     Some((r, g, b))
 }
 
-/// Determine if text should be light or dark based on background color
-fn should_use_light_text(bg_color: &str) -> bool {
-    if let Some((r, g, b)) = hex_to_rgb(bg_color) {
-        let luminance = calculate_luminance(r, g, b);
-        // Use light text if background is dark (luminance < 0.5)
-        luminance < 0.5
-    } else {
-        // Default to dark text if we can't parse the color
-        false
-    }
-}
-
 /// Convert Asana color to ratatui Color with explicit RGB values
 /// This ensures consistent colors regardless of terminal theme
 fn asana_color_to_ratatui(color: &str) -> Color {
@@ -2243,7 +3536,7 @@ fn create_colored_label(text: &str, bg_color: Color) -> Vec<Span<'static>> {
 
     // Use explicit RGB values for text colors to avoid terminal color scheme issues
     let text_color = if luminance > 0.5 {
-        Color::Rgb(0, 0, 0)      // Pure black for light backgrounds
+        Color::Rgb(0, 0, 0) // Pure black for light backgrounds
     } else {
         Color::Rgb(255, 255, 255) // Pure white for dark backgrounds
     };
@@ -2272,12 +3565,13 @@ pub async fn run_tui(mut app: App) -> Result<()> {
     let backend = ratatui::backend::CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Initialize app
-    app.initialize().await?;
-
     // Event handling
     let mut event_handler = EventHandler::new();
 
+    // Draw once before anything waits on Asana
+    terminal.draw(|frame| app.render(frame))?;
+    app.initialize().await?;
+
     // Main loop
     loop {
         terminal.draw(|frame| app.render(frame))?;
@@ -2289,8 +3583,47 @@ pub async fn run_tui(mut app: App) -> Result<()> {
             }
         }
 
+        // Hand the terminal to $EDITOR while the description is edited
+        if let Some((task_id, markdown)) = app.take_description_edit() {
+            crossterm::terminal::disable_raw_mode()?;
+            crossterm::execute!(
+                terminal.backend_mut(),
+                crossterm::terminal::LeaveAlternateScreen
+            )?;
+
+            let edited = editor::edit_text(&markdown, "md").await;
+
+            crossterm::terminal::enable_raw_mode()?;
+            crossterm::execute!(
+                terminal.backend_mut(),
+                crossterm::terminal::EnterAlternateScreen
+            )?;
+            terminal.clear()?;
+
+            app.finish_description_edit(&task_id, &markdown, edited)
+                .await?;
+        }
+
+        // Show fuzzy matches that finished in the background
+        app.tick_search();
+
+        // Replace the cached startup list once Asana has answered
+        app.poll_background_refresh().await?;
+
+        // Show Asana's answers to changes already on screen
+        app.poll_mutations();
+
+        // Send changes made offline once Asana is reachable again
+        app.tick_offline_sync().await?;
+
+        // Take in background loads that have finished
+        app.process_messages().await?;
+
+        // Pick up changes teammates made in Asana
+        app.tick_live_refresh();
+
         // Auto-load task details when selection changes
-        app.auto_load_selected_task().await?;
+        app.auto_load_selected_task();
 
         if event_handler.should_quit() {
             break;
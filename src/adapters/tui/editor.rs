@@ -0,0 +1,38 @@
+use color_eyre::{eyre::eyre, Result};
+
+/// The user's editor command from `$VISUAL` or `$EDITOR`, falling back to `vi`
+fn editor_command() -> Vec<String> {
+    let command = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|c| !c.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    // Allow editors that need flags, e.g. "code --wait"
+    command.split_whitespace().map(str::to_string).collect()
+}
+
+/// Open `initial` in the external editor and return the saved contents.
+///
+/// The editor needs the real terminal, so the caller must leave the alternate
+/// screen and raw mode before calling this.
+pub async fn edit_text(initial: &str, extension: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("asana-cli-{}.{extension}", uuid::Uuid::new_v4()));
+    tokio::fs::write(&path, initial).await?;
+
+    let command = editor_command();
+    let status = tokio::process::Command::new(&command[0])
+        .args(&command[1..])
+        .arg(&path)
+        .status()
+        .await;
+
+    let result = match status {
+        Ok(status) if status.success() => Ok(tokio::fs::read_to_string(&path).await?),
+        Ok(status) => Err(eyre!("Editor exited with {status}")),
+        Err(e) => Err(eyre!("Failed to launch editor '{}': {e}", command[0])),
+    };
+
+    let _ = tokio::fs::remove_file(&path).await;
+    result
+}
@@ -25,6 +25,7 @@ pub enum AppEvent {
     Enter,
     Tab,
     BackTab,
+    Submit,
 }
 
 pub struct EventHandler {
@@ -169,6 +170,18 @@ impl EventHandler {
 
             // 'c' is handled in Character processing to allow search input
 
+            // Submit multi-line input (Enter inserts a newline there)
+            KeyEvent {
+                code: KeyCode::Char('s'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => Some(AppEvent::Submit),
+
             // Input characters
             KeyEvent {
                 code: KeyCode::Char(c),
@@ -0,0 +1,192 @@
+use crate::domain::Task;
+use nucleo::pattern::{CaseMatching, Normalization};
+use nucleo::{Config, Matcher, Nucleo, Utf32Str};
+use ratatui::prelude::*;
+use std::sync::Arc;
+
+/// Fuzzy matcher over task names.
+///
+/// Matching runs on nucleo's worker threads, so typing never waits on a large
+/// task list; call [`TaskSearch::tick`] regularly to pick up finished results.
+pub struct TaskSearch {
+    nucleo: Nucleo<usize>,
+    matcher: Matcher,
+    query: String,
+}
+
+impl TaskSearch {
+    pub fn new() -> Self {
+        Self {
+            nucleo: Nucleo::new(Config::DEFAULT, Arc::new(|| {}), None, 1),
+            matcher: Matcher::new(Config::DEFAULT),
+            query: String::new(),
+        }
+    }
+
+    /// Replace the searchable tasks, keeping the current query
+    pub fn set_tasks(&mut self, tasks: &[Task]) {
+        self.nucleo.restart(true);
+        let injector = self.nucleo.injector();
+        for (index, task) in tasks.iter().enumerate() {
+            injector.push(index, |_, columns| columns[0] = task.name.as_str().into());
+        }
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        if query == self.query {
+            return;
+        }
+
+        // Typing more characters can only narrow the previous matches
+        let append = query.starts_with(self.query.as_str());
+        self.nucleo
+            .pattern
+            .reparse(0, query, CaseMatching::Smart, Normalization::Smart, append);
+        self.query = query.to_string();
+    }
+
+    /// Give the matcher up to `timeout_ms` to finish, returning whether the results changed
+    pub fn tick(&mut self, timeout_ms: u64) -> bool {
+        self.nucleo.tick(timeout_ms).changed
+    }
+
+    /// Wait for matching on the worker threads to finish
+    #[cfg(test)]
+    fn wait(&mut self) {
+        for _ in 0..200 {
+            if !self.nucleo.tick(10).running {
+                return;
+            }
+        }
+        panic!("Matching didn't finish in time");
+    }
+
+    /// Indices of the matching tasks in ranked order, best first
+    pub fn matches(&self) -> Vec<usize> {
+        self.nucleo
+            .snapshot()
+            .matched_items(..)
+            .map(|item| *item.data)
+            .collect()
+    }
+
+    /// The characters of `name` matched by the current query, for highlighting.
+    /// Cheap enough per row, so only call it for the rows being drawn.
+    pub fn highlights(&mut self, name: &str) -> Vec<u32> {
+        let mut buf = Vec::new();
+        let mut indices = Vec::new();
+        let pattern = self.nucleo.pattern.column_pattern(0);
+        pattern.indices(
+            Utf32Str::new(name, &mut buf),
+            &mut self.matcher,
+            &mut indices,
+        );
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+}
+
+/// Split `text` into spans, applying `highlight` to the characters at `indices`
+pub fn highlight_spans(
+    text: &str,
+    indices: &[u32],
+    base: Style,
+    highlight: Style,
+) -> Vec<Span<'static>> {
+    if indices.is_empty() {
+        return vec![Span::styled(text.to_string(), base)];
+    }
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_highlighted = false;
+
+    for (i, ch) in text.chars().enumerate() {
+        let highlighted = indices.binary_search(&(i as u32)).is_ok();
+        if highlighted != run_highlighted && !run.is_empty() {
+            let style = if run_highlighted { highlight } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_highlighted = highlighted;
+        run.push(ch);
+    }
+
+    if !run.is_empty() {
+        let style = if run_highlighted { highlight } else { base };
+        spans.push(Span::styled(run, style));
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::domain_task;
+
+    fn task(name: &str) -> Task {
+        domain_task(name, name)
+    }
+
+    fn search(tasks: &[Task], query: &str) -> Vec<usize> {
+        let mut search = TaskSearch::new();
+        search.set_tasks(tasks);
+        search.set_query(query);
+        search.wait();
+        search.matches()
+    }
+
+    #[test]
+    fn test_fuzzy_matches_are_ranked() {
+        let tasks = [
+            task("Update release notes"),
+            task("Fix login bug"),
+            task("Refactor billing"),
+        ];
+
+        let matches = search(&tasks, "fb");
+        let names: Vec<_> = matches
+            .iter()
+            .map(|&index| tasks[index].name.as_str())
+            .collect();
+        assert_eq!(names[0], "Fix login bug");
+        assert!(!names.contains(&"Update release notes"));
+    }
+
+    #[test]
+    fn test_highlights_point_at_name_characters() {
+        let mut search = TaskSearch::new();
+        search.set_query("login");
+
+        assert_eq!(search.highlights("Fix login bug"), vec![4, 5, 6, 7, 8]);
+        assert!(search.highlights("Refactor billing").is_empty());
+    }
+
+    #[test]
+    fn test_narrowing_query_reuses_results() {
+        let tasks = [task("Write docs"), task("Write tests")];
+        let mut search = TaskSearch::new();
+        search.set_tasks(&tasks);
+
+        search.set_query("wr");
+        search.wait();
+        assert_eq!(search.matches().len(), 2);
+
+        search.set_query("wrtest");
+        search.wait();
+        assert_eq!(search.matches(), [1]);
+    }
+
+    #[test]
+    fn test_highlight_spans_groups_runs() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let spans = highlight_spans("abcd", &[1, 2], Style::default(), bold);
+
+        let parts: Vec<_> = spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style == bold))
+            .collect();
+        assert_eq!(parts, [("a", false), ("bc", true), ("d", false)]);
+    }
+}
@@ -87,11 +87,10 @@ fn split_line_into_words(line: &Line) -> Vec<WordSpan> {
 /// Calculate indentation for continuation lines based on list structure
 fn calculate_continuation_indent(line: &Line) -> usize {
     let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
-    let mut chars = text.char_indices();
     let mut indent = 0;
 
     // Count leading whitespace
-    while let Some((_, ch)) = chars.next() {
+    for ch in text.chars() {
         if ch == ' ' {
             indent += 1;
         } else if ch == '\t' {
@@ -325,7 +324,7 @@ fn replace_markdown_images(markdown: &str) -> String {
             let mut alt_text = String::new();
             let mut found_closing = false;
 
-            while let Some(ch) = chars.next() {
+            for ch in chars.by_ref() {
                 if ch == ']' {
                     found_closing = true;
                     break;
@@ -338,7 +337,7 @@ fn replace_markdown_images(markdown: &str) -> String {
                 chars.next(); // consume '('
 
                 let mut depth = 1;
-                while let Some(ch) = chars.next() {
+                for ch in chars.by_ref() {
                     if ch == '(' {
                         depth += 1;
                     } else if ch == ')' {
@@ -413,12 +412,150 @@ pub fn html_to_markdown(html: &str) -> String {
     }
 }
 
+/// Escape text for inclusion in Asana rich text
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Convert markdown back to the rich text HTML Asana accepts in `html_notes`.
+///
+/// Mirrors `html_to_markdown`: Asana only allows a small set of tags inside a
+/// `<body>` root (no `<p>` or `<br>`), so paragraphs and line breaks become
+/// literal newlines, headings are capped at `<h2>` and raw HTML is escaped.
+pub fn markdown_to_html(markdown: &str) -> String {
+    use pulldown_cmark::{HeadingLevel, Options};
+
+    let mut html = String::from("<body>");
+    // Nesting of block containers (lists, quotes); paragraphs inside them are not separated
+    let mut block_depth = 0usize;
+    let mut needs_separator = false;
+
+    let parser = Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH);
+    for event in parser {
+        // Start of a top-level block: separate it from the previous one
+        let starts_block = matches!(
+            event,
+            Event::Start(
+                Tag::Paragraph
+                    | Tag::Heading { .. }
+                    | Tag::List(_)
+                    | Tag::CodeBlock(_)
+                    | Tag::BlockQuote(_)
+            ) | Event::Rule
+        );
+        if starts_block && block_depth == 0 && needs_separator {
+            html.push('\n');
+            needs_separator = false;
+        }
+
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Paragraph => {}
+                Tag::Heading { level, .. } => html.push_str(match level {
+                    HeadingLevel::H1 => "<h1>",
+                    _ => "<h2>",
+                }),
+                Tag::BlockQuote(_) => {
+                    block_depth += 1;
+                    html.push_str("<blockquote>");
+                }
+                Tag::CodeBlock(_) => html.push_str("<pre>"),
+                Tag::List(Some(_)) => {
+                    block_depth += 1;
+                    html.push_str("<ol>");
+                }
+                Tag::List(None) => {
+                    block_depth += 1;
+                    html.push_str("<ul>");
+                }
+                Tag::Item => html.push_str("<li>"),
+                Tag::Emphasis => html.push_str("<em>"),
+                Tag::Strong => html.push_str("<strong>"),
+                Tag::Strikethrough => html.push_str("<s>"),
+                // Asana has no inline images in notes, so keep them as links
+                Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                    html.push_str(&format!("<a href=\"{}\">", escape_html(&dest_url)));
+                }
+                _ => {}
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Paragraph => {
+                    if block_depth == 0 {
+                        // A blank line between paragraphs, like the markdown had
+                        html.push('\n');
+                        needs_separator = true;
+                    } else if !html.ends_with("<li>") {
+                        html.push('\n');
+                    }
+                }
+                TagEnd::Heading(level) => {
+                    html.push_str(match level {
+                        HeadingLevel::H1 => "</h1>",
+                        _ => "</h2>",
+                    });
+                    needs_separator = block_depth == 0;
+                }
+                TagEnd::BlockQuote => {
+                    block_depth -= 1;
+                    trim_trailing_newlines(&mut html);
+                    html.push_str("</blockquote>");
+                    needs_separator = block_depth == 0;
+                }
+                TagEnd::CodeBlock => {
+                    trim_trailing_newlines(&mut html);
+                    html.push_str("</pre>");
+                    needs_separator = block_depth == 0;
+                }
+                TagEnd::List(ordered) => {
+                    block_depth -= 1;
+                    html.push_str(if ordered { "</ol>" } else { "</ul>" });
+                    needs_separator = block_depth == 0;
+                }
+                TagEnd::Item => {
+                    trim_trailing_newlines(&mut html);
+                    html.push_str("</li>");
+                }
+                TagEnd::Emphasis => html.push_str("</em>"),
+                TagEnd::Strong => html.push_str("</strong>"),
+                TagEnd::Strikethrough => html.push_str("</s>"),
+                TagEnd::Link | TagEnd::Image => html.push_str("</a>"),
+                _ => {}
+            },
+            Event::Text(text) => html.push_str(&escape_html(&text)),
+            Event::Code(code) => html.push_str(&format!("<code>{}</code>", escape_html(&code))),
+            // Asana rejects unknown tags, so show raw HTML as typed
+            Event::Html(raw) | Event::InlineHtml(raw) => html.push_str(&escape_html(&raw)),
+            Event::SoftBreak | Event::HardBreak => html.push('\n'),
+            Event::Rule => {
+                html.push_str("<hr/>");
+                needs_separator = block_depth == 0;
+            }
+            _ => {}
+        }
+    }
+
+    trim_trailing_newlines(&mut html);
+    html.push_str("</body>");
+    html
+}
+
+fn trim_trailing_newlines(html: &mut String) {
+    while html.ends_with('\n') {
+        html.pop();
+    }
+}
+
 /// Parse markdown text and convert to styled Lines for better rendering
+#[cfg(test)]
 pub fn parse_markdown_to_lines(markdown: &str) -> Vec<Line<'static>> {
     parse_markdown_to_lines_with_width(markdown, None)
 }
 
 /// Parse markdown text and convert to styled Lines with optional width for code blocks
+#[cfg(test)]
 pub fn parse_markdown_to_lines_with_width(
     markdown: &str,
     width: Option<u16>,
@@ -473,7 +610,7 @@ pub fn parse_markdown_to_marked_lines(markdown: &str, width: Option<u16>) -> Vec
     let finish_line =
         |spans: &mut Vec<Span<'static>>, lines: &mut Vec<MarkdownLine>, is_code: bool| {
             if !spans.is_empty() {
-                let line_spans: Vec<Span<'static>> = spans.drain(..).collect();
+                let line_spans: Vec<Span<'static>> = std::mem::take(spans);
                 lines.push(MarkdownLine {
                     line: Line::from(line_spans),
                     is_code_block: is_code,
@@ -627,7 +764,7 @@ pub fn parse_markdown_to_marked_lines(markdown: &str, width: Option<u16>) -> Vec
                             if current_line_spans.is_empty()
                                 || !current_line_spans
                                     .last()
-                                    .map_or(false, |s| s.content.contains("["))
+                                    .is_some_and(|s| s.content.contains("["))
                             {
                                 current_line_spans.push(Span::raw("[Image]"));
                             }
@@ -651,7 +788,7 @@ pub fn parse_markdown_to_marked_lines(markdown: &str, width: Option<u16>) -> Vec
                 }
             }
             Event::Text(text) => {
-                if link_destination.is_some() && matches!(emphasis_stack.last(), Some(_)) {
+                if link_destination.is_some() && emphasis_stack.last().is_some() {
                     // This might be image alt text - check if we're in an image context
                     // For now, we'll handle it as regular link text
                     let mut style = Style::default();
@@ -798,7 +935,7 @@ where 1 = 1</pre>
             println!("\nLine {}: {} spans", i, line.spans.len());
 
             // Check if this is a list item by looking for bullet point
-            if line.spans.len() > 0 {
+            if !line.spans.is_empty() {
                 let first_span_content = &line.spans[0].content;
 
                 // Count leading spaces to determine depth
@@ -878,7 +1015,7 @@ fn main() {
         }
 
         // Basic assertions
-        assert!(lines.len() > 0);
+        assert!(!lines.is_empty());
         // Should have headers, list items, code blocks, etc
         // With blank lines added, we'll have more lines
         assert!(lines.len() >= 20);
@@ -960,7 +1097,7 @@ fn main() {
             .iter()
             .map(|s| s.content.to_string())
             .collect();
-        let line2_text: String = lines[2]
+        let _line2_text: String = lines[2]
             .spans
             .iter()
             .map(|s| s.content.to_string())
@@ -1266,4 +1403,30 @@ More content after the rule."#;
             "Should have 4-space indented item"
         );
     }
+
+    #[test]
+    fn test_markdown_to_html_uses_asana_tags() {
+        let markdown = "# Title\n\nSome **bold**, *italic*, ~~gone~~ and `code`.\nSecond line\n\n- one\n- two [link](https://example.com?a=1&b=2)\n\n```\nfn main() {}\n```\n\n> quoted\n\n### Small <b>heading</b>";
+
+        let html = markdown_to_html(markdown);
+
+        assert_eq!(
+            html,
+            "<body><h1>Title</h1>\nSome <strong>bold</strong>, <em>italic</em>, <s>gone</s> and <code>code</code>.\nSecond line\n\n<ul><li>one</li><li>two <a href=\"https://example.com?a=1&amp;b=2\">link</a></li></ul>\n<pre>fn main() {}</pre>\n<blockquote>quoted</blockquote>\n<h2>Small &lt;b&gt;heading&lt;/b&gt;</h2></body>"
+        );
+    }
+
+    #[test]
+    fn test_markdown_round_trips_through_asana_html() {
+        let markdown = "# Plan\n\nShip the **release** with `cargo`.\n\n1. Build\n2. Test\n   - unit\n   - e2e\n\nDone.";
+
+        let round_tripped = html_to_markdown(&markdown_to_html(markdown));
+
+        assert_eq!(markdown_to_html(&round_tripped), markdown_to_html(markdown));
+    }
+
+    #[test]
+    fn test_markdown_to_html_empty() {
+        assert_eq!(markdown_to_html(""), "<body></body>");
+    }
 }
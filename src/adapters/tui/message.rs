@@ -0,0 +1,81 @@
+use crate::application::{AppResult, EventCursor, LiveUpdate};
+use crate::domain::{
+    Comment, Project, ProjectId, Section, Task, TaskId, User, Workspace, WorkspaceId,
+};
+use std::collections::HashMap;
+
+/// What network work spawned off the event loop reports back to the app
+#[derive(Debug)]
+pub enum AppMessage {
+    /// A task list load finished; only the latest `generation` is shown
+    TasksLoaded {
+        generation: u64,
+        result: AppResult<LoadedTasks>,
+        /// Task to keep the cursor on, if it is still listed
+        select: Option<TaskId>,
+    },
+    /// Details for a task finished loading, maybe after the cursor moved on
+    TaskDetailsLoaded(Box<LoadedDetails>),
+    /// A poll of the list's event stream finished; only the latest `generation` counts
+    EventsPolled {
+        generation: u64,
+        cursor: EventCursor,
+        result: AppResult<LiveUpdate>,
+    },
+    /// A request made from a key press finished; only one is out at a time
+    Reply(Box<Reply>),
+}
+
+/// Answers to requests the user set off, e.g. by opening a picker or posting a comment
+#[derive(Debug)]
+pub enum Reply {
+    /// Signing in at startup finished; `true` when a workspace is already chosen
+    Initialized(AppResult<bool>),
+    /// Subtasks fetched to expand `parent`
+    SubtasksLoaded {
+        parent: TaskId,
+        result: AppResult<Vec<Task>>,
+    },
+    /// The workspace's people, fetched to assign `task` to one of them
+    UsersLoaded {
+        task: TaskId,
+        result: AppResult<Vec<User>>,
+    },
+    /// Workspaces to pick from, with the one in use
+    WorkspacesLoaded(AppResult<(Vec<Workspace>, Option<WorkspaceId>)>),
+    ProjectsLoaded(AppResult<Vec<Project>>),
+    /// A project's sections and tasks, to open its board or refresh the open one
+    BoardLoaded {
+        project: ProjectId,
+        name: String,
+        refresh: bool,
+        result: AppResult<(Vec<Section>, Vec<Task>)>,
+    },
+    /// Asana's answer to moving `task` one column in direction `step`
+    BoardTaskMoved {
+        task: TaskId,
+        step: isize,
+        result: AppResult<()>,
+    },
+    TaskCreated(AppResult<Task>),
+    CommentPosted {
+        task: TaskId,
+        result: AppResult<Comment>,
+    },
+}
+
+/// A fetched task list with the subtasks of its expanded rows
+#[derive(Debug)]
+pub struct LoadedTasks {
+    pub tasks: Vec<Task>,
+    pub subtasks: HashMap<TaskId, Vec<Task>>,
+}
+
+/// Everything the detail panes show for one task
+#[derive(Debug)]
+pub struct LoadedDetails {
+    pub task_id: TaskId,
+    pub task: AppResult<Task>,
+    pub comments: AppResult<Vec<Comment>>,
+    pub subtasks: Vec<Task>,
+}
@@ -1,6 +1,9 @@
 pub mod app;
+pub mod editor;
 pub mod event;
+pub mod fuzzy;
 pub mod md;
+pub mod message;
 pub mod views;
 pub mod widgets;
 
@@ -0,0 +1,290 @@
+use crate::domain::{ProjectId, Section, SectionId, Task, TaskId};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+};
+
+/// Narrowest a column may get before the board scrolls sideways
+const MIN_COLUMN_WIDTH: u16 = 24;
+
+struct BoardColumn {
+    section: Section,
+    tasks: Vec<Task>,
+    state: ListState,
+}
+
+/// A project's tasks laid out with one column per section
+pub struct Board {
+    project_name: String,
+    columns: Vec<BoardColumn>,
+    /// Index of the focused column
+    column: usize,
+    error: Option<String>,
+}
+
+impl Board {
+    /// Group `tasks` under the project's `sections`, keeping each section's task order
+    pub fn new(
+        project: &ProjectId,
+        project_name: impl Into<String>,
+        sections: Vec<Section>,
+        tasks: Vec<Task>,
+    ) -> Self {
+        let mut columns: Vec<BoardColumn> = sections
+            .into_iter()
+            .map(|section| BoardColumn {
+                section,
+                tasks: Vec::new(),
+                state: ListState::default(),
+            })
+            .collect();
+
+        for task in tasks {
+            let index = task
+                .section_in(project)
+                .and_then(|s| columns.iter().position(|c| c.section.id == s.id))
+                // Every project task has a section; fall back to the first column regardless
+                .unwrap_or(0);
+            if let Some(column) = columns.get_mut(index) {
+                column.tasks.push(task);
+            }
+        }
+
+        for column in &mut columns {
+            if !column.tasks.is_empty() {
+                column.state.select(Some(0));
+            }
+        }
+
+        Self {
+            project_name: project_name.into(),
+            columns,
+            column: 0,
+            error: None,
+        }
+    }
+
+    pub fn selected_task(&self) -> Option<&Task> {
+        let column = self.columns.get(self.column)?;
+        column.tasks.get(column.state.selected()?)
+    }
+
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+
+    pub fn next_task(&mut self) {
+        if let Some(column) = self.columns.get_mut(self.column) {
+            if let Some(i) = column.state.selected() {
+                column
+                    .state
+                    .select(Some((i + 1).min(column.tasks.len() - 1)));
+            }
+        }
+    }
+
+    pub fn previous_task(&mut self) {
+        if let Some(column) = self.columns.get_mut(self.column) {
+            if let Some(i) = column.state.selected() {
+                column.state.select(Some(i.saturating_sub(1)));
+            }
+        }
+    }
+
+    pub fn next_column(&mut self) {
+        self.column = (self.column + 1).min(self.columns.len().saturating_sub(1));
+    }
+
+    pub fn previous_column(&mut self) {
+        self.column = self.column.saturating_sub(1);
+    }
+
+    /// The selected task and the section next to it in direction `step`,
+    /// or `None` at the edge of the board
+    pub fn move_target(&self, step: isize) -> Option<(TaskId, SectionId)> {
+        let task = self.selected_task()?;
+        let target = self.column.checked_add_signed(step)?;
+        let section = &self.columns.get(target)?.section;
+        Some((task.id.clone(), section.id.clone()))
+    }
+
+    /// Move the selected task one column over in direction `step`, following it with the focus
+    pub fn move_selected(&mut self, step: isize) {
+        let Some(target) = self.column.checked_add_signed(step) else {
+            return;
+        };
+        if target >= self.columns.len() {
+            return;
+        }
+
+        let source = &mut self.columns[self.column];
+        let Some(index) = source.state.selected() else {
+            return;
+        };
+        let task = source.tasks.remove(index);
+        source.state.select(if source.tasks.is_empty() {
+            None
+        } else {
+            Some(index.min(source.tasks.len() - 1))
+        });
+
+        // Asana puts moved tasks at the top of their new section
+        let destination = &mut self.columns[target];
+        destination.tasks.insert(0, task);
+        destination.state.select(Some(0));
+        self.column = target;
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Columns
+                Constraint::Length(1), // Help or error
+            ])
+            .split(area);
+
+        if self.columns.is_empty() {
+            let paragraph = Paragraph::new("This project has no sections").block(
+                Block::default()
+                    .title(self.project_name.as_str())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            );
+            frame.render_widget(paragraph, chunks[0]);
+        } else {
+            // Show as many columns as fit, scrolled so the focused one is visible
+            let fit = (chunks[0].width / MIN_COLUMN_WIDTH).max(1) as usize;
+            let visible = fit.min(self.columns.len());
+            let first = (self.column + 1).saturating_sub(visible);
+
+            let areas = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, visible as u32); visible])
+                .split(chunks[0]);
+
+            for (offset, column_area) in areas.iter().enumerate() {
+                let index = first + offset;
+                let focused = index == self.column;
+                let column = &mut self.columns[index];
+                render_column(frame, *column_area, column, focused);
+            }
+        }
+
+        let footer = match &self.error {
+            Some(error) => Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)),
+            None => Paragraph::new(format!(
+                "{} | h/l: column | j/k: task | H/L: move task | r: refresh | Esc: back",
+                self.project_name
+            ))
+            .style(Style::default().fg(Color::DarkGray)),
+        };
+        frame.render_widget(footer, chunks[1]);
+    }
+}
+
+fn render_column(frame: &mut Frame, area: Rect, column: &mut BoardColumn, focused: bool) {
+    let border_style = if focused {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+    let block = Block::default()
+        .title(format!("{} ({})", column.section.name, column.tasks.len()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(border_style);
+
+    let items: Vec<ListItem> = column
+        .tasks
+        .iter()
+        .map(|task| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{} ", task.type_icon()),
+                    Style::default().fg(task.icon_color()),
+                ),
+                Span::raw(task.name.clone()),
+            ]))
+        })
+        .collect();
+
+    let highlight = if focused {
+        Style::default().bg(Color::DarkGray)
+    } else {
+        Style::default()
+    };
+    let list = List::new(items).block(block).highlight_style(highlight);
+    frame.render_stateful_widget(list, area, &mut column.state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{TaskMembership, TaskSection};
+    use crate::test_support::domain_task;
+
+    fn section(gid: &str, name: &str) -> Section {
+        Section {
+            id: gid.into(),
+            name: name.to_string(),
+            project: "p1".into(),
+        }
+    }
+
+    fn task_in(gid: &str, section: &str) -> Task {
+        let mut task = domain_task(gid, gid);
+        task.memberships.push(TaskMembership {
+            project: "p1".into(),
+            section: Some(TaskSection {
+                id: section.into(),
+                name: section.to_string(),
+            }),
+        });
+        task
+    }
+
+    fn board() -> Board {
+        Board::new(
+            &"p1".into(),
+            "Sprint",
+            vec![section("s1", "To do"), section("s2", "Doing")],
+            vec![task_in("1", "s2"), task_in("2", "s1"), task_in("3", "s1")],
+        )
+    }
+
+    fn column_tasks(board: &Board, index: usize) -> Vec<&str> {
+        board.columns[index]
+            .tasks
+            .iter()
+            .map(|t| t.id.0.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_tasks_are_grouped_by_section() {
+        let board = board();
+
+        assert_eq!(column_tasks(&board, 0), ["2", "3"]);
+        assert_eq!(column_tasks(&board, 1), ["1"]);
+        assert_eq!(board.selected_task().unwrap().id, TaskId::from("2"));
+    }
+
+    #[test]
+    fn test_move_selected_task_between_columns() {
+        let mut board = board();
+        board.next_task();
+
+        assert_eq!(board.move_target(-1), None);
+        assert_eq!(
+            board.move_target(1),
+            Some((TaskId::from("3"), SectionId::from("s2")))
+        );
+
+        board.move_selected(1);
+        assert_eq!(column_tasks(&board, 0), ["2"]);
+        assert_eq!(column_tasks(&board, 1), ["3", "1"]);
+        assert_eq!(board.selected_task().unwrap().id, TaskId::from("3"));
+        assert_eq!(board.move_target(1), None);
+    }
+}
@@ -1 +1,3 @@
-// Views will be implemented later
+pub mod board;
+
+pub use board::*;
@@ -0,0 +1,86 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+
+/// Multi-line text input for writing a comment on the selected task
+pub struct CommentComposer {
+    text: String,
+    error: Option<String>,
+}
+
+impl CommentComposer {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        self.text.trim()
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.text.push(c);
+        self.error = None;
+    }
+
+    pub fn insert_newline(&mut self) {
+        self.text.push('\n');
+    }
+
+    pub fn delete_char(&mut self) {
+        self.text.pop();
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title("New Comment (Ctrl+S: post | Enter: newline | Esc: cancel)")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+
+        let mut lines: Vec<Line> = if self.text.is_empty() {
+            vec![Line::styled(
+                "Write a comment...",
+                Style::default().fg(Color::DarkGray),
+            )]
+        } else {
+            self.text.split('\n').map(Line::raw).collect()
+        };
+        if let Some(error) = &self.error {
+            lines.push(Line::styled(error.clone(), Style::default().fg(Color::Red)));
+        }
+
+        // Keep the end of the text (where the cursor is) in view
+        let total_lines = lines.len() as u16;
+        let scroll = total_lines.saturating_sub(inner.height);
+
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0));
+        frame.render_widget(paragraph, area);
+
+        if self.error.is_none() {
+            let last_line = self.text.rsplit('\n').next().unwrap_or_default();
+            let cursor_x = inner.x + last_line.chars().count() as u16;
+            let cursor_y = inner.y
+                + total_lines
+                    .saturating_sub(1)
+                    .min(inner.height.saturating_sub(1));
+            if cursor_x < inner.x + inner.width {
+                frame.set_cursor_position(Position {
+                    x: cursor_x,
+                    y: cursor_y,
+                });
+            }
+        }
+    }
+}
@@ -1,3 +1,9 @@
+pub mod comment_composer;
+pub mod picker;
 pub mod search_bar;
+pub mod task_form;
 
+pub use comment_composer::*;
+pub use picker::*;
 pub use search_bar::*;
+pub use task_form::*;
@@ -0,0 +1,200 @@
+use nucleo::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo::{Config, Matcher, Utf32Str};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// A modal list for choosing one item, narrowed by fuzzy typing
+pub struct Picker<T> {
+    title: String,
+    items: Vec<(T, String)>,
+    filter: String,
+    matcher: Matcher,
+    /// Indices into `items` that match `filter`, best match first
+    visible: Vec<usize>,
+    state: ListState,
+    /// Index into `items` of the entry currently in use, marked in the list
+    active: Option<usize>,
+}
+
+impl<T> Picker<T> {
+    pub fn new(title: impl Into<String>, items: Vec<(T, String)>) -> Self {
+        let mut picker = Self {
+            title: title.into(),
+            items,
+            filter: String::new(),
+            matcher: Matcher::new(Config::DEFAULT),
+            visible: Vec::new(),
+            state: ListState::default(),
+            active: None,
+        };
+        picker.refilter();
+        picker
+    }
+
+    /// Mark and preselect the first item matching `is_active`
+    pub fn with_active(mut self, is_active: impl Fn(&T) -> bool) -> Self {
+        if let Some(index) = self.items.iter().position(|(item, _)| is_active(item)) {
+            self.active = Some(index);
+            self.state
+                .select(self.visible.iter().position(|&i| i == index));
+        }
+        self
+    }
+
+    pub fn selected(&self) -> Option<&T> {
+        let index = *self.visible.get(self.state.selected()?)?;
+        self.items.get(index).map(|(item, _)| item)
+    }
+
+    pub fn selected_label(&self) -> Option<&str> {
+        let index = *self.visible.get(self.state.selected()?)?;
+        self.items.get(index).map(|(_, label)| label.as_str())
+    }
+
+    pub fn next(&mut self) {
+        if !self.visible.is_empty() {
+            let next = self
+                .state
+                .selected()
+                .map_or(0, |i| (i + 1) % self.visible.len());
+            self.state.select(Some(next));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.visible.is_empty() {
+            let len = self.visible.len();
+            let previous = self.state.selected().map_or(0, |i| (i + len - 1) % len);
+            self.state.select(Some(previous));
+        }
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.filter.push(c);
+        self.refilter();
+    }
+
+    pub fn delete_char(&mut self) {
+        self.filter.pop();
+        self.refilter();
+    }
+
+    fn refilter(&mut self) {
+        let pattern = Pattern::parse(&self.filter, CaseMatching::Smart, Normalization::Smart);
+        let mut buf = Vec::new();
+        let mut scored: Vec<(usize, u32)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (_, label))| {
+                let score = pattern.score(Utf32Str::new(label, &mut buf), &mut self.matcher)?;
+                Some((i, score))
+            })
+            .collect();
+        // Stable, so an empty filter keeps the original order
+        scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        self.visible = scored.into_iter().map(|(i, _)| i).collect();
+        self.state.select(if self.visible.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(self.title.as_str())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Filter
+                Constraint::Min(1),    // Items
+                Constraint::Length(1), // Help
+            ])
+            .split(inner);
+
+        let filter = if self.filter.is_empty() {
+            Line::styled("Type to filter...", Style::default().fg(Color::DarkGray))
+        } else {
+            Line::raw(format!("> {}", self.filter))
+        };
+        frame.render_widget(Paragraph::new(filter), chunks[0]);
+
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .map(|&i| {
+                let label = &self.items[i].1;
+                if self.active == Some(i) {
+                    ListItem::new(Line::from(vec![
+                        Span::raw(label.clone()),
+                        Span::styled(" (current)", Style::default().fg(Color::DarkGray)),
+                    ]))
+                } else {
+                    ListItem::new(label.clone())
+                }
+            })
+            .collect();
+
+        if items.is_empty() {
+            let paragraph =
+                Paragraph::new("No matches").style(Style::default().fg(Color::DarkGray));
+            frame.render_widget(paragraph, chunks[1]);
+        } else {
+            let list = List::new(items)
+                .highlight_style(Style::default().bg(Color::DarkGray))
+                .highlight_symbol("> ");
+            frame.render_stateful_widget(list, chunks[1], &mut self.state);
+        }
+
+        let help = Paragraph::new("↑/↓: move | Enter: select | Esc: cancel")
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(help, chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(picker: &Picker<u32>) -> Vec<&str> {
+        picker
+            .visible
+            .iter()
+            .map(|&i| picker.items[i].1.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_is_fuzzy_and_ranked() {
+        let mut picker = Picker::new(
+            "Assignee",
+            vec![
+                (1, "Unassigned".to_string()),
+                (2, "Ada Lovelace <ada@example.com>".to_string()),
+                (3, "Alan Turing <alan@example.com>".to_string()),
+            ],
+        );
+        assert_eq!(labels(&picker).len(), 3);
+
+        for c in "atur".chars() {
+            picker.insert_char(c);
+        }
+        assert_eq!(labels(&picker), ["Alan Turing <alan@example.com>"]);
+        assert_eq!(picker.selected(), Some(&3));
+
+        picker.delete_char();
+        picker.delete_char();
+        assert_eq!(labels(&picker)[0], "Alan Turing <alan@example.com>");
+    }
+}
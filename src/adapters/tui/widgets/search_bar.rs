@@ -7,6 +7,7 @@ pub struct SearchBar {
     query: String,
     cursor_position: usize,
     is_focused: bool,
+    error: Option<String>,
 }
 
 impl SearchBar {
@@ -15,6 +16,7 @@ impl SearchBar {
             query: String::new(),
             cursor_position: 0,
             is_focused: false, // Start unfocused by default
+            error: None,
         }
     }
 
@@ -45,6 +47,12 @@ impl SearchBar {
     pub fn clear(&mut self) {
         self.query.clear();
         self.cursor_position = 0;
+        self.error = None;
+    }
+
+    /// Show a problem with the current query, e.g. a parse error
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
@@ -54,17 +62,25 @@ impl SearchBar {
             "Search (press / to focus)"
         };
 
-        let border_style = if self.is_focused {
+        let border_style = if self.error.is_some() {
+            Style::default().fg(Color::Red)
+        } else if self.is_focused {
             Style::default().fg(Color::Yellow)
         } else {
             Style::default().fg(Color::Gray)
         };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .title(title)
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(border_style);
+        if let Some(error) = &self.error {
+            block = block.title_bottom(Line::styled(
+                format!(" {error} "),
+                Style::default().fg(Color::Red),
+            ));
+        }
 
         let search_text = if self.query.is_empty() {
             if self.is_focused {
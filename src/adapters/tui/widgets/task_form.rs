@@ -0,0 +1,191 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+pub struct FormField {
+    pub label: &'static str,
+    pub placeholder: &'static str,
+    pub value: String,
+}
+
+/// A small modal form of single-line text fields
+pub struct TaskForm {
+    title: &'static str,
+    submit_label: &'static str,
+    fields: Vec<FormField>,
+    focused: usize,
+    error: Option<String>,
+}
+
+impl TaskForm {
+    pub fn new(
+        title: &'static str,
+        submit_label: &'static str,
+        fields: &[(&'static str, &'static str)],
+    ) -> Self {
+        Self {
+            title,
+            submit_label,
+            fields: fields
+                .iter()
+                .map(|(label, placeholder)| FormField {
+                    label,
+                    placeholder,
+                    value: String::new(),
+                })
+                .collect(),
+            focused: 0,
+            error: None,
+        }
+    }
+
+    pub fn new_task() -> Self {
+        Self::new(
+            "New Task",
+            "create",
+            &[
+                ("Name", "What needs to be done?"),
+                ("Due", "tomorrow, fri, +3d or YYYY-MM-DD (optional)"),
+            ],
+        )
+    }
+
+    pub fn new_subtask() -> Self {
+        Self::new(
+            "New Subtask",
+            "create",
+            &[
+                ("Name", "What needs to be done?"),
+                ("Due", "tomorrow, fri, +3d or YYYY-MM-DD (optional)"),
+            ],
+        )
+    }
+
+    pub fn due_date() -> Self {
+        Self::new(
+            "Set Due Date",
+            "set",
+            &[("Due", "tomorrow, fri, +3d or YYYY-MM-DD (empty clears)")],
+        )
+    }
+
+    pub fn workspace_search() -> Self {
+        Self::new(
+            "Search Workspace",
+            "search",
+            &[(
+                "Text",
+                "words in task names and descriptions (empty clears)",
+            )],
+        )
+    }
+
+    /// Current value of the field with the given label
+    pub fn value(&self, label: &str) -> &str {
+        self.fields
+            .iter()
+            .find(|f| f.label == label)
+            .map(|f| f.value.trim())
+            .unwrap_or_default()
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        if let Some(field) = self.fields.get_mut(self.focused) {
+            field.value.push(c);
+        }
+        self.error = None;
+    }
+
+    pub fn delete_char(&mut self) {
+        if let Some(field) = self.fields.get_mut(self.focused) {
+            field.value.pop();
+        }
+        self.error = None;
+    }
+
+    pub fn next_field(&mut self) {
+        self.focused = (self.focused + 1) % self.fields.len().max(1);
+    }
+
+    pub fn previous_field(&mut self) {
+        let len = self.fields.len().max(1);
+        self.focused = (self.focused + len - 1) % len;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Height needed to render every field plus the status and help lines
+    pub fn height(&self) -> u16 {
+        self.fields.len() as u16 * 3 + 4
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(self.title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut constraints: Vec<Constraint> =
+            self.fields.iter().map(|_| Constraint::Length(3)).collect();
+        constraints.push(Constraint::Length(1)); // Error line
+        constraints.push(Constraint::Min(1)); // Help line
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(inner);
+
+        for (i, field) in self.fields.iter().enumerate() {
+            let is_focused = i == self.focused;
+            let border_style = if is_focused {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+
+            let (text, text_style) = if field.value.is_empty() {
+                (field.placeholder, Style::default().fg(Color::DarkGray))
+            } else {
+                (field.value.as_str(), Style::default())
+            };
+
+            let paragraph = Paragraph::new(text).style(text_style).block(
+                Block::default()
+                    .title(field.label)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(border_style),
+            );
+            frame.render_widget(paragraph, chunks[i]);
+
+            if is_focused {
+                let cursor_x = chunks[i].x + 1 + field.value.chars().count() as u16;
+                if cursor_x < chunks[i].x + chunks[i].width - 1 {
+                    frame.set_cursor_position(Position {
+                        x: cursor_x,
+                        y: chunks[i].y + 1,
+                    });
+                }
+            }
+        }
+
+        if let Some(error) = &self.error {
+            let paragraph = Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red));
+            frame.render_widget(paragraph, chunks[self.fields.len()]);
+        }
+
+        let help = format!(
+            "Tab: next field | Enter: {} | Esc: cancel",
+            self.submit_label
+        );
+        let paragraph = Paragraph::new(help).style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(paragraph, chunks[self.fields.len() + 1]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::ports::{ConfigError, RepositoryError};
+use crate::ports::{ConfigError, OutboxError, RepositoryError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -9,14 +9,34 @@ pub enum AppError {
     #[error("Configuration error: {0}")]
     Config(#[from] ConfigError),
 
+    #[error("Offline queue error: {0}")]
+    Outbox(#[from] OutboxError),
+
     #[error("Application error: {0}")]
     Application(String),
 
     #[error("Authentication required")]
     AuthenticationRequired,
 
-    #[error("Workspace not configured")]
+    #[error("No workspace selected; pass --workspace <ID> (see `asana-cli workspaces list`)")]
     WorkspaceNotConfigured,
 }
 
+impl AppError {
+    /// Whether Asana could not be reached at all, as opposed to refusing the request
+    pub fn is_network(&self) -> bool {
+        matches!(self, AppError::Repository(RepositoryError::Network(_)))
+    }
+
+    /// Whether Asana turned the request down for good: the resource is gone,
+    /// or a 4xx that sending it again won't change (401 and 429 map elsewhere)
+    pub fn is_refusal(&self) -> bool {
+        match self {
+            AppError::Repository(RepositoryError::NotFound(_)) => true,
+            AppError::Repository(RepositoryError::Api(message)) => message.starts_with("HTTP 4"),
+            _ => false,
+        }
+    }
+}
+
 pub type AppResult<T> = Result<T, AppError>;
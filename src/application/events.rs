@@ -0,0 +1,180 @@
+use crate::domain::{ProjectId, Task, TaskId};
+
+/// What a task list follows to stay current
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventFeed {
+    /// The signed-in user's My Tasks list in the current workspace
+    MyTasks,
+    Project(ProjectId),
+}
+
+/// A task list's place in its Asana event stream, passed back to
+/// [`super::StateManager::poll_events`] each time
+#[derive(Debug, Clone)]
+pub struct EventCursor {
+    pub feed: EventFeed,
+    // The gid events are asked for, looked up on the first poll for My Tasks
+    pub(super) resource: Option<String>,
+    pub(super) sync: Option<String>,
+}
+
+impl EventCursor {
+    pub fn new(feed: EventFeed) -> Self {
+        Self {
+            feed,
+            resource: None,
+            sync: None,
+        }
+    }
+}
+
+/// What changed since the previous poll; the caches already reflect it
+#[derive(Debug)]
+pub enum LiveUpdate {
+    Changed {
+        /// Tasks as they are now, with offline changes still queued applied
+        tasks: Vec<Task>,
+        deleted: Vec<TaskId>,
+        /// Tasks whose comments changed
+        commented: Vec<TaskId>,
+    },
+    /// Asana dropped events we never saw, so the list must be fetched again
+    Resync,
+}
+
+impl LiveUpdate {
+    /// Whether there is anything to show
+    pub fn is_empty(&self) -> bool {
+        match self {
+            LiveUpdate::Changed {
+                tasks,
+                deleted,
+                commented,
+            } => tasks.is_empty() && deleted.is_empty() && commented.is_empty(),
+            LiveUpdate::Resync => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::StateManager;
+    use crate::test_support::{self as fake, FakeResponse, Sandbox};
+    use serde_json::{json, Value};
+
+    fn in_project(mut task: Value, project: &str) -> Value {
+        task["projects"] = json!([{ "gid": project, "name": "Home" }]);
+        task
+    }
+
+    #[tokio::test]
+    async fn test_events_patch_cached_lists_and_expired_tokens_resync() {
+        let sandbox = Sandbox::new(vec![
+            in_project(fake::task("1", "Water plants"), "p1"),
+            in_project(fake::task("2", "File taxes"), "p1"),
+            in_project(fake::task("3", "Call plumber"), "p1"),
+        ])
+        .await;
+        let fake = &sandbox.fake;
+        let state_manager = sandbox.state_manager();
+        let filter = StateManager::project_task_filter(&"p1".into());
+        let elsewhere = StateManager::project_task_filter(&"p2".into());
+        for filter in [&filter, &elsewhere] {
+            state_manager
+                .get_tasks_with_filter(filter, true)
+                .await
+                .unwrap();
+        }
+        let mut cursor = EventCursor::new(EventFeed::Project("p1".into()));
+
+        // The first poll only picks up a sync token
+        fake.enqueue(
+            "GET",
+            "/events",
+            FakeResponse::json(412, json!({ "sync": "t1" })),
+        );
+        assert!(state_manager
+            .poll_events(&mut cursor)
+            .await
+            .unwrap()
+            .is_empty());
+
+        fake.on(
+            "GET",
+            "/tasks/2",
+            FakeResponse::data(in_project(fake::task("2", "File taxes early"), "p1")),
+        );
+        fake.enqueue(
+            "GET",
+            "/events",
+            FakeResponse::json(
+                200,
+                json!({
+                    "data": [
+                        { "action": "changed", "resource": { "gid": "2", "resource_type": "task" } },
+                        {
+                            "action": "added",
+                            "resource": { "gid": "s1", "resource_type": "story" },
+                            "parent": { "gid": "1", "resource_type": "task" }
+                        },
+                        { "action": "deleted", "resource": { "gid": "3", "resource_type": "task" } }
+                    ],
+                    "sync": "t2",
+                    "has_more": false
+                }),
+            ),
+        );
+        let LiveUpdate::Changed {
+            tasks,
+            deleted,
+            commented,
+        } = state_manager.poll_events(&mut cursor).await.unwrap()
+        else {
+            panic!("Expected changes, not a resync");
+        };
+        let names: Vec<_> = tasks.iter().map(|task| task.name.as_str()).collect();
+        assert_eq!(names, ["File taxes early", "Water plants"]);
+        assert_eq!(deleted, [TaskId::from("3")]);
+        assert_eq!(commented, [TaskId::from("1")]);
+        assert_eq!(fake.requests_to("GET", "/events")[1].query["sync"], "t1");
+
+        // The cached list shows the changes without being fetched again
+        let mut names: Vec<_> = state_manager
+            .get_tasks_with_filter(&filter, true)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|task| task.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["File taxes early", "Water plants"]);
+        assert_eq!(fake.requests_to("GET", "/tasks").len(), 2);
+
+        // Asana expired the token: start over with a fresh list, leaving
+        // listings the feed doesn't cover alone
+        fake.enqueue(
+            "GET",
+            "/events",
+            FakeResponse::json(412, json!({ "sync": "t3" })),
+        );
+        assert!(matches!(
+            state_manager.poll_events(&mut cursor).await.unwrap(),
+            LiveUpdate::Resync
+        ));
+        assert_eq!(cursor.sync.as_deref(), Some("t3"));
+        assert!(state_manager.cached_tasks(&filter).await.is_some());
+        for filter in [&filter, &elsewhere] {
+            state_manager
+                .get_tasks_with_filter(filter, true)
+                .await
+                .unwrap();
+        }
+        let refetched: Vec<_> = fake
+            .requests_to("GET", "/tasks")
+            .into_iter()
+            .map(|request| request.query["project"].clone())
+            .collect();
+        assert_eq!(refetched, ["p1", "p2", "p1"]);
+    }
+}
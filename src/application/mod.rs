@@ -1,7 +1,15 @@
 pub mod error;
+pub mod events;
+pub mod mutations;
+pub mod offline;
+pub mod prefetch;
 pub mod state_manager;
 pub mod task_service;
 
 pub use error::*;
+pub use events::*;
+pub use mutations::*;
+pub use offline::*;
+pub use prefetch::*;
 pub use state_manager::*;
 pub use task_service::*;
@@ -0,0 +1,238 @@
+use super::{AppError, StateManager};
+use crate::domain::{Task, TaskUpdate};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Task changes shown straight away and sent to Asana in the background,
+/// one at a time in the order they were made
+pub struct MutationPipeline {
+    requests: mpsc::UnboundedSender<Mutation>,
+    outcomes: mpsc::UnboundedReceiver<MutationOutcome>,
+    // Sent but not yet answered, oldest first, as the worker handles them
+    in_flight: VecDeque<Mutation>,
+}
+
+#[derive(Debug, Clone)]
+struct Mutation {
+    before: Task,
+    update: TaskUpdate,
+}
+
+/// How a change ended
+#[derive(Debug)]
+pub enum MutationOutcome {
+    /// Asana took the change; the task as it has it now, with any changes
+    /// still on their way applied
+    Confirmed(Task),
+    /// Asana refused the change; undo it with [`TaskUpdate::revert`] against `before`
+    Rejected {
+        before: Task,
+        update: TaskUpdate,
+        error: AppError,
+    },
+}
+
+impl MutationPipeline {
+    /// Start the worker that sends changes; needs a Tokio runtime
+    pub fn new(state_manager: Arc<StateManager>) -> Self {
+        let (requests, mut queue) = mpsc::unbounded_channel::<Mutation>();
+        let (results, outcomes) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Some(Mutation { before, update }) = queue.recv().await {
+                let outcome = match state_manager.update_task(&before.id, &update).await {
+                    Ok(task) => MutationOutcome::Confirmed(task),
+                    Err(error) => MutationOutcome::Rejected {
+                        before,
+                        update,
+                        error,
+                    },
+                };
+                if results.send(outcome).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            requests,
+            outcomes,
+            in_flight: VecDeque::new(),
+        }
+    }
+
+    /// Queue `update` to `task` and return the task to show until Asana answers
+    pub fn submit(&mut self, task: &Task, update: TaskUpdate) -> Task {
+        let mut shown = task.clone();
+        update.apply_to(&mut shown);
+
+        let mutation = Mutation {
+            before: task.clone(),
+            update,
+        };
+        if self.requests.send(mutation.clone()).is_ok() {
+            self.in_flight.push_back(mutation);
+        }
+        shown
+    }
+
+    /// Whether any change is still waiting for Asana
+    pub fn is_busy(&self) -> bool {
+        !self.in_flight.is_empty()
+    }
+
+    /// Outcomes of the changes Asana has answered since the last call
+    pub fn poll(&mut self) -> Vec<MutationOutcome> {
+        let mut finished = Vec::new();
+        while let Ok(mut outcome) = self.outcomes.try_recv() {
+            self.in_flight.pop_front();
+
+            // Keep later changes to the same task showing until they are answered too
+            if let MutationOutcome::Confirmed(task) = &mut outcome {
+                let id = task.id.clone();
+                for mutation in self.in_flight.iter().filter(|m| m.before.id == id) {
+                    mutation.update.apply_to(task);
+                }
+            }
+            finished.push(outcome);
+        }
+        finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TaskFilter;
+    use crate::test_support::{self as fake, FakeResponse, Sandbox};
+    use serde_json::json;
+
+    async fn next_outcome(pipeline: &mut MutationPipeline) -> MutationOutcome {
+        loop {
+            if let Some(outcome) = pipeline.poll().pop() {
+                return outcome;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_changes_show_at_once_and_roll_back_when_rejected() {
+        let sandbox = Sandbox::new(vec![
+            fake::task("1", "Water plants"),
+            fake::task("2", "File taxes"),
+        ])
+        .await;
+        let fake = &sandbox.fake;
+        let mut done = fake::task("1", "Water plants");
+        done["completed"] = json!(true);
+        fake.on("PUT", "/tasks/1", FakeResponse::data(done));
+        fake.on("PUT", "/tasks/2", FakeResponse::error(403, "Forbidden"));
+
+        let state_manager = Arc::new(sandbox.state_manager());
+        let filter = TaskFilter {
+            limit: None,
+            ..Default::default()
+        };
+        let tasks = state_manager
+            .get_tasks_with_filter(&filter, true)
+            .await
+            .unwrap();
+        let mut pipeline = MutationPipeline::new(state_manager.clone());
+
+        let complete = TaskUpdate {
+            completed: Some(true),
+            ..Default::default()
+        };
+        assert!(pipeline.submit(&tasks[0], complete.clone()).completed);
+        assert!(pipeline.is_busy());
+        assert!(matches!(
+            next_outcome(&mut pipeline).await,
+            MutationOutcome::Confirmed(task) if task.completed
+        ));
+
+        let mut shown = pipeline.submit(&tasks[1], complete);
+        let MutationOutcome::Rejected { before, update, .. } = next_outcome(&mut pipeline).await
+        else {
+            panic!("Expected the change to task 2 to be rejected");
+        };
+        update.revert(&mut shown, &before);
+        assert!(!shown.completed);
+        assert!(!pipeline.is_busy());
+
+        // Only the confirmed change reaches the cached list, without a refetch
+        let tasks = state_manager
+            .get_tasks_with_filter(&filter, true)
+            .await
+            .unwrap();
+        let names: Vec<_> = tasks.iter().map(|task| task.name.as_str()).collect();
+        assert_eq!(names, ["File taxes"]);
+        assert_eq!(fake.requests_to("GET", "/tasks").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_listings_cached_by_other_runs_are_patched_too() {
+        let sandbox = Sandbox::new(vec![
+            fake::task("1", "Water plants"),
+            fake::task("2", "File taxes"),
+        ])
+        .await;
+        let fake = &sandbox.fake;
+        let mut done = fake::task("1", "Water plants");
+        done["completed"] = json!(true);
+        fake.on("PUT", "/tasks/1", FakeResponse::data(done));
+
+        let all = TaskFilter {
+            limit: None,
+            ..Default::default()
+        };
+        let first_ten = TaskFilter {
+            limit: Some(10),
+            ..Default::default()
+        };
+        let completed = TaskFilter {
+            completed: Some(true),
+            limit: None,
+            ..Default::default()
+        };
+        let next_run = sandbox.state_manager();
+        // Another run cached listings this one never asks for
+        let other_run = sandbox.state_manager();
+        for filter in [&first_ten, &completed] {
+            other_run.get_tasks_with_filter(filter, true).await.unwrap();
+        }
+
+        let state_manager = Arc::new(sandbox.state_manager());
+        let tasks = state_manager
+            .get_tasks_with_filter(&all, true)
+            .await
+            .unwrap();
+        let mut pipeline = MutationPipeline::new(state_manager.clone());
+        let complete = TaskUpdate {
+            completed: Some(true),
+            ..Default::default()
+        };
+        pipeline.submit(&tasks[0], complete);
+        assert!(matches!(
+            next_outcome(&mut pipeline).await,
+            MutationOutcome::Confirmed(_)
+        ));
+
+        for (filter, expected) in [
+            (&all, "File taxes"),
+            (&first_ten, "File taxes"),
+            (&completed, "Water plants"),
+        ] {
+            let names: Vec<_> = next_run
+                .cached_tasks(filter)
+                .await
+                .unwrap()
+                .items
+                .into_iter()
+                .map(|task| task.name)
+                .collect();
+            assert_eq!(names, [expected]);
+        }
+    }
+}
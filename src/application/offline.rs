@@ -0,0 +1,357 @@
+use super::AppResult;
+use crate::domain::*;
+use crate::ports::{Cache, Outbox};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// What keeps the app usable without Asana: the last-known copy of every task
+/// and comment thread seen, and the outbox of writes waiting to be sent
+pub struct OfflineStore {
+    outbox: Arc<dyn Outbox>,
+    comments: Arc<dyn Cache<TaskId, Vec<Comment>>>,
+    tasks: DashMap<TaskId, Task>,
+    // The `modified_at` our own replayed changes left on each task, which
+    // later queued changes to it must not mistake for someone else's edit
+    sent: DashMap<TaskId, DateTime<Utc>>,
+    offline: AtomicBool,
+}
+
+/// What happened when queued changes were sent
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub sent: usize,
+    /// Changes dropped because the task changed in Asana since, or Asana refused them
+    pub conflicts: Vec<Conflict>,
+    /// Asana is still unreachable, so the rest stay queued
+    pub still_offline: bool,
+    /// Why Asana couldn't take the next change just now, e.g. rate limiting;
+    /// it and the rest stay queued for the next sync
+    pub held_back: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Conflict {
+    pub change: PendingChange,
+    pub task_name: String,
+    pub reason: String,
+}
+
+impl OfflineStore {
+    pub fn new(outbox: Arc<dyn Outbox>, comments: Arc<dyn Cache<TaskId, Vec<Comment>>>) -> Self {
+        Self {
+            outbox,
+            comments,
+            tasks: DashMap::new(),
+            sent: DashMap::new(),
+            offline: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether the last request failed to reach Asana
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::Relaxed);
+    }
+
+    pub(super) fn remember_tasks(&self, tasks: &[Task]) {
+        for task in tasks {
+            self.tasks.insert(task.id.clone(), task.clone());
+        }
+    }
+
+    /// Remember tasks from a cached listing, without displacing fresher copies
+    pub(super) fn remember_stale_tasks(&self, tasks: &[Task]) {
+        for task in tasks {
+            self.tasks
+                .entry(task.id.clone())
+                .or_insert_with(|| task.clone());
+        }
+    }
+
+    /// Note a task as a replayed change left it
+    pub(super) fn record_sent(&self, task: &Task) {
+        self.sent.insert(task.id.clone(), task.modified_at);
+        self.remember_tasks(std::slice::from_ref(task));
+    }
+
+    /// Whether `current` is still the task `change` was made against, give or
+    /// take changes of ours sent since
+    pub(super) fn is_base_of(&self, change: &PendingChange, current: &Task) -> bool {
+        current.modified_at == change.base_modified_at
+            || self
+                .sent
+                .get(&current.id)
+                .is_some_and(|sent| *sent == current.modified_at)
+    }
+
+    pub(super) async fn remember_comments(&self, task_id: &TaskId, comments: &[Comment]) {
+        self.comments
+            .insert(task_id.clone(), comments.to_vec())
+            .await;
+    }
+
+    pub async fn pending(&self) -> AppResult<Vec<PendingChange>> {
+        Ok(self.outbox.pending().await?)
+    }
+
+    pub(super) async fn forget(&self, change: &PendingChange) -> AppResult<()> {
+        Ok(self.outbox.remove(&change.id).await?)
+    }
+
+    /// The last-known copy of a task as Asana had it, without queued changes
+    pub(super) fn server_copy(&self, id: &TaskId) -> Option<Task> {
+        self.tasks.get(id).map(|task| task.clone())
+    }
+
+    /// The last-known copy of a task with queued changes applied
+    pub(super) async fn task(&self, id: &TaskId) -> AppResult<Option<Task>> {
+        let Some(mut task) = self.server_copy(id) else {
+            return Ok(None);
+        };
+        self.apply_pending(std::slice::from_mut(&mut task)).await?;
+        Ok(Some(task))
+    }
+
+    /// Show queued changes on tasks fetched (or cached) before they were made
+    pub(super) async fn apply_pending(&self, tasks: &mut [Task]) -> AppResult<()> {
+        let pending = self.pending().await?;
+        for task in tasks {
+            let id = task.id.clone();
+            for change in pending.iter().filter(|change| change.task == id) {
+                change.change.apply_to(task);
+            }
+        }
+        Ok(())
+    }
+
+    /// The last comments seen on a task followed by any queued ones from `me`
+    pub(super) async fn comments(
+        &self,
+        task_id: &TaskId,
+        me: Option<User>,
+    ) -> AppResult<Option<Vec<Comment>>> {
+        let Some(mut comments) = self.comments.get(task_id).await else {
+            return Ok(None);
+        };
+
+        for change in self.pending().await? {
+            if let OfflineChange::Comment { text } = &change.change {
+                if &change.task == task_id {
+                    comments.push(queued_comment(&change, text, me.clone()));
+                }
+            }
+        }
+        Ok(Some(comments))
+    }
+
+    /// Queue `changes` to `task` and return it as it will look once they are sent
+    pub(super) async fn queue(&self, task: &Task, changes: Vec<OfflineChange>) -> AppResult<Task> {
+        let mut updated = self.task(&task.id).await?.unwrap_or_else(|| task.clone());
+
+        for change in changes {
+            change.apply_to(&mut updated);
+            self.outbox.push(PendingChange::new(task, change)).await?;
+        }
+        Ok(updated)
+    }
+
+    /// Queue a comment and return it as it will be shown until it is sent
+    pub(super) async fn queue_comment(
+        &self,
+        task: &Task,
+        text: &str,
+        me: Option<User>,
+    ) -> AppResult<Comment> {
+        let change = PendingChange::new(
+            task,
+            OfflineChange::Comment {
+                text: text.to_string(),
+            },
+        );
+        let comment = queued_comment(&change, text, me);
+        self.outbox.push(change).await?;
+        Ok(comment)
+    }
+}
+
+/// A comment that exists only in the outbox so far
+fn queued_comment(change: &PendingChange, text: &str, author: Option<User>) -> Comment {
+    Comment {
+        id: CommentId(format!("queued-{}", change.id)),
+        text: Some(text.to_string()),
+        author,
+        created_at: change.queued_at,
+        task_id: change.task.clone(),
+        story_type: Some("comment".to_string()),
+        resource_subtype: Some("comment_added".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::cache::MokaCacheAdapter;
+    use crate::adapters::outbox::FileOutbox;
+    use crate::application::StateManager;
+    use crate::test_support::{self as fake, state_manager, FakeResponse, Sandbox};
+    use serde_json::json;
+    use std::path::Path;
+
+    /// A TUI-style state manager, with its list cache and outbox under `dir`
+    /// so several can share them like successive runs
+    fn offline_manager(base_url: &str, dir: &Path) -> StateManager {
+        state_manager(base_url, dir).with_offline_store(OfflineStore::new(
+            Arc::new(FileOutbox::new(dir.join("outbox.json"))),
+            Arc::new(MokaCacheAdapter::new(300, 100)),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_offline_changes_queue_and_replay_with_conflicts() {
+        let sandbox = Sandbox::new(vec![
+            fake::task("1", "Water plants"),
+            fake::task("2", "File taxes"),
+        ])
+        .await;
+        let (fake, dir) = (&sandbox.fake, sandbox.dir());
+        let filter = TaskFilter {
+            completed: Some(false),
+            limit: None,
+            ..Default::default()
+        };
+
+        // Seen once online, then nothing answers
+        offline_manager(&fake.base_url(), dir)
+            .get_tasks_with_filter(&filter, false)
+            .await
+            .unwrap();
+        let offline = offline_manager("http://127.0.0.1:1", dir);
+        let tasks = offline.get_tasks_with_filter(&filter, false).await.unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert!(offline.is_offline());
+
+        let complete = TaskUpdate {
+            completed: Some(true),
+            ..Default::default()
+        };
+        let completed = offline
+            .update_task(&TaskId::from("1"), &complete)
+            .await
+            .unwrap();
+        assert!(completed.completed);
+        let rename = TaskUpdate {
+            name: Some("File taxes early".to_string()),
+            ..Default::default()
+        };
+        offline
+            .update_task(&TaskId::from("2"), &rename)
+            .await
+            .unwrap();
+        let comment = offline
+            .create_comment(&TaskId::from("1"), "Done on the train")
+            .await
+            .unwrap();
+        assert!(comment.id.0.starts_with("queued-"));
+
+        let tasks = offline.get_tasks_with_filter(&filter, false).await.unwrap();
+        let names: Vec<_> = tasks.iter().map(|task| task.name.as_str()).collect();
+        assert_eq!(names, ["File taxes early"]);
+
+        // Back online, someone else has edited task 2 in the meantime
+        let mut edited = fake::task("2", "File taxes");
+        edited["modified_at"] = json!("2025-01-05T08:00:00.000Z");
+        fake.on("GET", "/tasks/2", FakeResponse::data(edited));
+        let mut done = fake::task("1", "Water plants");
+        done["completed"] = json!(true);
+        fake.on("PUT", "/tasks/1", FakeResponse::data(done));
+        fake.on(
+            "POST",
+            "/tasks/1/stories",
+            FakeResponse::data(fake::story("s1", "Done on the train")),
+        );
+
+        let online = offline_manager(&fake.base_url(), dir);
+        let report = online.sync_offline_changes().await.unwrap();
+
+        assert_eq!(report.sent, 2);
+        assert!(!report.still_offline);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].task_name, "File taxes");
+        assert_eq!(
+            fake.requests_to("PUT", "/tasks/1")[0]
+                .body
+                .as_ref()
+                .unwrap()["data"]["completed"],
+            true
+        );
+        assert!(fake.requests_to("PUT", "/tasks/2").is_empty());
+        assert_eq!(fake.requests_to("POST", "/tasks/1/stories").len(), 1);
+        assert!(online.pending_changes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replay_keeps_changes_queued_when_asana_is_busy() {
+        let sandbox = Sandbox::new(vec![
+            fake::task("1", "Water plants"),
+            fake::task("2", "File taxes"),
+        ])
+        .await;
+        let (fake, dir) = (&sandbox.fake, sandbox.dir());
+        let filter = TaskFilter {
+            completed: Some(false),
+            limit: None,
+            ..Default::default()
+        };
+        offline_manager(&fake.base_url(), dir)
+            .get_tasks_with_filter(&filter, false)
+            .await
+            .unwrap();
+
+        let offline = offline_manager("http://127.0.0.1:1", dir);
+        offline.get_tasks_with_filter(&filter, false).await.unwrap();
+        let complete = TaskUpdate {
+            completed: Some(true),
+            ..Default::default()
+        };
+        for gid in ["1", "2"] {
+            offline
+                .update_task(&TaskId::from(gid), &complete)
+                .await
+                .unwrap();
+        }
+
+        let mut done = fake::task("1", "Water plants");
+        done["completed"] = json!(true);
+        fake.on("PUT", "/tasks/1", FakeResponse::data(done));
+        fake.enqueue(
+            "PUT",
+            "/tasks/1",
+            FakeResponse::error(503, "Service Unavailable"),
+        );
+        fake.enqueue("PUT", "/tasks/1", FakeResponse::rate_limited(30));
+        let online = offline_manager(&fake.base_url(), dir);
+
+        for _ in 0..2 {
+            let report = online.sync_offline_changes().await.unwrap();
+            assert_eq!(report.sent, 0);
+            assert!(report.conflicts.is_empty());
+            assert!(!report.still_offline);
+            assert!(report.held_back.is_some());
+            assert_eq!(online.pending_changes().await.unwrap().len(), 2);
+        }
+        assert!(fake.requests_to("PUT", "/tasks/2").is_empty());
+
+        // Asana takes the change once it has recovered; task 2 follows it
+        let mut done = fake::task("2", "File taxes");
+        done["completed"] = json!(true);
+        fake.on("PUT", "/tasks/2", FakeResponse::data(done));
+        let report = online.sync_offline_changes().await.unwrap();
+        assert_eq!(report.sent, 2);
+        assert!(online.pending_changes().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use super::{AppError, TaskService};
+use crate::domain::TaskId;
+use crate::ports::RepositoryError;
+use dashmap::{DashMap, DashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+/// Tasks fetched ahead at once, leaving most of Asana's rate limit to what
+/// the user actually opens
+const PREFETCH_CONCURRENCY: usize = 3;
+/// How long a prefetched task counts as ready to show without a wait
+const WARM_FOR: Duration = Duration::from_secs(60);
+
+/// Fetches tasks and their comments into [`TaskService`]'s caches before the
+/// user opens them, a few at a time, backing off when Asana rate limits us
+pub struct Prefetcher {
+    task_service: Arc<TaskService>,
+    permits: Arc<Semaphore>,
+    state: Arc<PrefetchState>,
+    // Tasks still waiting for a permit; replaced whenever the cursor moves
+    batch: Mutex<Option<JoinHandle<()>>>,
+}
+
+#[derive(Default)]
+struct PrefetchState {
+    in_flight: DashSet<TaskId>,
+    warmed: DashMap<TaskId, Instant>,
+    paused_until: Mutex<Option<Instant>>,
+}
+
+impl PrefetchState {
+    fn is_warm(&self, id: &TaskId) -> bool {
+        self.warmed
+            .get(id)
+            .is_some_and(|at| at.elapsed() < WARM_FOR)
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused_until
+            .lock()
+            .unwrap()
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    fn pause_for(&self, wait: Duration) {
+        *self.paused_until.lock().unwrap() = Some(Instant::now() + wait);
+    }
+}
+
+impl Prefetcher {
+    pub fn new(task_service: Arc<TaskService>) -> Self {
+        Self {
+            task_service,
+            permits: Arc::new(Semaphore::new(PREFETCH_CONCURRENCY)),
+            state: Arc::default(),
+            batch: Mutex::new(None),
+        }
+    }
+
+    /// Fetch `ids`, nearest first, in the background; needs a Tokio runtime.
+    /// Tasks from the previous call not yet started are dropped, while those
+    /// already on their way finish and fill the caches.
+    pub fn prefetch(&self, ids: Vec<TaskId>) {
+        let mut batch = self.batch.lock().unwrap();
+        if let Some(previous) = batch.take() {
+            previous.abort();
+        }
+        if self.state.is_paused() {
+            return;
+        }
+
+        self.state.warmed.retain(|_, at| at.elapsed() < WARM_FOR);
+        let ids: Vec<_> = ids
+            .into_iter()
+            .filter(|id| !self.state.is_warm(id) && !self.state.in_flight.contains(id))
+            .collect();
+        if ids.is_empty() {
+            return;
+        }
+
+        let task_service = self.task_service.clone();
+        let permits = self.permits.clone();
+        let state = self.state.clone();
+        *batch = Some(tokio::spawn(async move {
+            for id in ids {
+                let Ok(permit) = permits.clone().acquire_owned().await else {
+                    return;
+                };
+                if state.is_paused() {
+                    return;
+                }
+                if !state.in_flight.insert(id.clone()) {
+                    continue;
+                }
+
+                let task_service = task_service.clone();
+                let state = state.clone();
+                tokio::spawn(async move {
+                    let (task, comments) = tokio::join!(
+                        task_service.get_task(&id, true),
+                        task_service.get_task_comments(&id, true)
+                    );
+                    state.in_flight.remove(&id);
+
+                    let errors: Vec<_> =
+                        [task.err(), comments.err()].into_iter().flatten().collect();
+                    for error in &errors {
+                        if let AppError::Repository(RepositoryError::RateLimit(secs)) = error {
+                            state.pause_for(Duration::from_secs((*secs).max(1)));
+                        } else {
+                            tracing::debug!("Failed to prefetch task {}: {}", id, error);
+                        }
+                    }
+                    if errors.is_empty() {
+                        state.warmed.insert(id, Instant::now());
+                    }
+                    drop(permit);
+                });
+            }
+        }));
+    }
+
+    /// Whether `id` was prefetched recently enough to show straight from the caches
+    pub fn is_warm(&self, id: &TaskId) -> bool {
+        self.state.is_warm(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self as fake, FakeResponse, Sandbox};
+
+    fn ids(gids: &[&str]) -> Vec<TaskId> {
+        gids.iter().map(|gid| TaskId::from(*gid)).collect()
+    }
+
+    async fn wait_until(done: impl Fn() -> bool) {
+        for _ in 0..200 {
+            if done() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("Prefetching didn't finish in time");
+    }
+
+    #[tokio::test]
+    async fn test_neighbours_are_fetched_once_and_then_served_from_cache() {
+        let sandbox = Sandbox::new(vec![
+            fake::task("1", "Water plants"),
+            fake::task("2", "File taxes"),
+            fake::task("3", "Call plumber"),
+        ])
+        .await;
+        let fake = &sandbox.fake;
+        for gid in ["1", "2", "3"] {
+            fake.on_list(
+                &format!("/tasks/{gid}/stories"),
+                vec![fake::story(&format!("s{gid}"), "On it")],
+            );
+        }
+        let state_manager = sandbox.state_manager();
+
+        state_manager.prefetch_tasks(ids(&["2", "3"]));
+        wait_until(|| state_manager.is_prefetched(&TaskId::from("3"))).await;
+        state_manager.prefetch_tasks(ids(&["1", "2", "3"]));
+        wait_until(|| state_manager.is_prefetched(&TaskId::from("1"))).await;
+
+        for gid in ["1", "2", "3"] {
+            assert_eq!(fake.requests_to("GET", &format!("/tasks/{gid}")).len(), 1);
+            assert_eq!(
+                fake.requests_to("GET", &format!("/tasks/{gid}/stories"))
+                    .len(),
+                1
+            );
+        }
+
+        // Opening a prefetched task needs no further requests
+        let task = state_manager.get_task(&TaskId::from("2")).await.unwrap();
+        let comments = state_manager
+            .get_task_comments(&TaskId::from("2"))
+            .await
+            .unwrap();
+        assert_eq!(task.name, "File taxes");
+        assert_eq!(comments.len(), 1);
+        assert_eq!(fake.requests_to("GET", "/tasks/2").len(), 1);
+        assert_eq!(fake.requests_to("GET", "/tasks/2/stories").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiting_pauses_prefetching() {
+        let sandbox = Sandbox::new(vec![
+            fake::task("1", "Water plants"),
+            fake::task("2", "File taxes"),
+        ])
+        .await;
+        let fake = &sandbox.fake;
+        fake.on("GET", "/tasks/1/stories", FakeResponse::rate_limited(30));
+        fake.on_list("/tasks/2/stories", Vec::new());
+        let state_manager = sandbox.state_manager();
+
+        state_manager.prefetch_tasks(ids(&["1"]));
+        wait_until(|| fake.requests_to("GET", "/tasks/1/stories").len() == 1).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        state_manager.prefetch_tasks(ids(&["2"]));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!state_manager.is_prefetched(&TaskId::from("1")));
+        assert!(fake.requests_to("GET", "/tasks/2").is_empty());
+    }
+}
@@ -1,23 +1,51 @@
-use super::{AppError, AppResult, TaskService};
+use super::{
+    AppError, AppResult, Conflict, EventCursor, EventFeed, LiveUpdate, OfflineStore, Prefetcher,
+    SyncReport, TaskService,
+};
 use crate::domain::*;
-use crate::ports::{ConfigStore, WorkspaceRepository};
+use crate::ports::{
+    Cache, ConfigStore, EventRepository, ProjectRepository, RepositoryError, WorkspaceRepository,
+};
 use chrono::{DateTime, Utc};
-use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
 use std::sync::Arc;
 
-#[derive(Debug, Clone)]
+/// Key of the signed-in user in the session cache
+const CURRENT_USER_KEY: &str = "current_user";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedList<T> {
     pub items: Vec<T>,
     pub fetched_at: DateTime<Utc>,
+    /// What the listing holds, so any process can patch a changed task into it
+    pub filter: TaskFilter,
+}
+
+/// What became of a cached listing looked over by [`StateManager::edit_cached_lists`]
+enum ListEdit {
+    Unchanged,
+    Changed,
+    Dropped,
 }
 
 pub struct StateManager {
     task_service: Arc<TaskService>,
     workspace_repo: Arc<dyn WorkspaceRepository>,
+    project_repo: Arc<dyn ProjectRepository>,
+    event_repo: Arc<dyn EventRepository>,
     config_store: Arc<dyn ConfigStore>,
 
-    // List caches
-    task_list_cache: DashMap<String, CachedList<Task>>,
+    // List caches, keyed by user and workspace so they can outlive the process
+    task_list_cache: Arc<dyn Cache<String, CachedList<Task>>>,
+    // Who was signed in last time, to show cached lists before Asana answers
+    session_cache: Arc<dyn Cache<String, User>>,
+    // Held while cached listings are read, changed and written back, so a
+    // listing being patched isn't overwritten halfway through
+    list_lock: tokio::sync::Mutex<()>,
+    // Set for the TUI only; the CLI should fail loudly rather than queue
+    offline: Option<OfflineStore>,
+    prefetcher: Prefetcher,
 
     // Application state
     current_workspace: tokio::sync::RwLock<Option<WorkspaceId>>,
@@ -28,29 +56,97 @@ impl StateManager {
     pub fn new(
         task_service: Arc<TaskService>,
         workspace_repo: Arc<dyn WorkspaceRepository>,
+        project_repo: Arc<dyn ProjectRepository>,
+        event_repo: Arc<dyn EventRepository>,
         config_store: Arc<dyn ConfigStore>,
+        task_list_cache: Arc<dyn Cache<String, CachedList<Task>>>,
+        session_cache: Arc<dyn Cache<String, User>>,
     ) -> Self {
         Self {
+            prefetcher: Prefetcher::new(task_service.clone()),
             task_service,
             workspace_repo,
+            project_repo,
+            event_repo,
             config_store,
-            task_list_cache: DashMap::new(),
+            task_list_cache,
+            session_cache,
+            list_lock: tokio::sync::Mutex::new(()),
+            offline: None,
             current_workspace: tokio::sync::RwLock::new(None),
             current_user: tokio::sync::RwLock::new(None),
         }
     }
 
+    /// Keep working without Asana: serve reads from the last copies seen and
+    /// queue writes in `store` until [`Self::sync_offline_changes`] can send them
+    pub fn with_offline_store(mut self, store: OfflineStore) -> Self {
+        self.offline = Some(store);
+        self
+    }
+
+    /// Whether the last request to Asana failed to reach it
+    pub fn is_offline(&self) -> bool {
+        self.offline
+            .as_ref()
+            .is_some_and(|offline| offline.is_offline())
+    }
+
+    /// Changes made offline that Asana doesn't have yet, oldest first
+    pub async fn pending_changes(&self) -> AppResult<Vec<PendingChange>> {
+        match &self.offline {
+            Some(offline) => offline.pending().await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Record whether a request reached Asana; any answer, even a refusal, means it did
+    fn note_reachability<T>(&self, result: &AppResult<T>) {
+        if let Some(offline) = &self.offline {
+            offline.set_offline(matches!(result, Err(e) if e.is_network()));
+        }
+    }
+
+    /// Make a request to Asana, failing fast while offline; only the offline
+    /// sync waits on the network then, and it notices when Asana is back
+    async fn reach<T>(&self, request: impl Future<Output = AppResult<T>>) -> AppResult<T> {
+        if self.is_offline() {
+            return Err(RepositoryError::Network("offline".to_string()).into());
+        }
+        let result = request.await;
+        self.note_reachability(&result);
+        result
+    }
+
+    /// Whether writes to `task` must queue behind changes already waiting for it
+    async fn has_pending_changes(&self, task: &TaskId) -> AppResult<bool> {
+        Ok(self
+            .pending_changes()
+            .await?
+            .iter()
+            .any(|change| &change.task == task))
+    }
+
     pub async fn initialize(&self) -> AppResult<()> {
         // Load configuration
         let mut config = self.config_store.load_config().await?;
 
         // Load current user first to verify authentication
-        match self.workspace_repo.get_current_user().await {
+        let user = self
+            .workspace_repo
+            .get_current_user()
+            .await
+            .map_err(AppError::from);
+        self.note_reachability(&user);
+        match user {
             Ok(user) => {
+                self.session_cache
+                    .insert(CURRENT_USER_KEY.to_string(), user.clone())
+                    .await;
                 *self.current_user.write().await = Some(user);
             }
             Err(e) => {
-                return Err(e.into());
+                return Err(e);
             }
         }
 
@@ -82,7 +178,7 @@ impl StateManager {
                             "No workspaces found for this account".to_string(),
                         ));
                     } else {
-                        // Multiple workspaces - user needs to choose
+                        // Multiple workspaces - leave it to the user to pick one
                         tracing::info!(
                             "Multiple workspaces found ({}), user needs to select one",
                             workspaces.len()
@@ -90,9 +186,6 @@ impl StateManager {
                         for workspace in &workspaces {
                             tracing::info!("  - {} ({})", workspace.name, workspace.id);
                         }
-                        return Err(crate::application::AppError::Application(
-                            format!("Multiple workspaces found ({}). Please specify one with --workspace <ID>", workspaces.len())
-                        ));
                     }
                 }
                 Err(e) => {
@@ -105,6 +198,22 @@ impl StateManager {
         Ok(())
     }
 
+    /// Pick up the last session's user and workspace without touching the network,
+    /// so cached lists can be shown while [`Self::initialize`] runs. Returns whether
+    /// both were known.
+    pub async fn restore_session(&self) -> AppResult<bool> {
+        let config = self.config_store.load_config().await?;
+        let user = self.session_cache.get(&CURRENT_USER_KEY.to_string()).await;
+
+        let (Some(workspace), Some(user)) = (config.default_workspace, user) else {
+            return Ok(false);
+        };
+        *self.current_workspace.write().await = Some(workspace);
+        *self.current_user.write().await = Some(user);
+
+        Ok(true)
+    }
+
     pub async fn get_current_workspace(&self) -> Option<WorkspaceId> {
         self.current_workspace.read().await.clone()
     }
@@ -113,7 +222,98 @@ impl StateManager {
         self.current_user.read().await.clone()
     }
 
-    pub async fn get_tasks_for_current_workspace(&self, use_cache: bool) -> AppResult<Vec<Task>> {
+    pub async fn list_workspaces(&self) -> AppResult<Vec<Workspace>> {
+        Ok(self.workspace_repo.list_workspaces().await?)
+    }
+
+    /// Users in the current workspace, sorted by name
+    pub async fn list_users(&self) -> AppResult<Vec<User>> {
+        let workspace = self
+            .get_current_workspace()
+            .await
+            .ok_or(AppError::WorkspaceNotConfigured)?;
+
+        let mut users = self.workspace_repo.list_users(&workspace).await?;
+        users.sort_by_key(|u| u.name.to_lowercase());
+
+        Ok(users)
+    }
+
+    /// Make `workspace` current, dropping everything cached for the previous one
+    /// and remembering the choice for next time
+    pub async fn switch_workspace(&self, workspace: WorkspaceId) -> AppResult<()> {
+        *self.current_workspace.write().await = Some(workspace.clone());
+
+        // Cached lists are keyed by workspace, so only the per-task caches need dropping
+        self.task_service.clear_caches().await;
+
+        let mut config = self.config_store.load_config().await?;
+        config.default_workspace = Some(workspace);
+        self.config_store.save_config(&config).await?;
+
+        Ok(())
+    }
+
+    /// Projects in the current workspace, sorted by name
+    pub async fn list_projects(&self, include_archived: bool) -> AppResult<Vec<Project>> {
+        let workspace = self
+            .get_current_workspace()
+            .await
+            .ok_or(AppError::WorkspaceNotConfigured)?;
+
+        let mut projects = self
+            .project_repo
+            .list_projects(&workspace, include_archived)
+            .await?;
+        projects.sort_by_key(|p| p.name.to_lowercase());
+
+        Ok(projects)
+    }
+
+    pub async fn get_project(&self, id: &ProjectId) -> AppResult<Project> {
+        Ok(self.project_repo.get_project(id).await?)
+    }
+
+    pub async fn list_sections(&self, project: &ProjectId) -> AppResult<Vec<Section>> {
+        Ok(self.project_repo.list_sections(project).await?)
+    }
+
+    /// Move a task into `section`, adding it to the section's project if needed
+    pub async fn move_task_to_section(&self, task: &TaskId, section: &SectionId) -> AppResult<()> {
+        self.project_repo.add_task_to_section(section, task).await?;
+
+        // Cached copies carry the old memberships, and maybe not the new project
+        self.task_service.invalidate_task(task).await;
+        match self.task_service.get_task(task, false).await {
+            Ok(moved) => self.patch_cached_lists(&moved).await,
+            // Without the task as it is now, the listings it was in are all in doubt
+            Err(_) => {
+                self.edit_cached_lists(|cached| {
+                    if cached.items.iter().any(|item| &item.id == task) {
+                        ListEdit::Dropped
+                    } else {
+                        ListEdit::Unchanged
+                    }
+                })
+                .await
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The filter behind a project's task list: its incomplete tasks
+    pub fn project_task_filter(project: &ProjectId) -> TaskFilter {
+        TaskFilter {
+            project: Some(project.clone()),
+            completed: Some(false),
+            limit: None,
+            ..Default::default()
+        }
+    }
+
+    /// The filter behind the main task list: my incomplete tasks in the current workspace
+    pub async fn default_task_filter(&self) -> AppResult<TaskFilter> {
         let workspace = self
             .get_current_workspace()
             .await
@@ -124,15 +324,85 @@ impl StateManager {
             .await
             .ok_or(AppError::Application("Current user not loaded".to_string()))?;
 
-        let filter = TaskFilter {
+        Ok(TaskFilter {
             workspace: Some(workspace),
             assignee: Some(current_user.id), // Use current user to satisfy API requirement
             completed: Some(false),          // Only incomplete tasks for main view
-            limit: Some(50),
+            limit: None,                     // Follow pagination to get every task
             ..Default::default()
-        };
+        })
+    }
 
-        self.get_tasks_with_filter(&filter, use_cache).await
+    /// Where the listing for `filter` is cached: under the current user and workspace
+    async fn list_cache_key(&self, filter: &TaskFilter) -> String {
+        let user = self.get_current_user().await.map(|u| u.id.0);
+        let workspace = self.get_current_workspace().await.map(|w| w.0);
+        format!(
+            "{}/{}/{}",
+            user.as_deref().unwrap_or("anonymous"),
+            workspace.as_deref().unwrap_or("none"),
+            filter.to_cache_key()
+        )
+    }
+
+    /// Bring cached listings in line with a changed or new task: take it out of
+    /// those it no longer matches and put it in those it now does, leaving the
+    /// rest alone. Search results can't be judged locally, so only get updated.
+    async fn patch_cached_lists(&self, task: &Task) {
+        let zone = Zone::current();
+        self.edit_cached_lists(|cached| {
+            let listed = cached.items.iter().any(|item| item.id == task.id);
+            let belongs = cached.filter.matches(task, zone)
+                && (listed || cached.filter.search_query.is_none());
+            if !listed && !belongs {
+                return ListEdit::Unchanged;
+            }
+
+            cached.items.retain(|item| item.id != task.id);
+            if belongs {
+                cached.items.push(task.clone());
+                cached.filter.sort.sort(&mut cached.items, zone);
+            }
+            ListEdit::Changed
+        })
+        .await;
+    }
+
+    /// Take a deleted task out of every cached listing
+    async fn drop_from_cached_lists(&self, id: &TaskId) {
+        self.edit_cached_lists(|cached| {
+            let listed = cached.items.len();
+            cached.items.retain(|item| &item.id != id);
+            if cached.items.len() == listed {
+                ListEdit::Unchanged
+            } else {
+                ListEdit::Changed
+            }
+        })
+        .await;
+    }
+
+    /// Run `edit` over every cached listing, including those other processes
+    /// fetched, and write back or drop the ones it changed
+    async fn edit_cached_lists(&self, edit: impl Fn(&mut CachedList<Task>) -> ListEdit) {
+        let _guard = self.list_lock.lock().await;
+        for key in self.task_list_cache.keys().await {
+            let Some(mut cached) = self.task_list_cache.get(&key).await else {
+                continue;
+            };
+            match edit(&mut cached) {
+                ListEdit::Unchanged => {}
+                ListEdit::Changed => self.task_list_cache.insert(key, cached).await,
+                ListEdit::Dropped => self.task_list_cache.remove(&key).await,
+            }
+        }
+    }
+
+    /// The last listing fetched for `filter`, however old, e.g. to show at startup
+    /// while a fresh one loads
+    pub async fn cached_tasks(&self, filter: &TaskFilter) -> Option<CachedList<Task>> {
+        let cache_key = self.list_cache_key(filter).await;
+        self.task_list_cache.get(&cache_key).await
     }
 
     pub async fn get_tasks_with_filter(
@@ -140,54 +410,462 @@ impl StateManager {
         filter: &TaskFilter,
         use_cache: bool,
     ) -> AppResult<Vec<Task>> {
-        let cache_key = filter.to_cache_key();
+        let cache_key = self.list_cache_key(filter).await;
 
-        if use_cache {
-            if let Some(cached) = self.task_list_cache.get(&cache_key) {
-                let age = Utc::now() - cached.fetched_at;
-                if age < chrono::Duration::minutes(5) {
-                    return Ok(cached.items.clone());
+        let cached = if use_cache {
+            self.task_list_cache
+                .get(&cache_key)
+                .await
+                .filter(|cached| Utc::now() - cached.fetched_at < chrono::Duration::minutes(5))
+        } else {
+            None
+        };
+        let (mut tasks, fetched) = match cached {
+            Some(cached) => (cached.items, false),
+            None => {
+                let result = self.reach(self.fetch_tasks(filter)).await;
+                match (result, &self.offline) {
+                    (Ok(tasks), offline) => {
+                        if let Some(offline) = offline {
+                            offline.remember_tasks(&tasks);
+                        }
+                        (tasks, true)
+                    }
+                    // Offline, the last listing fetched will do, however old
+                    (Err(e), Some(offline)) if e.is_network() => {
+                        let Some(cached) = self.task_list_cache.get(&cache_key).await else {
+                            return Err(e);
+                        };
+                        offline.remember_stale_tasks(&cached.items);
+                        (cached.items, false)
+                    }
+                    (Err(e), _) => return Err(e),
                 }
             }
+        };
+
+        // Queued changes go in before filtering, so a task completed offline
+        // leaves a list of incomplete tasks just as it would online
+        if let Some(offline) = &self.offline {
+            offline.apply_pending(&mut tasks).await?;
         }
 
-        let tasks = self.task_service.list_tasks(filter, false).await?;
+        let zone = Zone::current();
+        tasks.retain(|task| filter.matches(task, zone));
+        filter.sort.sort(&mut tasks, zone);
+        if let Some(limit) = filter.limit {
+            tasks.truncate(limit);
+        }
 
-        // Sort by due date (ascending, with None at the end)
-        let mut sorted_tasks = tasks;
-        sorted_tasks.sort_by(|a, b| match (a.due_date, b.due_date) {
-            (None, None) => std::cmp::Ordering::Equal,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (Some(a_due), Some(b_due)) => a_due.cmp(&b_due),
-        });
+        if fetched {
+            let _guard = self.list_lock.lock().await;
+            self.task_list_cache
+                .insert(
+                    cache_key,
+                    CachedList {
+                        items: tasks.clone(),
+                        fetched_at: Utc::now(),
+                        filter: filter.clone(),
+                    },
+                )
+                .await;
+        }
 
-        // Update cache
-        self.task_list_cache.insert(
-            cache_key.clone(),
-            CachedList {
-                items: sorted_tasks.clone(),
-                fetched_at: Utc::now(),
-            },
-        );
+        Ok(tasks)
+    }
 
-        Ok(sorted_tasks)
+    /// Fetch the tasks `filter` selects, before local filtering and sorting
+    async fn fetch_tasks(&self, filter: &TaskFilter) -> AppResult<Vec<Task>> {
+        // A text query goes to the search endpoint, which covers the whole workspace;
+        // anything the API can't filter on means fetching everything and trimming here
+        if filter.search_query.is_some() {
+            let workspace = match &filter.workspace {
+                Some(workspace) => workspace.clone(),
+                None => self
+                    .get_current_workspace()
+                    .await
+                    .ok_or(AppError::WorkspaceNotConfigured)?,
+            };
+            self.task_service
+                .search_tasks(&workspace, &WorkspaceSearch::from(filter))
+                .await
+        } else if filter.needs_local_filtering() {
+            let fetch = TaskFilter {
+                limit: None,
+                ..filter.clone()
+            };
+            self.task_service.list_tasks(&fetch, false).await
+        } else {
+            self.task_service.list_tasks(filter, false).await
+        }
+    }
+
+    /// Run `search` across the current workspace
+    pub async fn search_tasks(&self, search: &WorkspaceSearch) -> AppResult<Vec<Task>> {
+        let workspace = self
+            .get_current_workspace()
+            .await
+            .ok_or(AppError::WorkspaceNotConfigured)?;
+
+        self.task_service.search_tasks(&workspace, search).await
     }
 
     pub async fn get_task(&self, id: &TaskId) -> AppResult<Task> {
-        self.task_service.get_task(id, true).await
+        let result = self.reach(self.task_service.get_task(id, true)).await;
+        let Some(offline) = &self.offline else {
+            return result;
+        };
+
+        match result {
+            Ok(mut task) => {
+                offline.remember_tasks(std::slice::from_ref(&task));
+                offline
+                    .apply_pending(std::slice::from_mut(&mut task))
+                    .await?;
+                Ok(task)
+            }
+            Err(e) if e.is_network() => offline.task(id).await?.ok_or(e),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Warm the caches for tasks the user is likely to open next, nearest
+    /// first; returns at once, and gives up on anything still queued from
+    /// the previous call
+    pub fn prefetch_tasks(&self, ids: Vec<TaskId>) {
+        if !self.is_offline() {
+            self.prefetcher.prefetch(ids);
+        }
     }
 
-    pub async fn toggle_task_completion(&self, id: &TaskId) -> AppResult<Task> {
-        let result = self.task_service.toggle_task_completion(id).await;
+    /// Whether `id` and its comments were prefetched and can be shown without a wait
+    pub fn is_prefetched(&self, id: &TaskId) -> bool {
+        self.prefetcher.is_warm(id)
+    }
 
-        // Invalidate task list caches since completion status changed
-        self.task_list_cache.clear();
+    /// Send `update`, or queue it if Asana is unreachable or earlier offline
+    /// changes to the task are still waiting, so they all arrive in order
+    pub async fn update_task(&self, id: &TaskId, update: &TaskUpdate) -> AppResult<Task> {
+        if self.has_pending_changes(id).await? {
+            if let Some(task) = self.queue_update(id, update).await? {
+                return Ok(task);
+            }
+        }
 
-        result
+        let result = self.reach(self.task_service.update_task(id, update)).await;
+
+        match result {
+            Err(e) if e.is_network() => self.queue_update(id, update).await?.ok_or(e),
+            Err(e) => Err(e),
+            Ok(task) => {
+                self.patch_cached_lists(&task).await;
+                if let Some(offline) = &self.offline {
+                    offline.remember_tasks(std::slice::from_ref(&task));
+                }
+                Ok(task)
+            }
+        }
+    }
+
+    /// Queue `update` to send later; `None` if offline mode is off, the update
+    /// has parts that can't be queued, or the task has never been seen
+    async fn queue_update(&self, id: &TaskId, update: &TaskUpdate) -> AppResult<Option<Task>> {
+        let Some(offline) = &self.offline else {
+            return Ok(None);
+        };
+        let (Some(changes), Some(task)) =
+            (OfflineChange::from_update(update), offline.server_copy(id))
+        else {
+            return Ok(None);
+        };
+
+        offline.queue(&task, changes).await.map(Some)
+    }
+
+    pub async fn get_subtasks(&self, parent: &TaskId) -> AppResult<Vec<Task>> {
+        self.task_service.list_subtasks(parent).await
+    }
+
+    /// Create a task, defaulting to the current workspace when no project or parent is given
+    pub async fn create_task(&self, mut new_task: NewTask) -> AppResult<Task> {
+        if new_task.workspace.is_none() && new_task.projects.is_empty() && new_task.parent.is_none()
+        {
+            new_task.workspace = Some(
+                self.get_current_workspace()
+                    .await
+                    .ok_or(AppError::WorkspaceNotConfigured)?,
+            );
+        }
+
+        let task = self.task_service.create_task(&new_task).await?;
+        self.patch_cached_lists(&task).await;
+
+        Ok(task)
     }
 
     pub async fn get_task_comments(&self, task_id: &TaskId) -> AppResult<Vec<Comment>> {
-        self.task_service.get_task_comments(task_id, true).await
+        let result = self
+            .reach(self.task_service.get_task_comments(task_id, true))
+            .await;
+        let Some(offline) = &self.offline else {
+            return result;
+        };
+
+        let me = self.get_current_user().await;
+        match result {
+            Ok(comments) => {
+                offline.remember_comments(task_id, &comments).await;
+                Ok(offline.comments(task_id, me).await?.unwrap_or(comments))
+            }
+            Err(e) if e.is_network() => offline.comments(task_id, me).await?.ok_or(e),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Post a comment, or queue it as [`Self::update_task`] queues updates
+    pub async fn create_comment(&self, task_id: &TaskId, text: &str) -> AppResult<Comment> {
+        if self.has_pending_changes(task_id).await? {
+            if let Some(comment) = self.queue_comment(task_id, text).await? {
+                return Ok(comment);
+            }
+        }
+
+        let result = self
+            .reach(self.task_service.create_comment(task_id, text))
+            .await;
+        let mut comment = match result {
+            Err(e) if e.is_network() => return self.queue_comment(task_id, text).await?.ok_or(e),
+            result => result?,
+        };
+
+        // The create response may omit the author; it is always us
+        if comment.author.is_none() {
+            comment.author = self.get_current_user().await;
+        }
+
+        Ok(comment)
+    }
+
+    async fn queue_comment(&self, task_id: &TaskId, text: &str) -> AppResult<Option<Comment>> {
+        let Some(offline) = &self.offline else {
+            return Ok(None);
+        };
+        let Some(task) = offline.server_copy(task_id) else {
+            return Ok(None);
+        };
+
+        let me = self.get_current_user().await;
+        offline.queue_comment(&task, text, me).await.map(Some)
+    }
+
+    /// Send changes queued offline in the order they were made, stopping if
+    /// Asana is still unreachable or can't take them just now. An update to a
+    /// task someone else changed since it was queued is dropped and reported
+    /// rather than overwriting theirs; so is anything Asana refuses for good.
+    pub async fn sync_offline_changes(&self) -> AppResult<SyncReport> {
+        let Some(offline) = &self.offline else {
+            return Ok(SyncReport::default());
+        };
+
+        let pending = offline.pending().await?;
+        if pending.is_empty() {
+            // Nothing to send, but find out whether Asana is back
+            if offline.is_offline() {
+                let probe = self.workspace_repo.get_current_user().await;
+                self.note_reachability(&probe.map_err(AppError::from));
+            }
+            return Ok(SyncReport {
+                still_offline: offline.is_offline(),
+                ..Default::default()
+            });
+        }
+
+        let mut report = SyncReport::default();
+        for change in pending {
+            match self.replay(offline, &change).await {
+                Ok(None) => report.sent += 1,
+                Ok(Some(conflict)) => report.conflicts.push(conflict),
+                Err(e) if e.is_network() => {
+                    offline.set_offline(true);
+                    report.still_offline = true;
+                    break;
+                }
+                Err(e) if e.is_refusal() => report.conflicts.push(Conflict {
+                    task_name: offline
+                        .server_copy(&change.task)
+                        .map_or_else(|| change.task.to_string(), |task| task.name),
+                    reason: e.to_string(),
+                    change: change.clone(),
+                }),
+                // Rate limited, a server error and the like: try again later,
+                // keeping this change and the ones after it in order
+                Err(e) => {
+                    offline.set_offline(false);
+                    report.held_back = Some(e.to_string());
+                    break;
+                }
+            }
+            offline.set_offline(false);
+            offline.forget(&change).await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Send one queued change; `Some` if it was dropped as a conflict instead
+    async fn replay(
+        &self,
+        offline: &OfflineStore,
+        change: &PendingChange,
+    ) -> AppResult<Option<Conflict>> {
+        // Comments add to the thread rather than overwrite anything, so never conflict
+        if let OfflineChange::Comment { text } = &change.change {
+            self.task_service.create_comment(&change.task, text).await?;
+            return Ok(None);
+        }
+
+        let current = self.task_service.get_task(&change.task, false).await?;
+        if !offline.is_base_of(change, &current) {
+            // Cached listings show the queued value; put back what Asana has
+            self.patch_cached_lists(&current).await;
+            return Ok(Some(Conflict {
+                task_name: current.name,
+                reason: format!(
+                    "changed in Asana at {} after this was queued",
+                    current.modified_at.format("%b %d %H:%M UTC")
+                ),
+                change: change.clone(),
+            }));
+        }
+
+        if let Some(update) = change.change.to_update() {
+            let updated = self.task_service.update_task(&change.task, &update).await?;
+            self.patch_cached_lists(&updated).await;
+            offline.record_sent(&updated);
+        }
+        Ok(None)
+    }
+
+    /// Bring the caches up to date with what changed on `cursor`'s feed since
+    /// the last poll, refetching each changed task once. The first poll only
+    /// starts the stream; if Asana has expired the token, the cached listings
+    /// the feed covers are marked stale, the cached tasks are dropped and
+    /// [`LiveUpdate::Resync`] asks for a full reload.
+    pub async fn poll_events(&self, cursor: &mut EventCursor) -> AppResult<LiveUpdate> {
+        let resource = match &cursor.resource {
+            Some(resource) => resource.clone(),
+            None => {
+                let resource = match &cursor.feed {
+                    EventFeed::Project(project) => project.0.clone(),
+                    EventFeed::MyTasks => {
+                        let workspace = self
+                            .get_current_workspace()
+                            .await
+                            .ok_or(AppError::WorkspaceNotConfigured)?;
+                        self.reach(async {
+                            Ok(self.event_repo.get_user_task_list(&workspace).await?)
+                        })
+                        .await?
+                    }
+                };
+                cursor.resource = Some(resource.clone());
+                resource
+            }
+        };
+
+        // Only move the cursor on once the changes are in the caches, so a
+        // failed poll is retried from the same place
+        let mut sync = cursor.sync.clone();
+        let mut events = Vec::new();
+        loop {
+            let page = self
+                .reach(async {
+                    Ok(self
+                        .event_repo
+                        .get_events(&resource, sync.as_deref())
+                        .await?)
+                })
+                .await;
+            match page {
+                Ok(page) => {
+                    events.extend(page.events);
+                    sync = Some(page.sync);
+                    if !page.has_more {
+                        break;
+                    }
+                }
+                Err(AppError::Repository(RepositoryError::SyncExpired(fresh))) => {
+                    let started = cursor.sync.is_some();
+                    cursor.sync = Some(fresh);
+                    if !started {
+                        return Ok(LiveUpdate::Changed {
+                            tasks: Vec::new(),
+                            deleted: Vec::new(),
+                            commented: Vec::new(),
+                        });
+                    }
+                    // Still fine to show at startup, but fetched again before use
+                    let me = self.get_current_user().await.map(|user| user.id);
+                    let workspace = self.get_current_workspace().await;
+                    self.edit_cached_lists(|cached| {
+                        let covered = match &cursor.feed {
+                            EventFeed::Project(project) => {
+                                cached.filter.project.as_ref() == Some(project)
+                            }
+                            EventFeed::MyTasks => {
+                                me.is_some()
+                                    && cached.filter.assignee == me
+                                    && cached.filter.workspace == workspace
+                            }
+                        };
+                        if !covered {
+                            return ListEdit::Unchanged;
+                        }
+                        cached.fetched_at = DateTime::UNIX_EPOCH;
+                        ListEdit::Changed
+                    })
+                    .await;
+                    self.task_service.clear_caches().await;
+                    return Ok(LiveUpdate::Resync);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let EventSummary {
+            changed,
+            mut deleted,
+            commented,
+        } = EventSummary::from_events(&events);
+
+        for id in &commented {
+            self.task_service.invalidate_comments(id).await;
+        }
+        let mut tasks = Vec::new();
+        for id in changed {
+            match self.reach(self.task_service.get_task(&id, false)).await {
+                Ok(task) => {
+                    self.patch_cached_lists(&task).await;
+                    tasks.push(task);
+                }
+                Err(AppError::Repository(RepositoryError::NotFound(_))) => deleted.push(id),
+                Err(e) => return Err(e),
+            }
+        }
+        for id in &deleted {
+            self.drop_from_cached_lists(id).await;
+            self.task_service.invalidate_task(id).await;
+        }
+        if let Some(offline) = &self.offline {
+            offline.remember_tasks(&tasks);
+            offline.apply_pending(&mut tasks).await?;
+        }
+
+        cursor.sync = sync;
+        Ok(LiveUpdate::Changed {
+            tasks,
+            deleted,
+            commented,
+        })
     }
 }
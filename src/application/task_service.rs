@@ -36,7 +36,7 @@ impl TaskService {
 
     pub async fn list_tasks(&self, filter: &TaskFilter, _use_cache: bool) -> AppResult<Vec<Task>> {
         // For list operations, we don't cache the entire list but we do cache individual tasks
-        let tasks = self.repository.list_tasks(filter).await?;
+        let tasks = self.repository.list_tasks(filter).await?.items;
 
         // Cache individual tasks for future single-task lookups
         for task in &tasks {
@@ -46,6 +46,42 @@ impl TaskService {
         Ok(tasks)
     }
 
+    pub async fn search_tasks(
+        &self,
+        workspace: &WorkspaceId,
+        search: &WorkspaceSearch,
+    ) -> AppResult<Vec<Task>> {
+        let tasks = self.repository.search_tasks(workspace, search).await?;
+
+        for task in &tasks {
+            self.cache.insert(task.id.clone(), task.clone()).await;
+        }
+
+        Ok(tasks)
+    }
+
+    pub async fn create_task(&self, new_task: &NewTask) -> AppResult<Task> {
+        let task = self.repository.create_task(new_task).await?;
+        self.cache.insert(task.id.clone(), task.clone()).await;
+
+        // The parent's subtask count just changed
+        if let Some(parent) = &new_task.parent {
+            self.cache.remove(parent).await;
+        }
+
+        Ok(task)
+    }
+
+    pub async fn list_subtasks(&self, parent: &TaskId) -> AppResult<Vec<Task>> {
+        let subtasks = self.repository.list_subtasks(parent).await?;
+
+        for task in &subtasks {
+            self.cache.insert(task.id.clone(), task.clone()).await;
+        }
+
+        Ok(subtasks)
+    }
+
     pub async fn update_task(&self, id: &TaskId, updates: &TaskUpdate) -> AppResult<Task> {
         let updated_task = self.repository.update_task(id, updates).await?;
 
@@ -55,15 +91,20 @@ impl TaskService {
         Ok(updated_task)
     }
 
-    pub async fn toggle_task_completion(&self, id: &TaskId) -> AppResult<Task> {
-        let task = self.get_task(id, true).await?;
+    /// Forget the cached copy of a task changed behind the repository's back
+    pub async fn invalidate_task(&self, id: &TaskId) {
+        self.cache.remove(id).await;
+    }
 
-        let update = TaskUpdate {
-            completed: Some(!task.completed),
-            ..Default::default()
-        };
+    /// Forget the cached comments of a task someone else commented on
+    pub async fn invalidate_comments(&self, id: &TaskId) {
+        self.comment_cache.remove(id).await;
+    }
 
-        self.update_task(id, &update).await
+    /// Drop every cached task and comment, e.g. after switching workspaces
+    pub async fn clear_caches(&self) {
+        self.cache.clear().await;
+        self.comment_cache.clear().await;
     }
 
     pub async fn get_task_comments(
@@ -84,7 +125,6 @@ impl TaskService {
         Ok(comments)
     }
 
-    #[allow(dead_code)] // Might be used when comment creation is added to TUI
     pub async fn create_comment(&self, task_id: &TaskId, content: &str) -> AppResult<Comment> {
         let comment = self.repository.create_comment(task_id, content).await?;
 
@@ -0,0 +1,316 @@
+use chrono::{
+    DateTime, Datelike, Days, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// The time zone dates are shown and compared in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    /// Whatever the system (or `TZ`) says
+    Local,
+    Named(Tz),
+}
+
+static CONFIGURED_ZONE: OnceLock<Zone> = OnceLock::new();
+
+impl Zone {
+    /// Use `zone` for the rest of the process; only the first call takes effect
+    pub fn init(zone: Zone) {
+        let _ = CONFIGURED_ZONE.set(zone);
+    }
+
+    /// The configured zone, or the system's local one
+    pub fn current() -> Zone {
+        CONFIGURED_ZONE.get().copied().unwrap_or(Zone::Local)
+    }
+
+    /// Wall-clock time of `instant` in this zone
+    pub fn local_datetime(&self, instant: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).naive_local(),
+            Zone::Named(tz) => instant.with_timezone(tz).naive_local(),
+        }
+    }
+
+    pub fn today(&self, now: DateTime<Utc>) -> NaiveDate {
+        self.local_datetime(now).date()
+    }
+
+    /// The instant `date` begins in this zone
+    pub fn start_of(&self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_time(NaiveTime::MIN);
+        let start = match self {
+            Zone::Local => Local
+                .from_local_datetime(&midnight)
+                .earliest()
+                .map(|dt| dt.to_utc()),
+            Zone::Named(tz) => tz
+                .from_local_datetime(&midnight)
+                .earliest()
+                .map(|dt| dt.to_utc()),
+        };
+        // Only reachable if a zone skips midnight entirely
+        start.unwrap_or_else(|| midnight.and_utc())
+    }
+}
+
+impl FromStr for Zone {
+    type Err = String;
+
+    /// `local` or an IANA name such as `Europe/Berlin`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("local") {
+            return Ok(Zone::Local);
+        }
+        s.parse::<Tz>().map(Zone::Named).map_err(|_| {
+            format!("Unknown time zone '{s}': expected 'local' or a name like Europe/Berlin")
+        })
+    }
+}
+
+/// When a task is due: all day on a date (Asana's `due_on`) or at a moment (`due_at`).
+///
+/// Serialized as the bare date or timestamp, so JSON output keeps the distinction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Due {
+    On(NaiveDate),
+    At(DateTime<Utc>),
+}
+
+impl Due {
+    /// The calendar day this falls on in `zone`
+    pub fn date(&self, zone: Zone) -> NaiveDate {
+        match self {
+            Due::On(date) => *date,
+            Due::At(instant) => zone.local_datetime(*instant).date(),
+        }
+    }
+
+    /// Wall-clock time in `zone`, or `None` for all-day dates
+    pub fn time(&self, zone: Zone) -> Option<NaiveTime> {
+        match self {
+            Due::On(_) => None,
+            Due::At(instant) => Some(zone.local_datetime(*instant).time()),
+        }
+    }
+
+    /// Whether the deadline has passed: the moment for timed dues, the end of
+    /// the day for all-day ones
+    pub fn is_past(&self, now: DateTime<Utc>, zone: Zone) -> bool {
+        match self {
+            Due::On(date) => *date < zone.today(now),
+            Due::At(instant) => *instant < now,
+        }
+    }
+
+    /// The instant the due day or moment begins, for comparing against durations
+    pub fn starts_at(&self, zone: Zone) -> DateTime<Utc> {
+        match self {
+            Due::On(date) => zone.start_of(*date),
+            Due::At(instant) => *instant,
+        }
+    }
+
+    /// Ordering key: by day, timed dues first, all-day ones at the end of their day
+    pub fn sort_key(&self, zone: Zone) -> (NaiveDate, bool, Option<NaiveTime>) {
+        (self.date(zone), self.time(zone).is_none(), self.time(zone))
+    }
+
+    /// Human-friendly, relative to `now`: "Today", "Tomorrow 15:00", "3 days ago", …
+    pub fn display(&self, now: DateTime<Utc>, zone: Zone) -> String {
+        let date = self.date(zone);
+        let days_diff = (date - zone.today(now)).num_days();
+
+        let day = match days_diff {
+            0 => "Today".to_string(),
+            1 => "Tomorrow".to_string(),
+            -1 => "Yesterday".to_string(),
+            d if d < 0 => format!("{} days ago", -d),
+            d if d <= 7 => format!("In {d} days"),
+            _ => date.format("%Y-%m-%d").to_string(),
+        };
+
+        match self.time(zone) {
+            Some(time) => format!("{day} {}", time.format("%H:%M")),
+            None => day,
+        }
+    }
+}
+
+/// Parse a user-entered all-day due date relative to today in the current zone.
+/// See [`parse_due_date_from`] for the accepted forms.
+pub fn parse_due_date(input: &str) -> Result<Due, String> {
+    parse_date(input).map(Due::On)
+}
+
+/// Parse a user-entered date such as a start date, in the same forms as [`parse_due_date`]
+pub fn parse_date(input: &str) -> Result<NaiveDate, String> {
+    parse_due_date_from(input, Zone::current().today(Utc::now()))
+}
+
+/// Parse a due date relative to `today`: `YYYY-MM-DD`, `today`, `tomorrow`,
+/// a weekday (`fri`, `friday`; always the next one after today), or an offset
+/// like `+3d` or `+2w`
+pub fn parse_due_date_from(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let text = input.trim().to_lowercase();
+
+    let date = match text.as_str() {
+        "today" => Some(today),
+        "tomorrow" => today.succ_opt(),
+        _ => NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+            .ok()
+            .or_else(|| parse_weekday(&text).map(|weekday| next_weekday(today, weekday)))
+            .or_else(|| parse_offset(&text).and_then(|days| today.checked_add_days(days))),
+    };
+
+    date.ok_or_else(|| {
+        format!(
+            "Invalid date '{}': expected YYYY-MM-DD, today, tomorrow, a weekday or +Nd/+Nw",
+            input.trim()
+        )
+    })
+}
+
+fn parse_weekday(text: &str) -> Option<Weekday> {
+    // chrono accepts both "fri" and "friday"
+    text.parse().ok()
+}
+
+/// The first `weekday` strictly after `today`
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    today + chrono::Duration::days(if ahead == 0 { 7 } else { ahead.into() })
+}
+
+fn parse_offset(text: &str) -> Option<Days> {
+    let rest = text.strip_prefix('+')?;
+    if let Some(days) = rest.strip_suffix('d') {
+        return Some(Days::new(days.parse().ok()?));
+    }
+    let weeks: u64 = rest.strip_suffix('w')?.parse().ok()?;
+    Some(Days::new(weeks.checked_mul(7)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(name: &str) -> Zone {
+        name.parse().unwrap()
+    }
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        timestamp.parse().unwrap()
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn due(input: &str) -> Result<String, String> {
+        // A Wednesday
+        let today = date("2026-10-14");
+        parse_due_date_from(input, today).map(|date| date.format("%Y-%m-%d").to_string())
+    }
+
+    #[test]
+    fn test_parse_due_date_accepts_natural_phrases() {
+        assert_eq!(due("2026-11-01").unwrap(), "2026-11-01");
+        assert_eq!(due("today").unwrap(), "2026-10-14");
+        assert_eq!(due(" Tomorrow ").unwrap(), "2026-10-15");
+        assert_eq!(due("fri").unwrap(), "2026-10-16");
+        assert_eq!(due("monday").unwrap(), "2026-10-19");
+        assert_eq!(due("wed").unwrap(), "2026-10-21");
+        assert_eq!(due("+3d").unwrap(), "2026-10-17");
+        assert_eq!(due("+2w").unwrap(), "2026-10-28");
+    }
+
+    #[test]
+    fn test_parse_due_date_rejects_unknown_input() {
+        for input in [
+            "soon",
+            "+d",
+            "+3x",
+            "",
+            "2026-13-01",
+            "+3é",
+            "+3000000000000000000w",
+            "+99999999999999d",
+        ] {
+            assert!(due(input).is_err(), "{input:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_all_day_due_is_not_overdue_until_the_day_ends_locally() {
+        let due = Due::On(date("2026-10-14"));
+        let new_york = zone("America/New_York");
+
+        // 01:00 UTC on the 15th is still the evening of the 14th in New York
+        let evening = at("2026-10-15T01:00:00Z");
+        assert!(!due.is_past(evening, new_york));
+        assert_eq!(due.display(evening, new_york), "Today");
+
+        // …but already the 15th in Berlin
+        let berlin = zone("Europe/Berlin");
+        assert!(due.is_past(evening, berlin));
+        assert_eq!(due.display(evening, berlin), "Yesterday");
+    }
+
+    #[test]
+    fn test_timed_due_uses_the_local_date_and_time() {
+        let due = Due::At(at("2026-10-15T02:30:00Z"));
+        let los_angeles = zone("America/Los_Angeles");
+
+        assert_eq!(due.date(los_angeles), date("2026-10-14"));
+        assert_eq!(
+            due.display(at("2026-10-14T16:00:00Z"), los_angeles),
+            "Today 19:30"
+        );
+        assert!(!due.is_past(at("2026-10-15T02:00:00Z"), los_angeles));
+        assert!(due.is_past(at("2026-10-15T03:00:00Z"), los_angeles));
+    }
+
+    #[test]
+    fn test_sort_key_puts_timed_before_all_day_on_the_same_day() {
+        let utc = zone("UTC");
+        let mut dues = [
+            Due::On(date("2026-10-14")),
+            Due::At(at("2026-10-15T09:00:00Z")),
+            Due::At(at("2026-10-14T17:00:00Z")),
+            Due::At(at("2026-10-14T08:00:00Z")),
+        ];
+        dues.sort_by_key(|due| due.sort_key(utc));
+
+        assert_eq!(
+            dues,
+            [
+                Due::At(at("2026-10-14T08:00:00Z")),
+                Due::At(at("2026-10-14T17:00:00Z")),
+                Due::On(date("2026-10-14")),
+                Due::At(at("2026-10-15T09:00:00Z")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_due_serializes_as_a_bare_date_or_timestamp() {
+        let on = Due::On(date("2026-11-01"));
+        let at = Due::At(at("2026-11-01T15:00:00Z"));
+
+        assert_eq!(serde_json::to_value(on).unwrap(), "2026-11-01");
+        assert_eq!(serde_json::to_value(at).unwrap(), "2026-11-01T15:00:00Z");
+        assert_eq!(
+            serde_json::from_value::<Due>("2026-11-01".into()).unwrap(),
+            on
+        );
+        assert_eq!(
+            serde_json::from_value::<Due>("2026-11-01T15:00:00Z".into()).unwrap(),
+            at
+        );
+    }
+}
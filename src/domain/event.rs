@@ -0,0 +1,105 @@
+use super::TaskId;
+
+/// What happened to a resource, as Asana's Events API reports it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventAction {
+    Added,
+    Changed,
+    Removed,
+    Deleted,
+    Undeleted,
+}
+
+/// Something that happened to a task in a watched project or My Tasks list,
+/// or to a story (a comment or activity entry) on it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskEvent {
+    pub task: TaskId,
+    pub action: EventAction,
+    /// The event was about one of the task's stories rather than the task itself
+    pub story: bool,
+}
+
+/// Events since a sync token, and the token to ask from next time
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventPage {
+    pub events: Vec<TaskEvent>,
+    pub sync: String,
+    /// More events are waiting; ask again with `sync` straight away
+    pub has_more: bool,
+}
+
+/// The net effect of a run of events, each task listed once in the order it
+/// was first mentioned
+#[derive(Debug, Default, PartialEq)]
+pub struct EventSummary {
+    /// Tasks that still exist and may look different now
+    pub changed: Vec<TaskId>,
+    pub deleted: Vec<TaskId>,
+    /// Tasks whose comments changed
+    pub commented: Vec<TaskId>,
+}
+
+impl EventSummary {
+    pub fn from_events(events: &[TaskEvent]) -> Self {
+        let mut summary = Self::default();
+
+        for event in events {
+            let id = &event.task;
+            // A story deleted is a comment gone, not the task
+            if event.story {
+                if !summary.commented.contains(id) {
+                    summary.commented.push(id.clone());
+                }
+            } else if event.action == EventAction::Deleted {
+                summary.changed.retain(|changed| changed != id);
+                if !summary.deleted.contains(id) {
+                    summary.deleted.push(id.clone());
+                }
+                continue;
+            }
+
+            summary.deleted.retain(|deleted| deleted != id);
+            if !summary.changed.contains(id) {
+                summary.changed.push(id.clone());
+            }
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(task: &str, action: EventAction, story: bool) -> TaskEvent {
+        TaskEvent {
+            task: TaskId::from(task),
+            action,
+            story,
+        }
+    }
+
+    #[test]
+    fn test_summary_keeps_the_last_word_on_each_task() {
+        let summary = EventSummary::from_events(&[
+            event("1", EventAction::Changed, false),
+            event("2", EventAction::Added, false),
+            event("1", EventAction::Changed, false),
+            event("2", EventAction::Deleted, false),
+            event("3", EventAction::Deleted, false),
+            event("3", EventAction::Undeleted, false),
+            event("4", EventAction::Deleted, true),
+        ]);
+
+        assert_eq!(
+            summary,
+            EventSummary {
+                changed: vec![TaskId::from("1"), TaskId::from("3"), TaskId::from("4")],
+                deleted: vec![TaskId::from("2")],
+                commented: vec![TaskId::from("4")],
+            }
+        );
+    }
+}
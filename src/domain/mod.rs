@@ -1,11 +1,25 @@
 pub mod comment;
+pub mod due;
+pub mod event;
+pub mod outbox;
+pub mod page;
 pub mod project;
+pub mod query;
+pub mod search;
+pub mod section;
 pub mod task;
 pub mod user;
 pub mod workspace;
 
 pub use comment::*;
+pub use due::*;
+pub use event::*;
+pub use outbox::*;
+pub use page::*;
 pub use project::*;
+pub use query::*;
+pub use search::*;
+pub use section::*;
 pub use task::*;
 pub use user::*;
 pub use workspace::*;
@@ -0,0 +1,165 @@
+use super::{Due, Task, TaskId, TaskUpdate};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A write made while Asana was unreachable, kept until it can be sent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingChange {
+    pub id: String,
+    pub task: TaskId,
+    pub change: OfflineChange,
+    /// The task's `modified_at` as last seen from Asana; a different one at
+    /// replay time means someone else changed the task in the meantime
+    pub base_modified_at: DateTime<Utc>,
+    pub queued_at: DateTime<Utc>,
+}
+
+impl PendingChange {
+    pub fn new(task: &Task, change: OfflineChange) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            task: task.id.clone(),
+            change,
+            base_modified_at: task.modified_at,
+            queued_at: Utc::now(),
+        }
+    }
+}
+
+/// The writes that can be made offline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OfflineChange {
+    SetCompleted { completed: bool },
+    Rename { name: String },
+    SetDue { due: Option<Due> },
+    Comment { text: String },
+}
+
+impl OfflineChange {
+    /// Split an update into queueable changes; `None` if it touches anything
+    /// else, such as the description or assignee
+    pub fn from_update(update: &TaskUpdate) -> Option<Vec<Self>> {
+        let TaskUpdate {
+            name,
+            description: None,
+            html_description: None,
+            completed,
+            due,
+            start_on: None,
+            assignee: None,
+        } = update
+        else {
+            return None;
+        };
+
+        let changes: Vec<_> = [
+            name.clone().map(|name| OfflineChange::Rename { name }),
+            completed.map(|completed| OfflineChange::SetCompleted { completed }),
+            due.map(|due| OfflineChange::SetDue { due }),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        (!changes.is_empty()).then_some(changes)
+    }
+
+    /// The task update that sends this change, or `None` for a comment
+    pub fn to_update(&self) -> Option<TaskUpdate> {
+        let update = match self {
+            OfflineChange::SetCompleted { completed } => TaskUpdate {
+                completed: Some(*completed),
+                ..Default::default()
+            },
+            OfflineChange::Rename { name } => TaskUpdate {
+                name: Some(name.clone()),
+                ..Default::default()
+            },
+            OfflineChange::SetDue { due } => TaskUpdate {
+                due: Some(*due),
+                ..Default::default()
+            },
+            OfflineChange::Comment { .. } => return None,
+        };
+        Some(update)
+    }
+
+    /// Show the change locally before Asana has it
+    pub fn apply_to(&self, task: &mut Task) {
+        match self {
+            OfflineChange::SetCompleted { completed } => task.completed = *completed,
+            OfflineChange::Rename { name } => task.name = name.clone(),
+            OfflineChange::SetDue { due } => task.due = *due,
+            OfflineChange::Comment { .. } => {}
+        }
+    }
+
+    /// A few words for messages about the change
+    pub fn describe(&self) -> String {
+        match self {
+            OfflineChange::SetCompleted { completed: true } => "mark complete".to_string(),
+            OfflineChange::SetCompleted { completed: false } => "mark incomplete".to_string(),
+            OfflineChange::Rename { name } => format!("rename to '{name}'"),
+            OfflineChange::SetDue { due: Some(_) } => "set due date".to_string(),
+            OfflineChange::SetDue { due: None } => "clear due date".to_string(),
+            OfflineChange::Comment { .. } => "comment".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::domain_task;
+
+    #[test]
+    fn test_updates_split_into_offline_changes() {
+        let update = TaskUpdate {
+            name: Some("Renamed".to_string()),
+            completed: Some(true),
+            ..Default::default()
+        };
+        let changes = OfflineChange::from_update(&update).unwrap();
+        assert_eq!(
+            changes,
+            [
+                OfflineChange::Rename {
+                    name: "Renamed".to_string()
+                },
+                OfflineChange::SetCompleted { completed: true },
+            ]
+        );
+
+        let mut task = domain_task("1", "Original");
+        for change in &changes {
+            change.apply_to(&mut task);
+        }
+        assert_eq!(task.name, "Renamed");
+        assert!(task.completed);
+
+        let description = TaskUpdate {
+            description: Some("Notes".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(OfflineChange::from_update(&description), None);
+        assert_eq!(OfflineChange::from_update(&TaskUpdate::default()), None);
+    }
+
+    #[test]
+    fn test_pending_changes_round_trip_through_json() {
+        let change = PendingChange::new(
+            &domain_task("1", "Task"),
+            OfflineChange::SetDue {
+                due: Some(Due::On("2026-10-20".parse().unwrap())),
+            },
+        );
+
+        let json = serde_json::to_string(&change).unwrap();
+        assert!(json.contains("\"kind\":\"set_due\""));
+        assert_eq!(
+            serde_json::from_str::<PendingChange>(&json).unwrap(),
+            change
+        );
+    }
+}
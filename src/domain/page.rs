@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// One page of a cursor-paginated listing.
+///
+/// `next_offset` is an opaque cursor; pass it back as `TaskFilter::offset` to
+/// continue where this page left off. `None` means there is nothing more to fetch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_offset: Option<String>,
+}
@@ -0,0 +1,492 @@
+use super::{CustomField, Due, ProjectId, Task, TaskFilter, UserId, Zone};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::fmt;
+
+/// A parsed search query: free text for fuzzy matching plus structured filters,
+/// all of which must match.
+///
+/// Filters are written as `key:value` tokens (`project:Roadmap`, `tag:bug`,
+/// `due:<7d`, `assignee:me`, `is:milestone`, `cf:Priority=High`) or the bare
+/// keyword `overdue`. A leading `-` negates a filter and double quotes allow
+/// spaces in values, e.g. `-project:"Q3 Launch"`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskQuery {
+    pub text: String,
+    pub filters: Vec<QueryFilter>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryFilter {
+    /// Project name (case-insensitive substring) or gid
+    Project(String),
+    /// Tag name (case-insensitive substring)
+    Tag(String),
+    Due(DueCondition),
+    Overdue,
+    Assignee(AssigneeMatch),
+    Is(TaskKind),
+    /// Custom field by name whose value equals `value` (case-insensitive)
+    CustomField {
+        name: String,
+        value: String,
+    },
+    Not(Box<QueryFilter>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DueCondition {
+    /// Has a due date earlier than now plus the duration (`due:<7d`)
+    Within(Duration),
+    /// Has a due date at least the duration away (`due:>7d`)
+    Beyond(Duration),
+    On(NaiveDate),
+    Before(NaiveDate),
+    After(NaiveDate),
+    Today,
+    /// Has any due date (`due:any`)
+    Any,
+    /// Has no due date (`due:none`)
+    None,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssigneeMatch {
+    Me,
+    Unassigned,
+    /// Assignee name (case-insensitive substring) or gid
+    Named(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskKind {
+    Milestone,
+    Completed,
+    Incomplete,
+}
+
+/// A query that could not be parsed, with the character index of the offending token
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl TaskQuery {
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let mut query = TaskQuery::default();
+        let mut text = Vec::new();
+
+        for (position, token) in tokenize(input)? {
+            match parse_filter(&token).map_err(|message| QueryError { message, position })? {
+                Some(filter) => query.filters.push(filter),
+                None => text.push(token),
+            }
+        }
+
+        query.text = text.join(" ");
+        Ok(query)
+    }
+
+    /// Whether `task` passes every structured filter; the free text is left to the fuzzy matcher
+    pub fn matches(&self, task: &Task, me: Option<&UserId>, now: DateTime<Utc>) -> bool {
+        self.filters
+            .iter()
+            .all(|filter| filter.matches(task, me, now))
+    }
+
+    /// Narrow an API filter with the parts of this query the Asana API can apply itself.
+    ///
+    /// Only a project gid and completed tasks can be expressed on `/tasks`; a
+    /// project replaces the assignee + workspace scope since the API accepts only
+    /// one of them. Everything else still has to go through [`TaskQuery::matches`].
+    pub fn push_down(&self, base: &TaskFilter) -> TaskFilter {
+        let mut filter = base.clone();
+
+        for query_filter in &self.filters {
+            match query_filter {
+                QueryFilter::Project(project) if is_gid(project) => {
+                    filter.project = Some(ProjectId(project.clone()));
+                    filter.workspace = None;
+                    filter.assignee = None;
+                }
+                QueryFilter::Is(TaskKind::Completed) => filter.completed = None,
+                _ => {}
+            }
+        }
+
+        filter
+    }
+}
+
+impl QueryFilter {
+    pub fn matches(&self, task: &Task, me: Option<&UserId>, now: DateTime<Utc>) -> bool {
+        match self {
+            QueryFilter::Project(project) => task
+                .projects
+                .iter()
+                .any(|p| p.gid == *project || contains_ignore_case(&p.name, project)),
+            QueryFilter::Tag(tag) => task.tags.iter().any(|t| contains_ignore_case(t, tag)),
+            QueryFilter::Due(condition) => condition.matches(task.due, now, Zone::current()),
+            QueryFilter::Overdue => task.is_overdue_at(now, Zone::current()),
+            QueryFilter::Assignee(AssigneeMatch::Me) => {
+                me.is_some() && task.assignee.as_ref() == me
+            }
+            QueryFilter::Assignee(AssigneeMatch::Unassigned) => task.assignee.is_none(),
+            QueryFilter::Assignee(AssigneeMatch::Named(name)) => {
+                task.assignee.as_ref().is_some_and(|a| a.0 == *name)
+                    || task
+                        .assignee_name
+                        .as_deref()
+                        .is_some_and(|n| contains_ignore_case(n, name))
+            }
+            QueryFilter::Is(TaskKind::Milestone) => task.is_milestone(),
+            QueryFilter::Is(TaskKind::Completed) => task.completed,
+            QueryFilter::Is(TaskKind::Incomplete) => !task.completed,
+            QueryFilter::CustomField { name, value } => task
+                .custom_fields
+                .iter()
+                .filter(|cf| cf.name.eq_ignore_ascii_case(name))
+                .any(|cf| custom_field_equals(cf, value)),
+            QueryFilter::Not(inner) => !inner.matches(task, me, now),
+        }
+    }
+}
+
+impl DueCondition {
+    /// Durations are measured from `now` to the due moment (the start of the day
+    /// for all-day dates); dates are compared as calendar days in `zone`
+    fn matches(&self, due: Option<Due>, now: DateTime<Utc>, zone: Zone) -> bool {
+        let Some(due) = due else {
+            return matches!(self, DueCondition::None);
+        };
+
+        match self {
+            // Durations too long to add to `now` match nothing
+            DueCondition::Within(duration) => now
+                .checked_add_signed(*duration)
+                .is_some_and(|end| due.starts_at(zone) < end),
+            DueCondition::Beyond(duration) => now
+                .checked_add_signed(*duration)
+                .is_some_and(|start| due.starts_at(zone) >= start),
+            DueCondition::On(date) => due.date(zone) == *date,
+            DueCondition::Before(date) => due.date(zone) < *date,
+            DueCondition::After(date) => due.date(zone) > *date,
+            DueCondition::Today => due.date(zone) == zone.today(now),
+            DueCondition::Any => true,
+            DueCondition::None => false,
+        }
+    }
+}
+
+fn custom_field_equals(field: &CustomField, value: &str) -> bool {
+    let equals = |candidate: Option<&str>| candidate.is_some_and(|c| c.eq_ignore_ascii_case(value));
+
+    equals(field.display_value.as_deref())
+        || equals(field.enum_value.as_ref().map(|e| e.name.as_str()))
+        || equals(field.text_value.as_deref())
+        || field
+            .number_value
+            .is_some_and(|n| n.to_string() == value.trim())
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+fn is_gid(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Split on whitespace outside double quotes, dropping the quotes
+fn tokenize(input: &str) -> Result<Vec<(usize, String)>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    let mut quote_start = None;
+
+    for (i, c) in input.chars().enumerate() {
+        match c {
+            '"' => {
+                quote_start = match quote_start {
+                    Some(_) => None,
+                    None => Some(i),
+                };
+                current.get_or_insert((i, String::new()));
+            }
+            c if c.is_whitespace() && quote_start.is_none() => {
+                tokens.extend(current.take());
+            }
+            c => current.get_or_insert((i, String::new())).1.push(c),
+        }
+    }
+
+    if let Some(position) = quote_start {
+        return Err(QueryError {
+            message: "Unclosed quote".to_string(),
+            position,
+        });
+    }
+    tokens.extend(current);
+    Ok(tokens)
+}
+
+/// Parse one token as a filter; `Ok(None)` means it is free text
+fn parse_filter(token: &str) -> Result<Option<QueryFilter>, String> {
+    if let Some(negated) = token.strip_prefix('-').filter(|t| !t.is_empty()) {
+        return Ok(parse_filter(negated)?.map(|f| QueryFilter::Not(Box::new(f))));
+    }
+
+    if token.eq_ignore_ascii_case("overdue") {
+        return Ok(Some(QueryFilter::Overdue));
+    }
+
+    let Some((key, value)) = token.split_once(':') else {
+        return Ok(None);
+    };
+    let key = key.to_ascii_lowercase();
+    if !["project", "tag", "due", "assignee", "is", "cf"].contains(&key.as_str()) {
+        // Not one of ours, e.g. "10:30"
+        return Ok(None);
+    }
+    if value.is_empty() {
+        return Err(format!("{key}: needs a value"));
+    }
+
+    let filter = match key.as_str() {
+        "project" => QueryFilter::Project(value.to_string()),
+        "tag" => QueryFilter::Tag(value.to_string()),
+        "due" => QueryFilter::Due(parse_due_condition(value)?),
+        "assignee" => QueryFilter::Assignee(match value.to_ascii_lowercase().as_str() {
+            "me" => AssigneeMatch::Me,
+            "none" => AssigneeMatch::Unassigned,
+            _ => AssigneeMatch::Named(value.to_string()),
+        }),
+        "is" => QueryFilter::Is(match value.to_ascii_lowercase().as_str() {
+            "milestone" => TaskKind::Milestone,
+            "completed" | "complete" | "done" => TaskKind::Completed,
+            "incomplete" | "open" => TaskKind::Incomplete,
+            _ => {
+                return Err(format!(
+                    "Unknown is:{value} (expected milestone, completed or open)"
+                ))
+            }
+        }),
+        _ => {
+            let Some((name, field_value)) = value.split_once('=') else {
+                return Err(format!("cf:{value} needs a value, e.g. cf:Priority=High"));
+            };
+            if name.is_empty() || field_value.is_empty() {
+                return Err("cf: expects Name=Value".to_string());
+            }
+            QueryFilter::CustomField {
+                name: name.to_string(),
+                value: field_value.to_string(),
+            }
+        }
+    };
+
+    Ok(Some(filter))
+}
+
+fn parse_due_condition(value: &str) -> Result<DueCondition, String> {
+    let invalid = || format!("Invalid due:{value} (try today, none, <7d, >2w or YYYY-MM-DD)");
+
+    match value.to_ascii_lowercase().as_str() {
+        "today" => return Ok(DueCondition::Today),
+        "any" => return Ok(DueCondition::Any),
+        "none" => return Ok(DueCondition::None),
+        _ => {}
+    }
+
+    let (comparison, rest) = match value.chars().next() {
+        Some(c @ ('<' | '>')) => (Some(c), &value[1..]),
+        _ => (None, value),
+    };
+
+    if let Ok(date) = NaiveDate::parse_from_str(rest, "%Y-%m-%d") {
+        return Ok(match comparison {
+            Some('<') => DueCondition::Before(date),
+            Some(_) => DueCondition::After(date),
+            None => DueCondition::On(date),
+        });
+    }
+
+    let duration = parse_relative_duration(rest).ok_or_else(invalid)?;
+    match comparison {
+        Some('<') => Ok(DueCondition::Within(duration)),
+        Some(_) => Ok(DueCondition::Beyond(duration)),
+        None => Err(invalid()),
+    }
+}
+
+/// `7d` or `2w`
+fn parse_relative_duration(value: &str) -> Option<Duration> {
+    let unit = value.chars().last()?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    match unit.to_ascii_lowercase() {
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{EnumValue, TaskProject};
+    use crate::test_support::domain_task;
+
+    fn parse(input: &str) -> TaskQuery {
+        TaskQuery::parse(input).unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2025-06-10T12:00:00Z".parse().unwrap()
+    }
+
+    fn due_in(days: i64) -> Task {
+        let mut task = domain_task("1", "Due");
+        task.due = Some(Due::At(now() + Duration::days(days)));
+        task
+    }
+
+    #[test]
+    fn test_parse_splits_text_and_filters() {
+        let query = parse("fix -tag:wontfix project:\"Q3 Launch\" login overdue 10:30");
+
+        assert_eq!(query.text, "fix login 10:30");
+        assert_eq!(
+            query.filters,
+            vec![
+                QueryFilter::Not(Box::new(QueryFilter::Tag("wontfix".to_string()))),
+                QueryFilter::Project("Q3 Launch".to_string()),
+                QueryFilter::Overdue,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(
+            parse("due:<7d due:>2w due:2025-01-31 due:none").filters,
+            vec![
+                QueryFilter::Due(DueCondition::Within(Duration::days(7))),
+                QueryFilter::Due(DueCondition::Beyond(Duration::weeks(2))),
+                QueryFilter::Due(DueCondition::On(
+                    NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()
+                )),
+                QueryFilter::Due(DueCondition::None),
+            ]
+        );
+        assert_eq!(
+            parse("assignee:me is:milestone cf:Priority=High").filters,
+            vec![
+                QueryFilter::Assignee(AssigneeMatch::Me),
+                QueryFilter::Is(TaskKind::Milestone),
+                QueryFilter::CustomField {
+                    name: "Priority".to_string(),
+                    value: "High".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_token() {
+        let error = TaskQuery::parse("login due:soon").unwrap_err();
+        assert_eq!(error.position, 6);
+        assert!(error.message.contains("due:soon"));
+
+        assert!(TaskQuery::parse("project:").is_err());
+        assert!(TaskQuery::parse("is:blocked").is_err());
+        assert!(TaskQuery::parse("cf:Priority").is_err());
+        assert!(TaskQuery::parse("due:7d").is_err());
+        assert!(TaskQuery::parse("due:<99999999999999999d").is_err());
+        assert_eq!(TaskQuery::parse("tag:\"open").unwrap_err().position, 4);
+        assert_eq!(TaskQuery::parse("café due:soon").unwrap_err().position, 5);
+    }
+
+    #[test]
+    fn test_due_filters() {
+        let query = parse("due:<7d");
+        assert!(query.matches(&due_in(3), None, now()));
+        assert!(query.matches(&due_in(-3), None, now()));
+        assert!(!query.matches(&due_in(10), None, now()));
+        assert!(!query.matches(&domain_task("1", "No due"), None, now()));
+        // Past the end of time: matches nothing rather than overflowing
+        assert!(!parse("due:<99999999999d").matches(&due_in(3), None, now()));
+
+        let overdue = parse("overdue");
+        assert!(overdue.matches(&due_in(-1), None, now()));
+        assert!(!overdue.matches(&due_in(1), None, now()));
+
+        let mut all_day = domain_task("1", "All day");
+        all_day.due = Some(Due::On(NaiveDate::from_ymd_opt(2025, 6, 8).unwrap()));
+        assert!(overdue.matches(&all_day, None, now()));
+        all_day.completed = true;
+        assert!(!overdue.matches(&all_day, None, now()));
+
+        assert!(parse("due:none").matches(&domain_task("1", "No due"), None, now()));
+        assert!(parse("due:today").matches(&due_in(0), None, now()));
+    }
+
+    #[test]
+    fn test_task_field_filters() {
+        let me = UserId::from("u1");
+        let mut task = domain_task("1", "Plan");
+        task.assignee = Some(me.clone());
+        task.tags = vec!["bug".to_string()];
+        task.resource_subtype = Some("milestone".to_string());
+        task.projects = vec![TaskProject {
+            gid: "42".to_string(),
+            name: "Roadmap 2025".to_string(),
+            color: None,
+        }];
+        task.custom_fields = vec![CustomField {
+            gid: "cf1".to_string(),
+            name: "Priority".to_string(),
+            display_value: Some("High".to_string()),
+            text_value: None,
+            number_value: None,
+            enum_value: Some(EnumValue {
+                gid: "e1".to_string(),
+                name: "High".to_string(),
+                color: None,
+            }),
+        }];
+
+        let query =
+            parse("project:roadmap project:42 tag:BUG assignee:me is:milestone cf:priority=high");
+        assert!(query.matches(&task, Some(&me), now()));
+
+        assert!(!parse("-tag:bug").matches(&task, Some(&me), now()));
+        assert!(!parse("assignee:me").matches(&task, None, now()));
+        assert!(!parse("cf:Priority=Low").matches(&task, Some(&me), now()));
+        assert!(!parse("project:Other").matches(&task, Some(&me), now()));
+    }
+
+    #[test]
+    fn test_push_down() {
+        let base = TaskFilter {
+            workspace: Some("w1".into()),
+            assignee: Some("u1".into()),
+            ..Default::default()
+        };
+
+        let by_name = parse("project:Roadmap").push_down(&base);
+        assert_eq!(by_name.to_cache_key(), base.to_cache_key());
+
+        let by_gid = parse("project:42 is:done").push_down(&base);
+        assert_eq!(by_gid.project, Some(ProjectId::from("42")));
+        assert_eq!(by_gid.assignee, None);
+        assert_eq!(by_gid.workspace, None);
+        assert_eq!(by_gid.completed, None);
+    }
+}
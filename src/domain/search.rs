@@ -0,0 +1,224 @@
+use super::{ProjectId, TaskFilter, UserId};
+use chrono::NaiveDate;
+use std::str::FromStr;
+
+/// A workspace-wide search run by Asana rather than over a fetched listing.
+///
+/// Every condition must hold; the `Vec` fields match tasks with *any* of
+/// their entries. Asana returns at most 100 results and has no cursor, so
+/// narrow the search rather than paging through it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkspaceSearch {
+    /// Matched against task names and descriptions
+    pub text: Option<String>,
+    pub assignees: Vec<UserId>,
+    pub projects: Vec<ProjectId>,
+    /// Tag gids
+    pub tags: Vec<String>,
+    /// Only tasks due before this day
+    pub due_before: Option<NaiveDate>,
+    /// Only tasks due after this day
+    pub due_after: Option<NaiveDate>,
+    pub completed: Option<bool>,
+    pub custom_fields: Vec<CustomFieldPredicate>,
+    /// Asana's order; `None` leaves its default, most recently modified first
+    pub sort_by: Option<SearchSort>,
+    pub sort_ascending: bool,
+    /// Capped at [`Self::MAX_RESULTS`]
+    pub limit: Option<usize>,
+}
+
+impl WorkspaceSearch {
+    /// The most results the search endpoint returns for one query
+    pub const MAX_RESULTS: usize = 100;
+
+    /// How many results to ask for
+    pub fn result_limit(&self) -> usize {
+        self.limit
+            .unwrap_or(Self::MAX_RESULTS)
+            .min(Self::MAX_RESULTS)
+    }
+}
+
+/// The server-side part of a listing filter whose `search_query` is set;
+/// the tag is a name rather than a gid, so it is left to [`TaskFilter::matches`]
+impl From<&TaskFilter> for WorkspaceSearch {
+    fn from(filter: &TaskFilter) -> Self {
+        Self {
+            text: filter.search_query.clone(),
+            assignees: filter.assignee.iter().cloned().collect(),
+            projects: filter.project.iter().cloned().collect(),
+            due_before: filter.due_before,
+            due_after: filter.due_after,
+            completed: filter.completed,
+            // Sorting and the limit are applied after local filtering
+            limit: None,
+            ..Default::default()
+        }
+    }
+}
+
+/// A condition on one custom field, written `GID=VALUE`, `GID~TEXT`,
+/// `GID<NUMBER`, `GID>NUMBER` or `GID?` (has any value)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomFieldPredicate {
+    /// Custom field gid
+    pub field: String,
+    pub condition: FieldCondition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldCondition {
+    /// Exact value; an enum option gid for enum fields
+    Value(String),
+    /// Text fields containing this
+    Contains(String),
+    LessThan(f64),
+    GreaterThan(f64),
+    IsSet,
+}
+
+impl FromStr for CustomFieldPredicate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!("Invalid custom field condition '{s}' (expected GID=VALUE, GID~TEXT, GID<N, GID>N or GID?)")
+        };
+
+        const OPERATORS: [char; 4] = ['=', '~', '<', '>'];
+
+        // A `?` ending a value (`123~why?`) is part of the value
+        let is_set = s
+            .strip_suffix('?')
+            .filter(|field| !field.contains(OPERATORS));
+        let (field, condition) = if let Some(field) = is_set {
+            (field, FieldCondition::IsSet)
+        } else {
+            let at = s.find(OPERATORS).ok_or_else(invalid)?;
+            let (field, rest) = s.split_at(at);
+            let (operator, value) = rest.split_at(1);
+            let number = || value.trim().parse::<f64>().map_err(|_| invalid());
+            let condition = match operator {
+                "=" => FieldCondition::Value(value.to_string()),
+                "~" => FieldCondition::Contains(value.to_string()),
+                "<" => FieldCondition::LessThan(number()?),
+                _ => FieldCondition::GreaterThan(number()?),
+            };
+            (field, condition)
+        };
+
+        let field = field.trim();
+        if field.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            field: field.to_string(),
+            condition,
+        })
+    }
+}
+
+/// The orders the search endpoint can return results in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSort {
+    DueDate,
+    CreatedAt,
+    CompletedAt,
+    Likes,
+    ModifiedAt,
+}
+
+impl SearchSort {
+    pub const NAMES: [&'static str; 5] = ["due", "created", "completed", "likes", "modified"];
+
+    /// The `sort_by` value Asana expects
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            SearchSort::DueDate => "due_date",
+            SearchSort::CreatedAt => "created_at",
+            SearchSort::CompletedAt => "completed_at",
+            SearchSort::Likes => "likes",
+            SearchSort::ModifiedAt => "modified_at",
+        }
+    }
+}
+
+impl FromStr for SearchSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "due" => Ok(SearchSort::DueDate),
+            "created" => Ok(SearchSort::CreatedAt),
+            "completed" => Ok(SearchSort::CompletedAt),
+            "likes" => Ok(SearchSort::Likes),
+            "modified" => Ok(SearchSort::ModifiedAt),
+            _ => Err(format!(
+                "Unknown sort '{s}' (expected one of {})",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn predicate(s: &str) -> CustomFieldPredicate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_custom_field_predicates() {
+        assert_eq!(
+            predicate("123=456"),
+            CustomFieldPredicate {
+                field: "123".to_string(),
+                condition: FieldCondition::Value("456".to_string()),
+            }
+        );
+        assert_eq!(
+            predicate("123~needs review").condition,
+            FieldCondition::Contains("needs review".to_string())
+        );
+        assert_eq!(predicate("123<5").condition, FieldCondition::LessThan(5.0));
+        assert_eq!(
+            predicate("123>2.5").condition,
+            FieldCondition::GreaterThan(2.5)
+        );
+        assert_eq!(predicate("123?").condition, FieldCondition::IsSet);
+        assert_eq!(
+            predicate("123~why?"),
+            CustomFieldPredicate {
+                field: "123".to_string(),
+                condition: FieldCondition::Contains("why?".to_string()),
+            }
+        );
+
+        for bad in ["123", "=5", "123<many", "?"] {
+            assert!(bad.parse::<CustomFieldPredicate>().is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn test_from_filter_carries_the_server_side_conditions() {
+        let filter = TaskFilter {
+            workspace: Some("w1".into()),
+            project: Some("p1".into()),
+            search_query: Some("launch".to_string()),
+            tag: Some("bug".to_string()),
+            limit: Some(10),
+            ..Default::default()
+        };
+
+        let search = WorkspaceSearch::from(&filter);
+        assert_eq!(search.text.as_deref(), Some("launch"));
+        assert_eq!(search.projects, vec![ProjectId("p1".to_string())]);
+        assert!(search.assignees.is_empty() && search.tags.is_empty());
+        assert_eq!(search.completed, Some(false));
+        assert_eq!(search.result_limit(), WorkspaceSearch::MAX_RESULTS);
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SectionId(pub String);
+
+impl fmt::Display for SectionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for SectionId {
+    fn from(s: String) -> Self {
+        SectionId(s)
+    }
+}
+
+impl From<&str> for SectionId {
+    fn from(s: &str) -> Self {
+        SectionId(s.to_string())
+    }
+}
+
+/// A named group of tasks within a project; a column on a board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Section {
+    pub id: SectionId,
+    pub name: String,
+    pub project: super::ProjectId,
+}
@@ -1,4 +1,5 @@
-use chrono::{DateTime, Utc};
+use super::{Due, Zone};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -29,7 +30,9 @@ pub struct Task {
     pub name: String,
     pub description: Option<String>,
     pub completed: bool,
-    pub due_date: Option<DateTime<Utc>>,
+    pub due: Option<Due>,
+    /// First day of work on the task, when it spans several days
+    pub start_on: Option<NaiveDate>,
     pub assignee: Option<super::UserId>,
     pub assignee_name: Option<String>,
     pub projects: Vec<TaskProject>,
@@ -41,6 +44,11 @@ pub struct Task {
     pub resource_subtype: Option<String>,
     pub custom_fields: Vec<CustomField>,
     pub dependencies: Vec<TaskDependency>,
+    /// The section the task sits in for each of its projects
+    pub memberships: Vec<TaskMembership>,
+    /// The task this is a subtask of
+    pub parent: Option<TaskId>,
+    pub num_subtasks: usize,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -74,12 +82,36 @@ pub struct TaskProject {
     pub color: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskMembership {
+    pub project: super::ProjectId,
+    pub section: Option<TaskSection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskSection {
+    pub id: super::SectionId,
+    pub name: String,
+}
+
 impl Task {
+    /// The section this task is in within `project`, if it belongs to one
+    pub fn section_in(&self, project: &super::ProjectId) -> Option<&TaskSection> {
+        self.memberships
+            .iter()
+            .find(|m| &m.project == project)
+            .and_then(|m| m.section.as_ref())
+    }
+
     /// Business rule: determine if task is overdue
     pub fn is_overdue(&self) -> bool {
-        self.due_date
-            .map(|due| due < Utc::now() && !self.completed)
-            .unwrap_or(false)
+        self.is_overdue_at(Utc::now(), Zone::current())
+    }
+
+    /// An incomplete task is overdue once its due moment, or the whole of its
+    /// due day in `zone`, has passed
+    pub fn is_overdue_at(&self, now: DateTime<Utc>, zone: Zone) -> bool {
+        !self.completed && self.due.is_some_and(|due| due.is_past(now, zone))
     }
 
     /// Business rule: get display status with color
@@ -93,27 +125,16 @@ impl Task {
 
     /// Format due date for display
     pub fn due_date_display(&self) -> String {
-        match self.due_date {
+        match self.due {
             None => "No due date".to_string(),
-            Some(due) => {
-                let now = Utc::now();
-                let days_diff = (due.date_naive() - now.date_naive()).num_days();
-
-                match days_diff {
-                    0 => "Today".to_string(),
-                    1 => "Tomorrow".to_string(),
-                    -1 => "Yesterday".to_string(),
-                    d if d < 0 => {
-                        let days = -d;
-                        format!("{days} days ago")
-                    }
-                    d if d <= 7 => format!("In {d} days"),
-                    _ => due.format("%Y-%m-%d").to_string(),
-                }
-            }
+            Some(due) => due.display(Utc::now(), Zone::current()),
         }
     }
 
+    pub fn has_subtasks(&self) -> bool {
+        self.num_subtasks > 0
+    }
+
     /// Check if this task is a milestone
     pub fn is_milestone(&self) -> bool {
         self.resource_subtype.as_deref() == Some("milestone")
@@ -132,14 +153,8 @@ impl Task {
     pub fn icon_color(&self) -> ratatui::style::Color {
         use ratatui::style::Color;
 
-        if let Some(due_date) = self.due_date {
-            let now = chrono::Utc::now();
-            // Red if due before today (not including today)
-            if due_date.date_naive() < now.date_naive() && !self.completed {
-                Color::Red
-            } else {
-                Color::Green
-            }
+        if self.is_overdue() {
+            Color::Red
         } else {
             Color::Green
         }
@@ -150,20 +165,101 @@ impl Task {
 pub struct TaskUpdate {
     pub name: Option<String>,
     pub description: Option<String>,
+    /// Rich text description, sent as `html_notes` in place of `description`
+    pub html_description: Option<String>,
     pub completed: Option<bool>,
-    pub due_date: Option<Option<DateTime<Utc>>>,
+    pub due: Option<Option<Due>>,
+    pub start_on: Option<Option<NaiveDate>>,
     pub assignee: Option<Option<super::UserId>>,
 }
 
-#[derive(Debug, Clone)]
+impl TaskUpdate {
+    /// Show the update on a local copy of the task before Asana confirms it.
+    /// A new assignee's name isn't known here, so it is cleared.
+    pub fn apply_to(&self, task: &mut Task) {
+        if let Some(name) = &self.name {
+            task.name = name.clone();
+        }
+        if let Some(description) = self.html_description.as_ref().or(self.description.as_ref()) {
+            task.description = Some(description.clone());
+        }
+        if let Some(completed) = self.completed {
+            task.completed = completed;
+        }
+        if let Some(due) = self.due {
+            task.due = due;
+        }
+        if let Some(start_on) = self.start_on {
+            task.start_on = start_on;
+        }
+        if let Some(assignee) = &self.assignee {
+            if &task.assignee != assignee {
+                task.assignee = assignee.clone();
+                task.assignee_name = None;
+            }
+        }
+    }
+
+    /// Undo [`Self::apply_to`] on `task`, putting back the fields this update
+    /// touches as they were in `before` and leaving the rest alone
+    pub fn revert(&self, task: &mut Task, before: &Task) {
+        if self.name.is_some() {
+            task.name = before.name.clone();
+        }
+        if self.html_description.is_some() || self.description.is_some() {
+            task.description = before.description.clone();
+        }
+        if self.completed.is_some() {
+            task.completed = before.completed;
+        }
+        if self.due.is_some() {
+            task.due = before.due;
+        }
+        if self.start_on.is_some() {
+            task.start_on = before.start_on;
+        }
+        if self.assignee.is_some() {
+            task.assignee = before.assignee.clone();
+            task.assignee_name = before.assignee_name.clone();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NewTask {
+    pub name: String,
+    pub description: Option<String>,
+    /// Required by Asana unless at least one project or a parent is given
+    pub workspace: Option<super::WorkspaceId>,
+    /// Create the task as a subtask of this one
+    pub parent: Option<TaskId>,
+    pub projects: Vec<super::ProjectId>,
+    pub assignee: Option<super::UserId>,
+    pub due: Option<Due>,
+    pub start_on: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskFilter {
     pub workspace: Option<super::WorkspaceId>,
     pub project: Option<super::ProjectId>,
     pub assignee: Option<super::UserId>,
     pub completed: Option<bool>,
     pub search_query: Option<String>,
+    /// Tag name (case-insensitive); `/tasks` cannot filter by tag, so this is always applied locally
+    pub tag: Option<String>,
+    /// Also include tasks completed at or after this moment
+    pub completed_since: Option<DateTime<Utc>>,
+    pub modified_since: Option<DateTime<Utc>>,
+    /// Only tasks due before this day
+    pub due_before: Option<NaiveDate>,
+    /// Only tasks due after this day
+    pub due_after: Option<NaiveDate>,
+    pub sort: TaskSort,
+    /// Maximum number of tasks to return across all pages; `None` fetches everything
     pub limit: Option<usize>,
-    pub offset: Option<usize>,
+    /// Opaque pagination cursor returned by a previous `Page::next_offset`
+    pub offset: Option<String>,
 }
 
 impl Default for TaskFilter {
@@ -174,6 +270,12 @@ impl Default for TaskFilter {
             assignee: None,
             completed: Some(false), // Default to incomplete tasks
             search_query: None,
+            tag: None,
+            completed_since: None,
+            modified_since: None,
+            due_before: None,
+            due_after: None,
+            sort: TaskSort::default(),
             limit: Some(50),
             offset: None,
         }
@@ -181,9 +283,49 @@ impl Default for TaskFilter {
 }
 
 impl TaskFilter {
+    /// Whether part of this filter can only be checked locally. The API scopes a
+    /// listing by project *or* by assignee + workspace and has no tag, due date or
+    /// completed-only filter, so those need every page fetched and [`Self::matches`] applied.
+    pub fn needs_local_filtering(&self) -> bool {
+        self.tag.is_some()
+            || self.due_before.is_some()
+            || self.due_after.is_some()
+            || self.completed == Some(true)
+            || (self.project.is_some() && self.assignee.is_some())
+    }
+
+    /// Whether `task` passes every condition here, with due dates taken as days in `zone`
+    pub fn matches(&self, task: &Task, zone: Zone) -> bool {
+        let due_date = task.due.map(|due| due.date(zone));
+
+        self.completed
+            .is_none_or(|completed| task.completed == completed)
+            && self
+                .assignee
+                .as_ref()
+                .is_none_or(|assignee| task.assignee.as_ref() == Some(assignee))
+            && self
+                .project
+                .as_ref()
+                .is_none_or(|project| task.projects.iter().any(|p| p.gid == project.0))
+            && self
+                .tag
+                .as_ref()
+                .is_none_or(|tag| task.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            && self
+                .modified_since
+                .is_none_or(|since| task.modified_at >= since)
+            && self
+                .due_before
+                .is_none_or(|before| due_date.is_some_and(|due| due < before))
+            && self
+                .due_after
+                .is_none_or(|after| due_date.is_some_and(|due| due > after))
+    }
+
     pub fn to_cache_key(&self) -> String {
         format!(
-            "tasks:{}:{}:{}:{}:{}",
+            "tasks:{}:{}:{}:{}:{}:{}:{}:{}:{}:{}:{}:{}:{:?}",
             self.workspace
                 .as_ref()
                 .map(|w| w.0.as_str())
@@ -197,6 +339,151 @@ impl TaskFilter {
                 .map(|c| c.to_string())
                 .unwrap_or_else(|| "all".to_string()),
             self.search_query.as_deref().unwrap_or(""),
+            self.tag.as_deref().unwrap_or(""),
+            self.completed_since
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default(),
+            self.modified_since
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default(),
+            self.due_before.map(|d| d.to_string()).unwrap_or_default(),
+            self.due_after.map(|d| d.to_string()).unwrap_or_default(),
+            self.limit
+                .map(|l| l.to_string())
+                .unwrap_or_else(|| "all".to_string()),
+            self.offset.as_deref().unwrap_or(""),
+            self.sort,
         )
     }
 }
+
+/// Order of a task listing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskSort {
+    /// Soonest first, undated tasks last
+    #[default]
+    Due,
+    Name,
+    /// Newest first
+    Created,
+    /// Most recently modified first
+    Modified,
+}
+
+impl TaskSort {
+    pub const NAMES: [&'static str; 4] = ["due", "name", "created", "modified"];
+
+    pub fn sort(&self, tasks: &mut [Task], zone: Zone) {
+        match self {
+            TaskSort::Due => tasks
+                .sort_by_key(|task| (task.due.is_none(), task.due.map(|due| due.sort_key(zone)))),
+            TaskSort::Name => tasks.sort_by_key(|task| task.name.to_lowercase()),
+            TaskSort::Created => tasks.sort_by_key(|task| std::cmp::Reverse(task.created_at)),
+            TaskSort::Modified => tasks.sort_by_key(|task| std::cmp::Reverse(task.modified_at)),
+        }
+    }
+}
+
+impl std::str::FromStr for TaskSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "due" => Ok(TaskSort::Due),
+            "name" => Ok(TaskSort::Name),
+            "created" => Ok(TaskSort::Created),
+            "modified" => Ok(TaskSort::Modified),
+            _ => Err(format!(
+                "Unknown sort '{s}' (expected one of {})",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::domain_task;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn task(gid: &str, name: &str, due: Option<&str>) -> Task {
+        let mut task = domain_task(gid, name);
+        task.due = due.map(|d| Due::On(date(d)));
+        task
+    }
+
+    #[test]
+    fn test_filter_matches_locally_checked_conditions() {
+        let zone: Zone = "UTC".parse().unwrap();
+        let mut tagged = task("1", "Tagged", Some("2026-10-20"));
+        tagged.tags = vec!["Bug".to_string()];
+
+        let filter = TaskFilter {
+            tag: Some("bug".to_string()),
+            due_after: Some(date("2026-10-19")),
+            due_before: Some(date("2026-10-21")),
+            ..Default::default()
+        };
+        assert!(filter.needs_local_filtering());
+        assert!(filter.matches(&tagged, zone));
+        assert!(!filter.matches(&task("2", "Untagged", Some("2026-10-20")), zone));
+
+        tagged.due = None;
+        assert!(!filter.matches(&tagged, zone));
+
+        tagged.completed = true;
+        let completed = TaskFilter {
+            completed: Some(true),
+            ..Default::default()
+        };
+        assert!(completed.matches(&tagged, zone));
+        assert!(!TaskFilter::default().matches(&tagged, zone));
+        assert!(!TaskFilter::default().needs_local_filtering());
+    }
+
+    #[test]
+    fn test_updates_apply_and_revert_only_their_fields() {
+        let before = task("1", "Original", Some("2026-10-20"));
+        let update = TaskUpdate {
+            completed: Some(true),
+            due: Some(None),
+            ..Default::default()
+        };
+
+        let mut shown = before.clone();
+        update.apply_to(&mut shown);
+        assert!(shown.completed);
+        assert_eq!(shown.due, None);
+
+        // A later rename survives rolling back the earlier update
+        shown.name = "Renamed".to_string();
+        update.revert(&mut shown, &before);
+        assert!(!shown.completed);
+        assert_eq!(shown.due, before.due);
+        assert_eq!(shown.name, "Renamed");
+    }
+
+    #[test]
+    fn test_sort_orders() {
+        let zone: Zone = "UTC".parse().unwrap();
+        let mut tasks = vec![
+            task("1", "beta", None),
+            task("2", "Alpha", Some("2026-10-20")),
+            task("3", "gamma", Some("2026-10-18")),
+        ];
+        tasks[0].modified_at += chrono::Duration::hours(1);
+
+        let ids = |tasks: &[Task]| tasks.iter().map(|t| t.id.0.clone()).collect::<Vec<_>>();
+
+        TaskSort::Due.sort(&mut tasks, zone);
+        assert_eq!(ids(&tasks), ["3", "2", "1"]);
+        TaskSort::Name.sort(&mut tasks, zone);
+        assert_eq!(ids(&tasks), ["2", "1", "3"]);
+        TaskSort::Modified.sort(&mut tasks, zone);
+        assert_eq!(ids(&tasks)[0], "1");
+    }
+}
@@ -1,4 +1,4 @@
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command};
 use color_eyre::Result;
 use std::sync::Arc;
 
@@ -6,16 +6,27 @@ mod adapters;
 mod application;
 mod domain;
 mod ports;
+#[cfg(test)]
+mod test_support;
 
 use adapters::{
-    api::{AsanaClient, AsanaTaskRepository},
-    cache::MokaCacheAdapter,
+    api::{AsanaClient, AsanaTaskRepository, RetryPolicy, DEFAULT_API_BASE},
+    cache::{default_cache_dir, DiskCacheAdapter, MokaCacheAdapter},
     config::FileConfigStore,
+    outbox::FileOutbox,
+    output::{Output, OutputFormat, Record, Template},
     tui::{run_tui, App},
 };
-use application::{AppError, StateManager, TaskService};
+use application::{AppError, OfflineStore, StateManager, TaskService};
+use domain::{
+    parse_date, parse_due_date, NewTask, SearchSort, TaskFilter, TaskSort, TaskUpdate, UserId,
+    WorkspaceSearch, Zone,
+};
 use ports::ConfigStore;
 
+/// How long cached task lists are kept on disk for a quick start
+const DISK_CACHE_RETENTION: std::time::Duration = std::time::Duration::from_secs(30 * 24 * 60 * 60);
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize color-eyre for better error reporting
@@ -36,7 +47,7 @@ async fn main() -> Result<()> {
     let matches = Command::new("asana-cli")
         .version("0.1.0")
         .about("A Terminal User Interface for Asana")
-        .long_about("A fast, keyboard-driven terminal interface for managing Asana tasks.\n\nIf you have only one workspace, it will be auto-selected.\nFor multiple workspaces, specify one with --workspace.")
+        .long_about("A fast, keyboard-driven terminal interface for managing Asana tasks.\n\nIf you have only one workspace, it will be auto-selected.\nWith several, pick one when the TUI starts (w switches later) or pass --workspace.")
         .arg(
             Arg::new("token")
                 .long("token")
@@ -44,6 +55,13 @@ async fn main() -> Result<()> {
                 .help("Asana API token (can also be set via ASANA_TOKEN env var)")
                 .global(true)
         )
+        .arg(
+            Arg::new("api_base")
+                .long("api-base")
+                .value_name("URL")
+                .help("Asana API base URL (can also be set via ASANA_API_BASE env var)")
+                .global(true)
+        )
         .arg(
             Arg::new("workspace")
                 .long("workspace")
@@ -51,12 +69,107 @@ async fn main() -> Result<()> {
                 .help("Workspace ID (required only if you have multiple workspaces)")
                 .global(true)
         )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .short('o')
+                .value_name("FORMAT")
+                .value_parser(OutputFormat::NAMES)
+                .help("Output format [default: table on a terminal, json otherwise]")
+                .global(true)
+        )
+        .arg(
+            Arg::new("fields")
+                .long("fields")
+                .value_name("FIELDS")
+                .value_delimiter(',')
+                .help("Comma-separated columns to print; task custom fields by name or as cf:NAME")
+                .global(true)
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("TEMPLATE")
+                .help("Print each result through a template, e.g. '{{id}}\\t{{name | truncate 40}}\\t{{due | date \"%a\" | default \"-\"}}'")
+                .long_help("Print each result through a template instead of --output, one line per result.\n\n{{FIELD}} inserts any name --fields accepts (quote names with spaces). Helpers chain with '|':\n  date \"FORMAT\"    strftime-style date formatting, in the configured time zone\n  truncate N       cut to N characters\n  default \"TEXT\"   used when the value is empty\n  pad N            right-pad to N columns\n\\t and \\n in the text become a tab and a newline.\n\nExample: --format '{{id}}\\t{{name | truncate 40}}\\t{{due | date \"%a %d\" | default \"-\"}}'")
+                .conflicts_with_all(["output", "fields"])
+                .global(true)
+        )
         .subcommand(
             Command::new("tasks")
                 .about("Task operations")
                 .subcommand(
                     Command::new("list")
-                        .about("List tasks as JSON")
+                        .about("List tasks (yours and incomplete unless filtered otherwise)")
+                        .arg(
+                            Arg::new("assignee")
+                                .long("assignee")
+                                .value_name("USER_ID")
+                                .help("Assignee user ID, or 'me' (the default without --project)")
+                        )
+                        .arg(
+                            Arg::new("project")
+                                .long("project")
+                                .value_name("PROJECT_ID")
+                                .help("Only tasks in this project")
+                        )
+                        .arg(
+                            Arg::new("tag")
+                                .long("tag")
+                                .value_name("NAME")
+                                .help("Only tasks with this tag")
+                        )
+                        .arg(
+                            Arg::new("completed")
+                                .long("completed")
+                                .help("Only completed tasks")
+                                .action(ArgAction::SetTrue)
+                                .conflicts_with("all")
+                        )
+                        .arg(
+                            Arg::new("all")
+                                .long("all")
+                                .help("Both complete and incomplete tasks")
+                                .action(ArgAction::SetTrue)
+                        )
+                        .arg(
+                            Arg::new("completed_since")
+                                .long("completed-since")
+                                .value_name("DATE")
+                                .help("Include tasks completed on or after DATE (YYYY-MM-DD, today, fri, +3d, ...)")
+                        )
+                        .arg(
+                            Arg::new("modified_since")
+                                .long("modified-since")
+                                .value_name("DATE")
+                                .help("Only tasks modified on or after DATE")
+                        )
+                        .arg(
+                            Arg::new("due_before")
+                                .long("due-before")
+                                .value_name("DATE")
+                                .help("Only tasks due before DATE")
+                        )
+                        .arg(
+                            Arg::new("due_after")
+                                .long("due-after")
+                                .value_name("DATE")
+                                .help("Only tasks due after DATE")
+                        )
+                        .arg(
+                            Arg::new("limit")
+                                .long("limit")
+                                .value_name("N")
+                                .value_parser(clap::value_parser!(usize))
+                                .help("Return at most N tasks")
+                        )
+                        .arg(
+                            Arg::new("sort")
+                                .long("sort")
+                                .value_name("ORDER")
+                                .value_parser(TaskSort::NAMES)
+                                .help("Sort by due date (default), name, or newest created/modified first")
+                        )
                 )
                 .subcommand(
                     Command::new("get")
@@ -68,6 +181,115 @@ async fn main() -> Result<()> {
                                 .index(1)
                         )
                 )
+                .subcommand(
+                    Command::new("create")
+                        .about("Create a task and print it")
+                        .arg(
+                            Arg::new("name")
+                                .long("name")
+                                .value_name("NAME")
+                                .help("Task name")
+                                .required(true)
+                        )
+                        .arg(
+                            Arg::new("notes")
+                                .long("notes")
+                                .value_name("TEXT")
+                                .help("Plain-text description")
+                        )
+                        .arg(
+                            Arg::new("project")
+                                .long("project")
+                                .value_name("PROJECT_ID")
+                                .help("Project to add the task to (repeatable)")
+                                .action(ArgAction::Append)
+                        )
+                        .arg(
+                            Arg::new("due")
+                                .long("due")
+                                .value_name("DATE")
+                                .help("Due date: YYYY-MM-DD, today, tomorrow, a weekday like 'fri', or +3d/+2w")
+                        )
+                        .arg(
+                            Arg::new("start")
+                                .long("start")
+                                .value_name("DATE")
+                                .help("Start date, in the same forms as --due")
+                        )
+                        .arg(
+                            Arg::new("assignee")
+                                .long("assignee")
+                                .value_name("USER_ID")
+                                .help("Assignee user ID, or 'me'")
+                        )
+                        .arg(
+                            Arg::new("parent")
+                                .long("parent")
+                                .value_name("TASK_ID")
+                                .help("Create the task as a subtask of this task")
+                        )
+                )
+                .subcommand(
+                    Command::new("update")
+                        .about("Update a task and print it")
+                        .arg(
+                            Arg::new("task_id")
+                                .help("Task ID to update")
+                                .required(true)
+                                .index(1)
+                        )
+                        .arg(
+                            Arg::new("name")
+                                .long("name")
+                                .value_name("NAME")
+                                .help("New task name")
+                        )
+                        .arg(
+                            Arg::new("assignee")
+                                .long("assignee")
+                                .value_name("USER_ID")
+                                .help("Assignee user ID, 'me', or 'none' to unassign")
+                        )
+                        .arg(
+                            Arg::new("due")
+                                .long("due")
+                                .value_name("DATE")
+                                .help("Due date: YYYY-MM-DD, today, tomorrow, a weekday like 'fri', +3d/+2w, or 'none' to clear")
+                        )
+                        .arg(
+                            Arg::new("start")
+                                .long("start")
+                                .value_name("DATE")
+                                .help("Start date, in the same forms as --due, or 'none' to clear")
+                        )
+                )
+                .subcommand(
+                    Command::new("subtasks")
+                        .about("List a task's subtasks")
+                        .arg(
+                            Arg::new("task_id")
+                                .help("Parent task ID")
+                                .required(true)
+                                .index(1)
+                        )
+                )
+                .subcommand(
+                    Command::new("move")
+                        .about("Move a task into a project section")
+                        .arg(
+                            Arg::new("task_id")
+                                .help("Task ID to move")
+                                .required(true)
+                                .index(1)
+                        )
+                        .arg(
+                            Arg::new("section")
+                                .long("section")
+                                .value_name("SECTION_ID")
+                                .help("Section to move the task into")
+                                .required(true)
+                        )
+                )
                 .subcommand(
                     Command::new("stories")
                         .about("List stories/comments for a task")
@@ -79,6 +301,48 @@ async fn main() -> Result<()> {
                         )
                 )
         )
+        .subcommand(
+            Command::new("workspaces")
+                .about("Workspace operations")
+                .subcommand(
+                    Command::new("list")
+                        .about("List the workspaces you belong to")
+                )
+        )
+        .subcommand(
+            Command::new("projects")
+                .about("Project operations")
+                .subcommand(
+                    Command::new("list")
+                        .about("List the projects in the workspace")
+                        .arg(
+                            Arg::new("archived")
+                                .long("archived")
+                                .help("Include archived projects")
+                                .action(ArgAction::SetTrue)
+                        )
+                )
+                .subcommand(
+                    Command::new("get")
+                        .about("Get a specific project by ID")
+                        .arg(
+                            Arg::new("project_id")
+                                .help("Project ID to fetch")
+                                .required(true)
+                                .index(1)
+                        )
+                )
+                .subcommand(
+                    Command::new("sections")
+                        .about("List a project's sections")
+                        .arg(
+                            Arg::new("project_id")
+                                .help("Project ID to list sections for")
+                                .required(true)
+                                .index(1)
+                        )
+                )
+        )
         .subcommand(
             Command::new("stories")
                 .about("Story/comment operations")
@@ -95,6 +359,89 @@ async fn main() -> Result<()> {
                         )
                 )
         )
+        .subcommand(
+            Command::new("search")
+                .about("Search every task in the workspace (incomplete unless --completed or --all)")
+                .arg(
+                    Arg::new("text")
+                        .value_name("TEXT")
+                        .help("Words to find in task names and descriptions")
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("assignee")
+                        .long("assignee")
+                        .value_name("USER_ID")
+                        .action(ArgAction::Append)
+                        .help("Assigned to this user, or 'me'; repeat to match any of several")
+                )
+                .arg(
+                    Arg::new("project")
+                        .long("project")
+                        .value_name("PROJECT_ID")
+                        .action(ArgAction::Append)
+                        .help("In this project; repeat to match any of several")
+                )
+                .arg(
+                    Arg::new("tag")
+                        .long("tag")
+                        .value_name("TAG_ID")
+                        .action(ArgAction::Append)
+                        .help("Tagged with this tag; repeat to match any of several")
+                )
+                .arg(
+                    Arg::new("cf")
+                        .long("cf")
+                        .value_name("CONDITION")
+                        .action(ArgAction::Append)
+                        .help("Custom field condition by field ID: ID=VALUE, ID~TEXT, ID<N, ID>N or ID? (has a value); repeatable")
+                )
+                .arg(
+                    Arg::new("due_before")
+                        .long("due-before")
+                        .value_name("DATE")
+                        .help("Only tasks due before DATE (YYYY-MM-DD, today, fri, +3d, ...)")
+                )
+                .arg(
+                    Arg::new("due_after")
+                        .long("due-after")
+                        .value_name("DATE")
+                        .help("Only tasks due after DATE")
+                )
+                .arg(
+                    Arg::new("completed")
+                        .long("completed")
+                        .help("Only completed tasks")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("all")
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .help("Both complete and incomplete tasks")
+                        .action(ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("sort")
+                        .long("sort")
+                        .value_name("ORDER")
+                        .value_parser(SearchSort::NAMES)
+                        .help("Sort by due, created, completed or modified date, or likes (default: modified)")
+                )
+                .arg(
+                    Arg::new("asc")
+                        .long("asc")
+                        .help("Sort ascending instead of newest/most first")
+                        .action(ArgAction::SetTrue)
+                )
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(usize))
+                        .help("Return at most N tasks (Asana returns no more than 100)")
+                )
+        )
         .get_matches();
 
     // Load configuration
@@ -112,6 +459,14 @@ async fn main() -> Result<()> {
         config.default_workspace = Some(workspace.as_str().into());
     }
 
+    // Due dates are shown and compared in the configured zone, else the system one
+    if let Some(name) = &config.time_zone {
+        match name.parse::<Zone>() {
+            Ok(zone) => Zone::init(zone),
+            Err(e) => eprintln!("⚠️  {e}; using the system time zone"),
+        }
+    }
+
     // Check for API token
     let api_token = config.api_token.clone().ok_or_else(|| {
         eprintln!("❌ No Asana API token found!");
@@ -132,8 +487,22 @@ async fn main() -> Result<()> {
     // Save config if we got new values
     config_store.save_config(&config).await?;
 
+    // API base URL overrides are per-invocation and never persisted
+    let api_base = matches
+        .get_one::<String>("api_base")
+        .cloned()
+        .or_else(|| std::env::var("ASANA_API_BASE").ok())
+        .or_else(|| config.api_base_url.clone())
+        .unwrap_or_else(|| DEFAULT_API_BASE.to_string());
+
     // Create dependencies
-    let api_client = AsanaClient::new(api_token);
+    let api_client = AsanaClient::new(api_token, &api_base).with_retry_policy(RetryPolicy {
+        max_attempts: config.retry_max_attempts.max(1),
+        deadline: Some(std::time::Duration::from_secs(
+            config.retry_deadline_seconds,
+        )),
+        ..Default::default()
+    });
     let task_repo = Arc::new(AsanaTaskRepository::new(api_client));
 
     // Create caches
@@ -147,25 +516,90 @@ async fn main() -> Result<()> {
         comment_cache,
     ));
 
-    let state_manager = Arc::new(StateManager::new(
+    // Task lists and the signed-in user persist so the TUI can start from them
+    let task_list_cache = Arc::new(DiskCacheAdapter::new(
+        default_cache_dir("tasks"),
+        DISK_CACHE_RETENTION,
+    ));
+    let session_cache = Arc::new(DiskCacheAdapter::new(
+        default_cache_dir("session"),
+        DISK_CACHE_RETENTION,
+    ));
+
+    let mut state_manager = StateManager::new(
         task_service,
         task_repo.clone(),
+        task_repo.clone(),
+        task_repo.clone(),
         config_store,
-    ));
+        task_list_cache,
+        session_cache,
+    );
+
+    // Only the TUI keeps working offline; a script should hear that a write failed
+    if matches.subcommand().is_none() {
+        match FileOutbox::default_path() {
+            Ok(outbox_path) => {
+                let comments = Arc::new(DiskCacheAdapter::new(
+                    default_cache_dir("comments"),
+                    DISK_CACHE_RETENTION,
+                ));
+                state_manager = state_manager.with_offline_store(OfflineStore::new(
+                    Arc::new(FileOutbox::new(outbox_path)),
+                    comments,
+                ));
+            }
+            Err(e) => tracing::warn!("Offline mode unavailable: {}", e),
+        }
+    }
+    let state_manager = Arc::new(state_manager);
+
+    let mut output = Output::new(
+        matches
+            .get_one::<String>("output")
+            .and_then(|format| format.parse().ok())
+            .unwrap_or_else(OutputFormat::default_for_stdout),
+        matches
+            .get_many::<String>("fields")
+            .map(|fields| fields.cloned().collect()),
+    );
+    if let Some(source) = matches.get_one::<String>("format") {
+        match Template::parse(source) {
+            Ok(template) => output = output.with_template(template),
+            Err(e) => {
+                eprintln!("❌ {e}");
+                std::process::exit(1);
+            }
+        }
+    }
 
     // Handle subcommands
     match matches.subcommand() {
         Some(("tasks", tasks_matches)) => {
             match tasks_matches.subcommand() {
-                Some(("list", _)) => {
+                Some(("list", list_matches)) => {
                     // Initialize state manager
                     state_manager.initialize().await?;
 
+                    let filter = match state_manager.default_task_filter().await {
+                        Ok(base) => base,
+                        Err(e) => {
+                            eprintln!("❌ Failed to list tasks: {e}");
+                            std::process::exit(1);
+                        }
+                    };
+                    let filter = match task_filter_from_args(list_matches, filter) {
+                        Ok(filter) => filter,
+                        Err(e) => {
+                            eprintln!("❌ {e}");
+                            std::process::exit(1);
+                        }
+                    };
+
                     // Get tasks
-                    match state_manager.get_tasks_for_current_workspace(false).await {
+                    match state_manager.get_tasks_with_filter(&filter, false).await {
                         Ok(tasks) => {
-                            let json = serde_json::to_string_pretty(&tasks)?;
-                            println!("{json}");
+                            print_list(&output, &tasks);
                         }
                         Err(e) => {
                             eprintln!("❌ Failed to list tasks: {e}");
@@ -181,8 +615,180 @@ async fn main() -> Result<()> {
                         // Get the specific task
                         match state_manager.get_task(&task_id.as_str().into()).await {
                             Ok(task) => {
-                                let json = serde_json::to_string_pretty(&task)?;
-                                println!("{json}");
+                                print(&output, &task);
+                            }
+                            Err(e) => {
+                                eprintln!("❌ Failed to get task: {e}");
+                                std::process::exit(1);
+                            }
+                        }
+                    }
+                }
+                Some(("create", create_matches)) => {
+                    state_manager.initialize().await?;
+
+                    let due = match create_matches.get_one::<String>("due") {
+                        Some(due) => match parse_due_date(due) {
+                            Ok(due) => Some(due),
+                            Err(e) => {
+                                eprintln!("❌ {e}");
+                                std::process::exit(1);
+                            }
+                        },
+                        None => None,
+                    };
+
+                    let start_on = match create_matches.get_one::<String>("start") {
+                        Some(start) => match parse_date(start) {
+                            Ok(date) => Some(date),
+                            Err(e) => {
+                                eprintln!("❌ {e}");
+                                std::process::exit(1);
+                            }
+                        },
+                        None => None,
+                    };
+
+                    let assignee = match create_matches.get_one::<String>("assignee") {
+                        Some(assignee) if assignee == "me" => {
+                            match state_manager.get_current_user().await {
+                                Some(user) => Some(user.id),
+                                None => {
+                                    eprintln!("❌ Could not determine the current user");
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
+                        Some(assignee) => Some(assignee.as_str().into()),
+                        None => None,
+                    };
+
+                    let new_task = NewTask {
+                        name: create_matches
+                            .get_one::<String>("name")
+                            .cloned()
+                            .unwrap_or_default(),
+                        description: create_matches.get_one::<String>("notes").cloned(),
+                        projects: create_matches
+                            .get_many::<String>("project")
+                            .map(|ids| ids.map(|id| id.as_str().into()).collect())
+                            .unwrap_or_default(),
+                        assignee,
+                        due,
+                        start_on,
+                        parent: create_matches
+                            .get_one::<String>("parent")
+                            .map(|id| id.as_str().into()),
+                        ..Default::default()
+                    };
+
+                    match state_manager.create_task(new_task).await {
+                        Ok(task) => {
+                            print(&output, &task);
+                        }
+                        Err(e) => {
+                            eprintln!("❌ Failed to create task: {e}");
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                Some(("update", update_matches)) => {
+                    state_manager.initialize().await?;
+
+                    let due = match update_matches.get_one::<String>("due") {
+                        Some(due) if due == "none" => Some(None),
+                        Some(due) => match parse_due_date(due) {
+                            Ok(due) => Some(Some(due)),
+                            Err(e) => {
+                                eprintln!("❌ {e}");
+                                std::process::exit(1);
+                            }
+                        },
+                        None => None,
+                    };
+
+                    let start_on = match update_matches.get_one::<String>("start") {
+                        Some(start) if start == "none" => Some(None),
+                        Some(start) => match parse_date(start) {
+                            Ok(date) => Some(Some(date)),
+                            Err(e) => {
+                                eprintln!("❌ {e}");
+                                std::process::exit(1);
+                            }
+                        },
+                        None => None,
+                    };
+
+                    let assignee = match update_matches.get_one::<String>("assignee") {
+                        Some(assignee) if assignee == "none" => Some(None),
+                        // Never send a null assignee for "me"; that would unassign the task
+                        Some(assignee) if assignee == "me" => {
+                            match state_manager.get_current_user().await {
+                                Some(user) => Some(Some(user.id)),
+                                None => {
+                                    eprintln!("❌ Could not determine the current user");
+                                    std::process::exit(1);
+                                }
+                            }
+                        }
+                        Some(assignee) => Some(Some(assignee.as_str().into())),
+                        None => None,
+                    };
+
+                    let update = TaskUpdate {
+                        name: update_matches.get_one::<String>("name").cloned(),
+                        assignee,
+                        due,
+                        start_on,
+                        ..Default::default()
+                    };
+
+                    if let Some(task_id) = update_matches.get_one::<String>("task_id") {
+                        match state_manager
+                            .update_task(&task_id.as_str().into(), &update)
+                            .await
+                        {
+                            Ok(task) => {
+                                print(&output, &task);
+                            }
+                            Err(e) => {
+                                eprintln!("❌ Failed to update task: {e}");
+                                std::process::exit(1);
+                            }
+                        }
+                    }
+                }
+                Some(("subtasks", subtasks_matches)) => {
+                    if let Some(task_id) = subtasks_matches.get_one::<String>("task_id") {
+                        match state_manager.get_subtasks(&task_id.as_str().into()).await {
+                            Ok(subtasks) => {
+                                print_list(&output, &subtasks);
+                            }
+                            Err(e) => {
+                                eprintln!("❌ Failed to list subtasks: {e}");
+                                std::process::exit(1);
+                            }
+                        }
+                    }
+                }
+                Some(("move", move_matches)) => {
+                    if let (Some(task_id), Some(section)) = (
+                        move_matches.get_one::<String>("task_id"),
+                        move_matches.get_one::<String>("section"),
+                    ) {
+                        let task_id = task_id.as_str().into();
+                        if let Err(e) = state_manager
+                            .move_task_to_section(&task_id, &section.as_str().into())
+                            .await
+                        {
+                            eprintln!("❌ Failed to move task: {e}");
+                            std::process::exit(1);
+                        }
+
+                        // Print the task with its new memberships
+                        match state_manager.get_task(&task_id).await {
+                            Ok(task) => {
+                                print(&output, &task);
                             }
                             Err(e) => {
                                 eprintln!("❌ Failed to get task: {e}");
@@ -199,8 +805,7 @@ async fn main() -> Result<()> {
                             .await
                         {
                             Ok(comments) => {
-                                let json = serde_json::to_string_pretty(&comments)?;
-                                println!("{json}");
+                                print_list(&output, &comments);
                             }
                             Err(e) => {
                                 eprintln!("❌ Failed to list stories: {e}");
@@ -225,8 +830,7 @@ async fn main() -> Result<()> {
                             .await
                         {
                             Ok(comments) => {
-                                let json = serde_json::to_string_pretty(&comments)?;
-                                println!("{json}");
+                                print_list(&output, &comments);
                             }
                             Err(e) => {
                                 eprintln!("❌ Failed to list stories: {e}");
@@ -241,6 +845,94 @@ async fn main() -> Result<()> {
                 }
             }
         }
+        Some(("workspaces", workspaces_matches)) => match workspaces_matches.subcommand() {
+            Some(("list", _)) => match state_manager.list_workspaces().await {
+                Ok(workspaces) => {
+                    print_list(&output, &workspaces);
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to list workspaces: {e}");
+                    std::process::exit(1);
+                }
+            },
+            _ => {
+                eprintln!("❌ Unknown workspaces subcommand");
+                std::process::exit(1);
+            }
+        },
+        Some(("projects", projects_matches)) => match projects_matches.subcommand() {
+            Some(("list", list_matches)) => {
+                state_manager.initialize().await?;
+
+                match state_manager
+                    .list_projects(list_matches.get_flag("archived"))
+                    .await
+                {
+                    Ok(projects) => {
+                        print_list(&output, &projects);
+                    }
+                    Err(e) => {
+                        eprintln!("❌ Failed to list projects: {e}");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            Some(("get", get_matches)) => {
+                if let Some(project_id) = get_matches.get_one::<String>("project_id") {
+                    match state_manager.get_project(&project_id.as_str().into()).await {
+                        Ok(project) => {
+                            print(&output, &project);
+                        }
+                        Err(e) => {
+                            eprintln!("❌ Failed to get project: {e}");
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+            Some(("sections", sections_matches)) => {
+                if let Some(project_id) = sections_matches.get_one::<String>("project_id") {
+                    match state_manager
+                        .list_sections(&project_id.as_str().into())
+                        .await
+                    {
+                        Ok(sections) => {
+                            print_list(&output, &sections);
+                        }
+                        Err(e) => {
+                            eprintln!("❌ Failed to list sections: {e}");
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+            _ => {
+                eprintln!("❌ Unknown projects subcommand");
+                std::process::exit(1);
+            }
+        },
+        Some(("search", search_matches)) => {
+            state_manager.initialize().await?;
+
+            let me = state_manager.get_current_user().await.map(|user| user.id);
+            let search = match workspace_search_from_args(search_matches, me) {
+                Ok(search) => search,
+                Err(e) => {
+                    eprintln!("❌ {e}");
+                    std::process::exit(1);
+                }
+            };
+
+            match state_manager.search_tasks(&search).await {
+                Ok(tasks) => {
+                    print_list(&output, &tasks);
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to search tasks: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
         None => {
             // Default behavior - run TUI
             let app = App::new(state_manager);
@@ -271,3 +963,124 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Print one record in the chosen output format, exiting on an unusable `--fields`
+fn print<T: Record>(output: &Output, record: &T) {
+    if let Err(e) = output.print(record) {
+        eprintln!("❌ {e}");
+        std::process::exit(1);
+    }
+}
+
+/// Print a list of records in the chosen output format, exiting on an unusable `--fields`
+fn print_list<T: Record>(output: &Output, records: &[T]) {
+    if let Err(e) = output.print_list(records) {
+        eprintln!("❌ {e}");
+        std::process::exit(1);
+    }
+}
+
+/// Narrow `base` (my incomplete tasks) with the `tasks list` flags
+fn task_filter_from_args(
+    matches: &clap::ArgMatches,
+    base: TaskFilter,
+) -> std::result::Result<TaskFilter, String> {
+    let date = |name: &str| {
+        matches
+            .get_one::<String>(name)
+            .map(|d| parse_date(d))
+            .transpose()
+    };
+    let zone = Zone::current();
+    let me = base.assignee.clone();
+
+    let mut filter = TaskFilter {
+        tag: matches.get_one::<String>("tag").cloned(),
+        completed_since: date("completed_since")?.map(|d| zone.start_of(d)),
+        modified_since: date("modified_since")?.map(|d| zone.start_of(d)),
+        due_before: date("due_before")?,
+        due_after: date("due_after")?,
+        limit: matches.get_one::<usize>("limit").copied(),
+        ..base
+    };
+
+    if let Some(project) = matches.get_one::<String>("project") {
+        filter.project = Some(project.as_str().into());
+        // Project listings cover everyone's tasks unless --assignee narrows them
+        filter.assignee = None;
+    }
+    match matches.get_one::<String>("assignee").map(String::as_str) {
+        Some("me") => filter.assignee = me,
+        Some(assignee) => filter.assignee = Some(assignee.into()),
+        None => {}
+    }
+
+    filter.completed = if matches.get_flag("completed") {
+        Some(true)
+    } else if matches.get_flag("all") || filter.completed_since.is_some() {
+        None
+    } else {
+        Some(false)
+    };
+
+    if let Some(sort) = matches.get_one::<String>("sort") {
+        filter.sort = sort.parse()?;
+    }
+
+    Ok(filter)
+}
+
+/// Build a workspace search from the `search` flags; `me` stands in for `--assignee me`
+fn workspace_search_from_args(
+    matches: &clap::ArgMatches,
+    me: Option<UserId>,
+) -> std::result::Result<WorkspaceSearch, String> {
+    let date = |name: &str| {
+        matches
+            .get_one::<String>(name)
+            .map(|d| parse_date(d))
+            .transpose()
+    };
+    let many = |name: &str| {
+        matches
+            .get_many::<String>(name)
+            .map(|values| values.cloned().collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+
+    let assignees = many("assignee")
+        .into_iter()
+        .map(|assignee| match assignee.as_str() {
+            "me" => me
+                .clone()
+                .ok_or_else(|| "Current user not loaded".to_string()),
+            _ => Ok(assignee.into()),
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(WorkspaceSearch {
+        text: matches.get_one::<String>("text").cloned(),
+        assignees,
+        projects: many("project").into_iter().map(Into::into).collect(),
+        tags: many("tag"),
+        due_before: date("due_before")?,
+        due_after: date("due_after")?,
+        completed: if matches.get_flag("completed") {
+            Some(true)
+        } else if matches.get_flag("all") {
+            None
+        } else {
+            Some(false)
+        },
+        custom_fields: many("cf")
+            .iter()
+            .map(|condition| condition.parse())
+            .collect::<std::result::Result<_, _>>()?,
+        sort_by: matches
+            .get_one::<String>("sort")
+            .map(|sort| sort.parse())
+            .transpose()?,
+        sort_ascending: matches.get_flag("asc"),
+        limit: matches.get_one::<usize>("limit").copied(),
+    })
+}
@@ -10,8 +10,9 @@ where
     async fn get(&self, key: &K) -> Option<V>;
     async fn insert(&self, key: K, value: V);
     async fn remove(&self, key: &K);
-    #[allow(dead_code)] // Used through trait objects
     async fn clear(&self);
+    /// Every key with an entry, possibly including some too old to be returned
+    async fn keys(&self) -> Vec<K>;
     #[allow(dead_code)] // Used through trait objects
     async fn contains_key(&self, key: &K) -> bool;
 }
@@ -19,18 +19,30 @@ pub type ConfigResult<T> = Result<T, ConfigError>;
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub api_token: Option<String>,
+    /// Alternative API root (e.g. a proxy or a local mock server)
+    pub api_base_url: Option<String>,
     pub default_workspace: Option<WorkspaceId>,
     pub cache_ttl_seconds: u64,
     pub max_tasks_per_page: usize,
+    /// Total attempts per API request, including the first
+    pub retry_max_attempts: u32,
+    /// Stop retrying once a request has been in flight this long
+    pub retry_deadline_seconds: u64,
+    /// IANA zone (e.g. `Europe/Berlin`) for due dates; the system zone when unset
+    pub time_zone: Option<String>,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             api_token: None,
+            api_base_url: None,
             default_workspace: None,
             cache_ttl_seconds: 300, // 5 minutes
             max_tasks_per_page: 50,
+            retry_max_attempts: 4,
+            retry_deadline_seconds: 90,
+            time_zone: None,
         }
     }
 }
@@ -1,7 +1,9 @@
 pub mod cache;
 pub mod config_store;
+pub mod outbox;
 pub mod task_repository;
 
 pub use cache::*;
 pub use config_store::*;
+pub use outbox::*;
 pub use task_repository::*;
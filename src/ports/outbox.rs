@@ -0,0 +1,25 @@
+use crate::domain::PendingChange;
+use async_trait::async_trait;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum OutboxError {
+    #[error("Failed to read outbox: {0}")]
+    ReadError(String),
+
+    #[error("Failed to write outbox: {0}")]
+    WriteError(String),
+}
+
+pub type OutboxResult<T> = Result<T, OutboxError>;
+
+/// Durable queue of changes made offline, replayed in order once Asana is reachable
+#[async_trait]
+pub trait Outbox: Send + Sync {
+    /// Append a change; it must survive a restart once this returns
+    async fn push(&self, change: PendingChange) -> OutboxResult<()>;
+    /// Every waiting change, oldest first
+    async fn pending(&self) -> OutboxResult<Vec<PendingChange>>;
+    /// Drop a change that was sent or given up on
+    async fn remove(&self, id: &str) -> OutboxResult<()>;
+}
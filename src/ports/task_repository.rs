@@ -1,4 +1,7 @@
-use crate::domain::{Comment, Task, TaskFilter, TaskId, TaskUpdate, User, Workspace};
+use crate::domain::{
+    Comment, EventPage, NewTask, Page, Project, ProjectId, Section, SectionId, Task, TaskFilter,
+    TaskId, TaskUpdate, User, Workspace, WorkspaceId, WorkspaceSearch,
+};
 use async_trait::async_trait;
 use thiserror::Error;
 
@@ -21,6 +24,11 @@ pub enum RepositoryError {
 
     #[error("Serialization error: {0}")]
     Serialization(String),
+
+    /// The event sync token was missing or too old; carries a fresh one that
+    /// picks up from now
+    #[error("Event sync token expired")]
+    SyncExpired(String),
 }
 
 pub type RepositoryResult<T> = Result<T, RepositoryError>;
@@ -28,10 +36,19 @@ pub type RepositoryResult<T> = Result<T, RepositoryError>;
 #[async_trait]
 pub trait TaskRepository: Send + Sync {
     async fn get_task(&self, id: &TaskId) -> RepositoryResult<Task>;
-    async fn list_tasks(&self, filter: &TaskFilter) -> RepositoryResult<Vec<Task>>;
+    /// List tasks matching `filter`, following pagination up to `filter.limit` items
+    async fn list_tasks(&self, filter: &TaskFilter) -> RepositoryResult<Page<Task>>;
+    /// Search a whole workspace server-side, returning at most `search.result_limit()` tasks
+    async fn search_tasks(
+        &self,
+        workspace: &WorkspaceId,
+        search: &WorkspaceSearch,
+    ) -> RepositoryResult<Vec<Task>>;
+    async fn create_task(&self, task: &NewTask) -> RepositoryResult<Task>;
     async fn update_task(&self, id: &TaskId, updates: &TaskUpdate) -> RepositoryResult<Task>;
+    /// Direct subtasks of a task, in the order Asana shows them
+    async fn list_subtasks(&self, parent: &TaskId) -> RepositoryResult<Vec<Task>>;
     async fn get_task_comments(&self, task_id: &TaskId) -> RepositoryResult<Vec<Comment>>;
-    #[allow(dead_code)] // Might be used when comment creation is added to TUI
     async fn create_comment(&self, task_id: &TaskId, content: &str) -> RepositoryResult<Comment>;
 }
 
@@ -39,4 +56,31 @@ pub trait TaskRepository: Send + Sync {
 pub trait WorkspaceRepository: Send + Sync {
     async fn list_workspaces(&self) -> RepositoryResult<Vec<Workspace>>;
     async fn get_current_user(&self) -> RepositoryResult<User>;
+    /// Everyone in a workspace, e.g. to choose an assignee
+    async fn list_users(&self, workspace: &WorkspaceId) -> RepositoryResult<Vec<User>>;
+}
+
+#[async_trait]
+pub trait ProjectRepository: Send + Sync {
+    /// List the projects in a workspace, skipping archived ones unless asked for
+    async fn list_projects(
+        &self,
+        workspace: &WorkspaceId,
+        include_archived: bool,
+    ) -> RepositoryResult<Vec<Project>>;
+    async fn get_project(&self, id: &ProjectId) -> RepositoryResult<Project>;
+    /// Sections of a project in board order
+    async fn list_sections(&self, project: &ProjectId) -> RepositoryResult<Vec<Section>>;
+    /// Put a task in a section, moving it out of any other section of that project
+    async fn add_task_to_section(&self, section: &SectionId, task: &TaskId)
+        -> RepositoryResult<()>;
+}
+
+#[async_trait]
+pub trait EventRepository: Send + Sync {
+    /// Task events on a project or user task list since `sync`; without a
+    /// token, or once it expires, fails with `SyncExpired`
+    async fn get_events(&self, resource: &str, sync: Option<&str>) -> RepositoryResult<EventPage>;
+    /// The gid of the signed-in user's My Tasks list in `workspace`
+    async fn get_user_task_list(&self, workspace: &WorkspaceId) -> RepositoryResult<String>;
 }
@@ -0,0 +1,427 @@
+//! In-process fake of the Asana REST API for tests.
+//!
+//! Speaks just enough HTTP/1.1 for reqwest: one request per connection and
+//! `Connection: close` on every response. Routes are matched on method and path
+//! (query string excluded). One-shot responses queued with [`FakeAsana::enqueue`]
+//! take priority over the standing fixture for a route, which makes it easy to
+//! script "fail once, then succeed" sequences.
+//!
+//! This file only depends on external crates so integration tests under `tests/`
+//! can pull it in with `#[path]`.
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+#[derive(Debug, Clone)]
+pub struct FakeResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl FakeResponse {
+    pub fn json(status: u16, body: Value) -> Self {
+        Self::raw(status, &body.to_string())
+    }
+
+    /// A 200 response wrapped in Asana's `{"data": ...}` envelope
+    pub fn data(data: Value) -> Self {
+        Self::json(200, json!({ "data": data }))
+    }
+
+    pub fn raw(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+
+    /// An error in Asana's `{"errors": [...]}` shape
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(status, json!({ "errors": [{ "message": message }] }))
+    }
+
+    pub fn rate_limited(retry_after_secs: u64) -> Self {
+        Self::error(429, "Rate limit exceeded")
+            .with_header("Retry-After", &retry_after_secs.to_string())
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub authorization: Option<String>,
+    pub body: Option<Value>,
+}
+
+enum Fixture {
+    Fixed(FakeResponse),
+    /// Served with Asana's offset pagination, at most `max_page_size` per page
+    Paginated {
+        items: Vec<Value>,
+        max_page_size: usize,
+    },
+}
+
+type RouteKey = (String, String);
+
+#[derive(Default)]
+struct State {
+    fixtures: HashMap<RouteKey, Fixture>,
+    queued: HashMap<RouteKey, VecDeque<FakeResponse>>,
+    requests: Vec<RecordedRequest>,
+}
+
+pub struct FakeAsana {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    server: JoinHandle<()>,
+}
+
+impl FakeAsana {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind fake Asana server");
+        let addr = listener.local_addr().expect("Fake server has no address");
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let server_state = state.clone();
+        let server = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = server_state.clone();
+                tokio::spawn(async move {
+                    let _ = serve_connection(stream, state).await;
+                });
+            }
+        });
+
+        Self {
+            addr,
+            state,
+            server,
+        }
+    }
+
+    /// A server pre-loaded with one user, one workspace, and the given tasks
+    /// listed at `/tasks` and individually at `/tasks/{gid}`
+    pub async fn with_defaults(tasks: Vec<Value>) -> Self {
+        let fake = Self::start().await;
+        fake.on(
+            "GET",
+            "/users/me",
+            FakeResponse::data(user("u1", "Test User")),
+        );
+        fake.on_list("/workspaces", vec![workspace("w1", "Test Workspace")]);
+        for task in &tasks {
+            let gid = task["gid"].as_str().expect("Task fixture without gid");
+            fake.on(
+                "GET",
+                &format!("/tasks/{gid}"),
+                FakeResponse::data(task.clone()),
+            );
+        }
+        fake.on_list("/tasks", tasks);
+        fake
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Serve `response` for every `method path` request without a queued response
+    pub fn on(&self, method: &str, path: &str, response: FakeResponse) {
+        self.state
+            .lock()
+            .unwrap()
+            .fixtures
+            .insert(route(method, path), Fixture::Fixed(response));
+    }
+
+    /// Serve `items` as a paginated `GET path` listing
+    pub fn on_list(&self, path: &str, items: Vec<Value>) {
+        self.on_paginated(path, items, 100);
+    }
+
+    /// Like [`Self::on_list`], but never return more than `max_page_size` items per page
+    pub fn on_paginated(&self, path: &str, items: Vec<Value>, max_page_size: usize) {
+        self.state.lock().unwrap().fixtures.insert(
+            route("GET", path),
+            Fixture::Paginated {
+                items,
+                max_page_size,
+            },
+        );
+    }
+
+    /// Serve `response` to the next `method path` request only
+    pub fn enqueue(&self, method: &str, path: &str, response: FakeResponse) {
+        self.state
+            .lock()
+            .unwrap()
+            .queued
+            .entry(route(method, path))
+            .or_default()
+            .push_back(response);
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method && r.path == path)
+            .collect()
+    }
+}
+
+impl Drop for FakeAsana {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+fn route(method: &str, path: &str) -> RouteKey {
+    (method.to_uppercase(), path.to_string())
+}
+
+async fn serve_connection(mut stream: TcpStream, state: Arc<Mutex<State>>) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+
+    let response = respond(&state, &request);
+    state.lock().unwrap().requests.push(request);
+
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        reason_phrase(response.status),
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(response.body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<RecordedRequest>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    for line in lines {
+        if let Some((name, value)) = line.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.trim().parse().unwrap_or(0),
+                "authorization" => authorization = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    while buffer.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    let body = &buffer[header_end..];
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(k), decode(v))
+        })
+        .collect();
+
+    Ok(Some(RecordedRequest {
+        method,
+        path: path.to_string(),
+        query,
+        authorization,
+        body: serde_json::from_slice(body).ok(),
+    }))
+}
+
+fn decode(s: &str) -> String {
+    urlencoding::decode(s)
+        .map(|d| d.into_owned())
+        .unwrap_or_else(|_| s.to_string())
+}
+
+fn respond(state: &Arc<Mutex<State>>, request: &RecordedRequest) -> FakeResponse {
+    let mut state = state.lock().unwrap();
+    let key = route(&request.method, &request.path);
+
+    if let Some(response) = state.queued.get_mut(&key).and_then(|q| q.pop_front()) {
+        return response;
+    }
+
+    match state.fixtures.get(&key) {
+        Some(Fixture::Fixed(response)) => response.clone(),
+        Some(Fixture::Paginated {
+            items,
+            max_page_size,
+        }) => paginate(request, items, *max_page_size),
+        None => FakeResponse::error(404, "Unknown object"),
+    }
+}
+
+/// Serve one page the way Asana does: `limit` sizes the page and `offset` is an
+/// opaque token handed out in the previous page's `next_page`
+fn paginate(request: &RecordedRequest, items: &[Value], max_page_size: usize) -> FakeResponse {
+    let start = match request.query.get("offset") {
+        None => 0,
+        Some(token) => match token.strip_prefix("cursor-").and_then(|n| n.parse().ok()) {
+            Some(start) => start,
+            None => return FakeResponse::error(400, "offset: Your pagination token is invalid"),
+        },
+    };
+    let limit = request
+        .query
+        .get("limit")
+        .and_then(|l| l.parse::<usize>().ok())
+        .unwrap_or(max_page_size)
+        .min(max_page_size);
+
+    let end = (start + limit).min(items.len());
+    let page: Vec<Value> = items.get(start..end).unwrap_or_default().to_vec();
+
+    let next_page = if end < items.len() {
+        let offset = format!("cursor-{end}");
+        let path = format!("{}?limit={limit}&offset={offset}", request.path);
+        json!({ "offset": offset, "path": path, "uri": format!("https://app.asana.com/api/1.0{path}") })
+    } else {
+        Value::Null
+    };
+
+    FakeResponse::json(200, json!({ "data": page, "next_page": next_page }))
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        412 => "Precondition Failed",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+// Fixture builders shaped like the compact records the API returns for our opt_fields
+
+pub fn task(gid: &str, name: &str) -> Value {
+    json!({
+        "gid": gid,
+        "name": name,
+        "notes": "",
+        "html_notes": "<body></body>",
+        "completed": false,
+        "due_on": null,
+        "due_at": null,
+        "start_on": null,
+        "assignee": { "gid": "u1", "name": "Test User" },
+        "projects": [],
+        "tags": [],
+        "created_at": "2025-01-01T12:00:00.000Z",
+        "modified_at": "2025-01-02T12:00:00.000Z",
+        "workspace": { "gid": "w1", "name": "Test Workspace" },
+        "resource_type": "task",
+        "resource_subtype": "default_task",
+        "custom_fields": [],
+        "dependencies": [],
+        "memberships": [],
+        "parent": null,
+        "num_subtasks": 0
+    })
+}
+
+pub fn user(gid: &str, name: &str) -> Value {
+    json!({
+        "gid": gid,
+        "name": name,
+        "email": format!("{gid}@example.com"),
+        "photo": null
+    })
+}
+
+pub fn workspace(gid: &str, name: &str) -> Value {
+    json!({ "gid": gid, "name": name, "is_organization": true })
+}
+
+pub fn project(gid: &str, name: &str) -> Value {
+    json!({
+        "gid": gid,
+        "name": name,
+        "notes": "",
+        "color": "light-green",
+        "archived": false,
+        "workspace": { "gid": "w1", "name": "Test Workspace" },
+        "created_at": "2025-01-01T12:00:00.000Z",
+        "modified_at": "2025-01-02T12:00:00.000Z"
+    })
+}
+
+pub fn section(gid: &str, name: &str) -> Value {
+    json!({ "gid": gid, "name": name, "project": { "gid": "p1" } })
+}
+
+pub fn story(gid: &str, text: &str) -> Value {
+    json!({
+        "gid": gid,
+        "text": text,
+        "created_by": { "gid": "u1", "name": "Test User" },
+        "created_at": "2025-01-03T09:30:00.000Z",
+        "type": "comment",
+        "resource_subtype": "comment_added"
+    })
+}
@@ -0,0 +1,522 @@
+//! Drive the `asana-cli` binary end to end against the in-process fake Asana server
+
+#[path = "../src/test_support/fake_asana.rs"]
+mod fake_asana;
+
+use fake_asana::{FakeAsana, FakeResponse};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::Output;
+
+/// A throwaway HOME/XDG config dir so tests never touch the real configuration
+struct Sandbox {
+    dir: PathBuf,
+}
+
+impl Sandbox {
+    fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("asana-cli-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self { dir }
+    }
+
+    async fn run(&self, fake: &FakeAsana, args: &[&str]) -> Output {
+        tokio::process::Command::new(env!("CARGO_BIN_EXE_asana-cli"))
+            .args(args)
+            .current_dir(&self.dir)
+            .env("HOME", &self.dir)
+            .env("XDG_CONFIG_HOME", self.dir.join("config"))
+            .env("XDG_CACHE_HOME", self.dir.join("cache"))
+            .env("ASANA_TOKEN", "test-token")
+            .env("ASANA_API_BASE", fake.base_url())
+            .output()
+            .await
+            .expect("Failed to run asana-cli")
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn stdout_json(output: &Output) -> Value {
+    assert!(
+        output.status.success(),
+        "asana-cli failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("stdout was not JSON")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_list_prints_every_page() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    let tasks = (1..=5)
+        .map(|i| fake_asana::task(&i.to_string(), &format!("Task {i}")))
+        .collect();
+    fake.on_paginated("/tasks", tasks, 2);
+    let sandbox = Sandbox::new();
+
+    let output = sandbox.run(&fake, &["tasks", "list"]).await;
+
+    let json = stdout_json(&output);
+    assert_eq!(json.as_array().unwrap().len(), 5);
+    assert_eq!(fake.requests_to("GET", "/tasks").len(), 3);
+    let request = &fake.requests_to("GET", "/tasks")[0];
+    assert_eq!(request.query.get("workspace").unwrap(), "w1");
+    assert_eq!(request.query.get("assignee").unwrap(), "u1");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_list_filters_sorts_and_limits() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    let mut tasks = Vec::new();
+    for (gid, name, tag, due) in [
+        ("1", "Zebra", "bug", "2026-10-20"),
+        ("2", "apple", "bug", "2026-10-21"),
+        ("3", "Mango", "bug", "2026-12-01"),
+        ("4", "Banana", "docs", "2026-10-20"),
+        ("5", "Cherry", "bug", "2026-10-22"),
+    ] {
+        let mut task = fake_asana::task(gid, name);
+        task["tags"] = serde_json::json!([{ "gid": "t1", "name": tag }]);
+        task["due_on"] = serde_json::json!(due);
+        task["projects"] = serde_json::json!([{ "gid": "p1", "name": "Roadmap" }]);
+        tasks.push(task);
+    }
+    fake.on_paginated("/tasks", tasks, 2);
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(
+            &fake,
+            &[
+                "tasks",
+                "list",
+                "--project",
+                "p1",
+                "--tag",
+                "BUG",
+                "--due-before",
+                "2026-11-01",
+                "--sort",
+                "name",
+                "--limit",
+                "2",
+            ],
+        )
+        .await;
+
+    let names: Vec<_> = stdout_json(&output)
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, ["apple", "Cherry"]);
+
+    // Tag and due filters are local, so every page was fetched
+    let requests = fake.requests_to("GET", "/tasks");
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].query.get("project").unwrap(), "p1");
+    assert_eq!(requests[0].query.get("assignee"), None);
+    assert_eq!(requests[0].query.get("limit").unwrap(), "100");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_task_lists_and_user_are_cached_on_disk_per_user_and_workspace() {
+    let fake = FakeAsana::with_defaults(vec![fake_asana::task("1", "Cached")]).await;
+    let sandbox = Sandbox::new();
+
+    let output = sandbox.run(&fake, &["tasks", "list"]).await;
+    assert!(output.status.success());
+
+    let cache = sandbox.dir.join("cache").join("asana-cli");
+    assert!(cache.join("session").join("current_user.json").exists());
+    let lists: Vec<_> = std::fs::read_dir(cache.join("tasks").join("u1").join("w1"))
+        .unwrap()
+        .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+        .collect();
+    assert_eq!(lists.len(), 1);
+    assert!(lists[0].contains("\"Cached\""));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_list_all_drops_the_completed_filter() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    let mut done = fake_asana::task("2", "Done");
+    done["completed"] = serde_json::json!(true);
+    fake.on_list("/tasks", vec![fake_asana::task("1", "Open"), done]);
+    let sandbox = Sandbox::new();
+
+    let output = sandbox.run(&fake, &["tasks", "list", "--all"]).await;
+
+    assert_eq!(stdout_json(&output).as_array().unwrap().len(), 2);
+    let request = &fake.requests_to("GET", "/tasks")[0];
+    assert_eq!(request.query.get("completed_since"), None);
+    assert_eq!(request.query.get("assignee").unwrap(), "u1");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_search_queries_the_workspace_search_endpoint() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    fake.on_list(
+        "/workspaces/w1/tasks/search",
+        vec![fake_asana::task("7", "Quarterly report")],
+    );
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(
+            &fake,
+            &[
+                "search",
+                "report",
+                "--assignee",
+                "me",
+                "--assignee",
+                "u2",
+                "--cf",
+                "cf1~draft",
+                "--sort",
+                "created",
+                "--limit",
+                "5",
+            ],
+        )
+        .await;
+
+    let tasks = stdout_json(&output);
+    assert_eq!(tasks[0]["name"], "Quarterly report");
+
+    let request = &fake.requests_to("GET", "/workspaces/w1/tasks/search")[0];
+    assert_eq!(request.query.get("text").unwrap(), "report");
+    assert_eq!(request.query.get("assignee.any").unwrap(), "u1,u2");
+    assert_eq!(
+        request.query.get("custom_fields.cf1.contains").unwrap(),
+        "draft"
+    );
+    assert_eq!(request.query.get("completed").unwrap(), "false");
+    assert_eq!(request.query.get("sort_by").unwrap(), "created_at");
+    assert_eq!(request.query.get("limit").unwrap(), "5");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_list_as_csv_with_custom_field_columns() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    let mut task = fake_asana::task("1", "Fix login, again");
+    task["custom_fields"] = serde_json::json!([
+        { "gid": "cf1", "name": "Priority", "display_value": "High" }
+    ]);
+    fake.on_list("/tasks", vec![task, fake_asana::task("2", "Write docs")]);
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(
+            &fake,
+            &[
+                "tasks",
+                "list",
+                "--output",
+                "csv",
+                "--fields",
+                "id,name,Priority",
+            ],
+        )
+        .await;
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "id,name,Priority\n1,\"Fix login, again\",High\n2,Write docs,\n"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_get_through_a_template() {
+    let mut task = fake_asana::task("123", "Fix the build");
+    task["due_on"] = serde_json::json!("2026-10-16");
+    let fake = FakeAsana::with_defaults(vec![task]).await;
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(
+            &fake,
+            &[
+                "tasks",
+                "get",
+                "123",
+                "--format",
+                r#"{{id}}\t{{name | truncate 7}}\t{{due | date "%a"}}\t{{start | default "-"}}"#,
+            ],
+        )
+        .await;
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "123\tFix th…\tFri\t-\n"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unknown_field_is_rejected() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    fake.on_list("/workspaces", vec![fake_asana::workspace("w1", "Company")]);
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(&fake, &["workspaces", "list", "--fields", "id,owner"])
+        .await;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown field 'owner'"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_get_prints_the_task() {
+    let fake = FakeAsana::with_defaults(vec![fake_asana::task("123", "Fix the build")]).await;
+    let sandbox = Sandbox::new();
+
+    let output = sandbox.run(&fake, &["tasks", "get", "123"]).await;
+
+    let json = stdout_json(&output);
+    assert_eq!(json["id"], "123");
+    assert_eq!(json["name"], "Fix the build");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_stories_list_prints_comments() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    fake.on_list(
+        "/tasks/123/stories",
+        vec![fake_asana::story("s1", "Looks good")],
+    );
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(&fake, &["stories", "list", "--task", "123"])
+        .await;
+
+    let json = stdout_json(&output);
+    assert_eq!(json[0]["text"], "Looks good");
+    assert_eq!(json[0]["task_id"], "123");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_invalid_token_fails_with_nonzero_exit() {
+    let fake = FakeAsana::start().await;
+    fake.on(
+        "GET",
+        "/users/me",
+        FakeResponse::error(401, "Not Authorized"),
+    );
+    let sandbox = Sandbox::new();
+
+    let output = sandbox.run(&fake, &["tasks", "list"]).await;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Authentication failed"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_missing_task_reports_error() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    let sandbox = Sandbox::new();
+
+    let output = sandbox.run(&fake, &["tasks", "get", "404"]).await;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to get task"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_create_posts_and_prints_the_task() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    fake.on(
+        "POST",
+        "/tasks",
+        FakeResponse::data(fake_asana::task("77", "Ship it")),
+    );
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(
+            &fake,
+            &[
+                "tasks",
+                "create",
+                "--name",
+                "Ship it",
+                "--project",
+                "p1",
+                "--project",
+                "p2",
+                "--due",
+                "2025-07-04",
+                "--assignee",
+                "me",
+            ],
+        )
+        .await;
+
+    let json = stdout_json(&output);
+    assert_eq!(json["id"], "77");
+    let body = fake.requests_to("POST", "/tasks")[0].body.clone().unwrap();
+    assert_eq!(body["data"]["name"], "Ship it");
+    assert_eq!(body["data"]["projects"], serde_json::json!(["p1", "p2"]));
+    assert_eq!(body["data"]["due_on"], "2025-07-04");
+    assert_eq!(body["data"]["assignee"], "u1");
+    assert!(body["data"].get("workspace").is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_create_with_parent_makes_a_subtask() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    let mut subtask = fake_asana::task("78", "Write changelog");
+    subtask["parent"] = serde_json::json!({ "gid": "77" });
+    fake.on("POST", "/tasks/77/subtasks", FakeResponse::data(subtask));
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(
+            &fake,
+            &[
+                "tasks",
+                "create",
+                "--name",
+                "Write changelog",
+                "--parent",
+                "77",
+            ],
+        )
+        .await;
+
+    let json = stdout_json(&output);
+    assert_eq!(json["parent"], "77");
+    let body = fake.requests_to("POST", "/tasks/77/subtasks")[0]
+        .body
+        .clone()
+        .unwrap();
+    assert_eq!(body["data"]["name"], "Write changelog");
+    assert!(body["data"].get("workspace").is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_update_sets_due_date_and_unassigns() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    fake.on(
+        "PUT",
+        "/tasks/77",
+        FakeResponse::data(fake_asana::task("77", "Ship it")),
+    );
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(
+            &fake,
+            &[
+                "tasks",
+                "update",
+                "77",
+                "--due",
+                "2026-11-01",
+                "--assignee",
+                "none",
+                "--start",
+                "2026-10-26",
+            ],
+        )
+        .await;
+
+    assert_eq!(stdout_json(&output)["id"], "77");
+    let body = fake.requests_to("PUT", "/tasks/77")[0]
+        .body
+        .clone()
+        .unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "data": { "due_on": "2026-11-01", "start_on": "2026-10-26", "assignee": null }
+        })
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tasks_create_rejects_bad_due_date() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    let sandbox = Sandbox::new();
+
+    let output = sandbox
+        .run(&fake, &["tasks", "create", "--name", "x", "--due", "soon"])
+        .await;
+
+    assert!(!output.status.success());
+    assert!(fake.requests_to("POST", "/tasks").is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_workspaces_list_prints_every_workspace() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    fake.on_list(
+        "/workspaces",
+        vec![
+            fake_asana::workspace("w1", "Company"),
+            fake_asana::workspace("w2", "Personal"),
+        ],
+    );
+    let sandbox = Sandbox::new();
+
+    let output = sandbox.run(&fake, &["workspaces", "list"]).await;
+
+    let json = stdout_json(&output);
+    assert_eq!(json[1]["id"], "w2");
+    assert_eq!(json[1]["name"], "Personal");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_projects_list_is_sorted_by_name() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    fake.on_list(
+        "/projects",
+        vec![
+            fake_asana::project("p1", "roadmap"),
+            fake_asana::project("p2", "Launch"),
+        ],
+    );
+    let sandbox = Sandbox::new();
+
+    let output = sandbox.run(&fake, &["projects", "list"]).await;
+
+    let json = stdout_json(&output);
+    assert_eq!(json[0]["name"], "Launch");
+    assert_eq!(json[1]["id"], "p1");
+    let request = &fake.requests_to("GET", "/projects")[0];
+    assert_eq!(request.query.get("workspace").unwrap(), "w1");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multiple_workspaces_require_a_choice() {
+    let fake = FakeAsana::with_defaults(vec![]).await;
+    fake.on_list(
+        "/workspaces",
+        vec![
+            fake_asana::workspace("w1", "Company"),
+            fake_asana::workspace("w2", "Personal"),
+        ],
+    );
+    let sandbox = Sandbox::new();
+
+    let output = sandbox.run(&fake, &["tasks", "list"]).await;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("workspaces list"));
+
+    let output = sandbox
+        .run(&fake, &["tasks", "list", "--workspace", "w2"])
+        .await;
+    stdout_json(&output);
+    let request = &fake.requests_to("GET", "/tasks")[0];
+    assert_eq!(request.query.get("workspace").unwrap(), "w2");
+}